@@ -0,0 +1,11 @@
+-- Template lifecycle: archived templates are hidden from new submissions
+ALTER TABLE templates
+    ADD COLUMN is_archived BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN archived_at TIMESTAMPTZ;
+
+-- Track which template an approval request was created from.
+-- RESTRICT keeps a referenced template from being deleted (archive it instead).
+ALTER TABLE pxm_approval_requests
+    ADD COLUMN template_id UUID REFERENCES templates(id) ON DELETE RESTRICT;
+
+CREATE INDEX idx_pxm_requests_template_id ON pxm_approval_requests(template_id);
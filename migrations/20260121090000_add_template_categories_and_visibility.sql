@@ -0,0 +1,31 @@
+-- 1. Template Categories (HR, Finance, IT ...)
+CREATE TABLE template_categories (
+    code VARCHAR(50) PRIMARY KEY, -- e.g. HR, FINANCE
+    name VARCHAR(100) NOT NULL,
+    sort_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO template_categories (code, name, sort_order)
+VALUES
+    ('GENERAL', 'General', 0),
+    ('HR', 'HR', 10),
+    ('FINANCE', 'Finance', 20),
+    ('IT', 'IT', 30)
+ON CONFLICT (code) DO NOTHING;
+
+ALTER TABLE templates
+    ADD COLUMN category_code VARCHAR(50) REFERENCES template_categories(code) ON DELETE SET NULL;
+
+CREATE INDEX idx_templates_category ON templates(category_code);
+
+-- 2. Department-scoped visibility
+-- No rows for a template => visible to everyone.
+-- A row for department D => visible to members of D and all of its child departments.
+CREATE TABLE template_visibility (
+    template_id UUID NOT NULL REFERENCES templates(id) ON DELETE CASCADE,
+    department_id UUID NOT NULL REFERENCES departments(id) ON DELETE CASCADE,
+    PRIMARY KEY (template_id, department_id)
+);
+
+CREATE INDEX idx_template_visibility_department ON template_visibility(department_id);
@@ -0,0 +1,2 @@
+-- e.g. "[Expense] {{form.purpose}} - {{requester.full_name}} ({{date:%Y-%m-%d}})"
+ALTER TABLE templates ADD COLUMN title_pattern VARCHAR(500);
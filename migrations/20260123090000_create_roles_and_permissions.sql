@@ -0,0 +1,62 @@
+-- 1. Roles & Permissions
+CREATE TABLE roles (
+    code VARCHAR(50) PRIMARY KEY, -- e.g. ADMIN, TEMPLATE_MANAGER
+    name VARCHAR(100) NOT NULL,
+    description TEXT
+);
+
+CREATE TABLE permissions (
+    code VARCHAR(100) PRIMARY KEY, -- e.g. template:manage
+    description TEXT
+);
+
+CREATE TABLE role_permissions (
+    role_code VARCHAR(50) NOT NULL REFERENCES roles(code) ON DELETE CASCADE,
+    permission_code VARCHAR(100) NOT NULL REFERENCES permissions(code) ON DELETE CASCADE,
+    PRIMARY KEY (role_code, permission_code)
+);
+
+CREATE TABLE user_roles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role_code VARCHAR(50) NOT NULL REFERENCES roles(code) ON DELETE CASCADE,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, role_code)
+);
+
+CREATE INDEX idx_user_roles_role ON user_roles(role_code);
+
+-- 2. Seed
+INSERT INTO roles (code, name, description)
+VALUES
+    ('ADMIN', 'Administrator', 'Full access to every administrative endpoint'),
+    ('TEMPLATE_MANAGER', 'Template Manager', 'Creates and maintains approval templates'),
+    ('ORG_MANAGER', 'Organization Manager', 'Manages users and departments'),
+    ('AUDITOR', 'Auditor', 'Read-only access to every approval request'),
+    ('USER', 'User', 'Regular employee')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO permissions (code, description)
+VALUES
+    ('template:manage', 'Create, update, archive, delete, import and export templates'),
+    ('user:manage', 'Register users and manage their accounts'),
+    ('org:manage', 'Manage departments'),
+    ('role:manage', 'Assign roles to users'),
+    ('approval:read_all', 'View every approval request')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_code, permission_code)
+SELECT 'ADMIN', code FROM permissions
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_code, permission_code)
+VALUES
+    ('TEMPLATE_MANAGER', 'template:manage'),
+    ('ORG_MANAGER', 'user:manage'),
+    ('ORG_MANAGER', 'org:manage'),
+    ('AUDITOR', 'approval:read_all')
+ON CONFLICT DO NOTHING;
+
+-- Seeded System Admin
+INSERT INTO user_roles (user_id, role_code)
+SELECT id, 'ADMIN' FROM users WHERE email = 'admin@pxm.com'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,7 @@
+-- Status lifecycle: draft -> pending (submitted, in progress) -> approved | rejected
+-- Until now requests were never created with an explicit status, so every existing
+-- 'draft' row was actually submitted and in progress.
+UPDATE pxm_approval_requests SET status = 'pending' WHERE status = 'draft';
+
+-- Inbox queries ("I requested", "drafts") filter by requester + status
+CREATE INDEX idx_pxm_requests_requester_status ON pxm_approval_requests(requester_id, status);
@@ -0,0 +1,52 @@
+-- Full-text search over approval requests
+-- search_text = title + string values in form_data + comment contents (approval_logs)
+-- search_vector uses the 'simple' config (no stemming) so Korean tokens are kept as-is;
+-- substring matches inside Korean compounds are covered by the pg_trgm index on search_text.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE pxm_approval_requests
+    ADD COLUMN search_text TEXT NOT NULL DEFAULT '',
+    ADD COLUMN search_vector TSVECTOR NOT NULL DEFAULT ''::tsvector;
+
+CREATE OR REPLACE FUNCTION pxm_approval_search_refresh() RETURNS trigger AS $$
+BEGIN
+    NEW.search_text := concat_ws(E'\n',
+        NEW.title,
+        (
+            SELECT string_agg(v #>> '{}', ' ')
+            FROM jsonb_path_query(NEW.form_data, 'strict $.** ? (@.type() == "string")') AS v
+        ),
+        (
+            SELECT string_agg(l.content, E'\n' ORDER BY l.created_at)
+            FROM approval_logs l
+            WHERE l.approval_id = NEW.id AND l.content IS NOT NULL
+        )
+    );
+    NEW.search_vector := to_tsvector('simple', NEW.search_text);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_pxm_approval_search
+    BEFORE INSERT OR UPDATE OF title, form_data, search_text ON pxm_approval_requests
+    FOR EACH ROW EXECUTE FUNCTION pxm_approval_search_refresh();
+
+-- Comments change the parent's document: touching search_text re-runs the trigger above.
+CREATE OR REPLACE FUNCTION pxm_approval_log_search_touch() RETURNS trigger AS $$
+BEGIN
+    UPDATE pxm_approval_requests
+    SET search_text = search_text
+    WHERE id = COALESCE(NEW.approval_id, OLD.approval_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_approval_logs_search
+    AFTER INSERT OR UPDATE OF content OR DELETE ON approval_logs
+    FOR EACH ROW EXECUTE FUNCTION pxm_approval_log_search_touch();
+
+-- Backfill existing rows
+UPDATE pxm_approval_requests SET search_text = search_text;
+
+CREATE INDEX idx_pxm_requests_search_vector ON pxm_approval_requests USING GIN (search_vector);
+CREATE INDEX idx_pxm_requests_search_trgm ON pxm_approval_requests USING GIN (search_text gin_trgm_ops);
@@ -0,0 +1,16 @@
+-- Files attached to approval requests (receipts, quotes, ...)
+-- The file body lives in the attachment storage backend under storage_key;
+-- this table only keeps metadata and the SHA-256 checksum.
+CREATE TABLE approval_attachments (
+    id UUID PRIMARY KEY,
+    approval_id UUID NOT NULL REFERENCES pxm_approval_requests(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    sha256 CHAR(64) NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    uploaded_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_approval_attachments_approval ON approval_attachments(approval_id);
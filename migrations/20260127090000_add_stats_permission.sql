@@ -0,0 +1,10 @@
+-- Organization-wide approval analytics (/stats/...)
+-- Department managers can always see statistics for the departments they manage;
+-- this permission widens the scope to the whole organization.
+INSERT INTO permissions (code, description)
+VALUES ('stats:read_all', 'View approval statistics for the whole organization')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_code, permission_code)
+VALUES ('ADMIN', 'stats:read_all')
+ON CONFLICT DO NOTHING;
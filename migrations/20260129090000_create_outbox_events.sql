@@ -0,0 +1,25 @@
+-- Transactional outbox for approval domain events
+-- ApprovalRepository writes one row per state change in the same transaction as the change;
+-- the dispatcher drains pending rows (FOR UPDATE SKIP LOCKED) and hands them to consumers
+-- (webhooks, ...). Delivery is at-least-once, so consumers must be idempotent on the event id.
+CREATE TABLE outbox_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    approval_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    -- pending -> processed | dead (a consumer kept failing)
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processed', 'dead')),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_outbox_events_due ON outbox_events(next_attempt_at, created_at) WHERE status = 'pending';
+CREATE INDEX idx_outbox_events_approval ON outbox_events(approval_id, created_at);
+
+-- Webhook deliveries are keyed by the outbox event id, so re-handling an event is a no-op
+CREATE UNIQUE INDEX idx_webhook_deliveries_event ON webhook_deliveries(subscription_id, event_id);
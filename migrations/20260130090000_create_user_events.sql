@@ -0,0 +1,17 @@
+-- Per-user real-time events (GET /events/stream)
+-- Filled from the domain event outbox; each insert is broadcast to every server instance
+-- with NOTIFY pxm_user_events. The BIGSERIAL id doubles as the SSE event id, so a client
+-- reconnecting with Last-Event-ID gets everything it missed replayed from this table.
+CREATE TABLE user_events (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    -- outbox event this row was derived from (re-handling the same event is a no-op)
+    source_event_id UUID NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (source_event_id, user_id, event_type)
+);
+
+CREATE INDEX idx_user_events_user ON user_events(user_id, id);
+CREATE INDEX idx_user_events_created_at ON user_events(created_at);
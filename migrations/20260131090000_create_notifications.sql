@@ -0,0 +1,36 @@
+-- In-app notification center
+-- Notifications are derived from domain events (outbox) and from a periodic deadline scan.
+-- dedupe_key keeps re-processing idempotent: the outbox event id for lifecycle notifications,
+-- "<approval_id>:<step>:<due_at>" for deadline reminders.
+
+-- 결재 기한 (요청자가 지정, 기한 임박 알림에 사용)
+ALTER TABLE pxm_approval_requests ADD COLUMN due_at TIMESTAMPTZ;
+CREATE INDEX idx_approval_requests_due_at ON pxm_approval_requests(due_at)
+    WHERE status = 'pending' AND due_at IS NOT NULL;
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    -- assigned, approved, rejected, returned, mentioned, deadline_approaching
+    notification_type VARCHAR(32) NOT NULL,
+    approval_id UUID REFERENCES pxm_approval_requests(id) ON DELETE CASCADE,
+    actor_id UUID,
+    title TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}',
+    dedupe_key TEXT NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, notification_type, dedupe_key)
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, created_at DESC, id DESC);
+CREATE INDEX idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+-- 유형별 수신 설정 (행이 없으면 수신)
+CREATE TABLE notification_preferences (
+    user_id UUID NOT NULL,
+    notification_type VARCHAR(32) NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, notification_type)
+);
@@ -0,0 +1,92 @@
+-- Email notifications over SMTP
+-- Every stored notification for a user with an email address gets one email_deliveries row
+-- (queued in the same transaction). A background dispatcher renders the message from
+-- email_templates in the recipient's locale and sends it, retrying with backoff, so SMTP
+-- failures never affect the approval action itself.
+INSERT INTO permissions (code, description)
+VALUES ('email_template:manage', 'Edit email notification templates')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_code, permission_code)
+VALUES ('ADMIN', 'email_template:manage')
+ON CONFLICT DO NOTHING;
+
+-- 알림 메일 언어
+ALTER TABLE users ADD COLUMN locale VARCHAR(8) NOT NULL DEFAULT 'ko'
+    CHECK (locale IN ('ko', 'en'));
+
+-- Placeholders: {{title}} {{requester}} {{step_name}} {{link}} {{recipient}}
+CREATE TABLE email_templates (
+    notification_type VARCHAR(32) NOT NULL,
+    locale VARCHAR(8) NOT NULL CHECK (locale IN ('ko', 'en')),
+    subject TEXT NOT NULL,
+    body_text TEXT NOT NULL,
+    body_html TEXT NOT NULL,
+    updated_by UUID,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (notification_type, locale)
+);
+
+INSERT INTO email_templates (notification_type, locale, subject, body_text, body_html) VALUES
+('assigned', 'ko',
+ '[결재 요청] {{title}}',
+ E'{{recipient}}님, {{requester}}님이 올린 문서의 결재 차례입니다.\n\n문서: {{title}}\n단계: {{step_name}}\n\n{{link}}',
+ '<p>{{recipient}}님, {{requester}}님이 올린 문서의 결재 차례입니다.</p><p>문서: <b>{{title}}</b><br>단계: {{step_name}}</p><p><a href="{{link}}">문서 열기</a></p>'),
+('assigned', 'en',
+ '[Approval needed] {{title}}',
+ E'Hi {{recipient}}, a request from {{requester}} is waiting for your approval.\n\nRequest: {{title}}\nStep: {{step_name}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, a request from {{requester}} is waiting for your approval.</p><p>Request: <b>{{title}}</b><br>Step: {{step_name}}</p><p><a href="{{link}}">Open request</a></p>'),
+('approved', 'ko',
+ '[최종 승인] {{title}}',
+ E'{{recipient}}님, 올리신 문서가 최종 승인되었습니다.\n\n문서: {{title}}\n\n{{link}}',
+ '<p>{{recipient}}님, 올리신 문서가 최종 승인되었습니다.</p><p>문서: <b>{{title}}</b></p><p><a href="{{link}}">문서 열기</a></p>'),
+('approved', 'en',
+ '[Approved] {{title}}',
+ E'Hi {{recipient}}, your request has been approved.\n\nRequest: {{title}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, your request has been approved.</p><p>Request: <b>{{title}}</b></p><p><a href="{{link}}">Open request</a></p>'),
+('rejected', 'ko',
+ '[반려] {{title}}',
+ E'{{recipient}}님, 올리신 문서가 {{step_name}} 단계에서 반려되었습니다.\n\n문서: {{title}}\n\n{{link}}',
+ '<p>{{recipient}}님, 올리신 문서가 {{step_name}} 단계에서 반려되었습니다.</p><p>문서: <b>{{title}}</b></p><p><a href="{{link}}">문서 열기</a></p>'),
+('rejected', 'en',
+ '[Rejected] {{title}}',
+ E'Hi {{recipient}}, your request was rejected at step {{step_name}}.\n\nRequest: {{title}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, your request was rejected at step {{step_name}}.</p><p>Request: <b>{{title}}</b></p><p><a href="{{link}}">Open request</a></p>'),
+('returned', 'ko',
+ '[보완 요청] {{title}}',
+ E'{{recipient}}님, 올리신 문서가 {{step_name}} 단계에서 보완 요청으로 되돌아왔습니다.\n\n문서: {{title}}\n\n{{link}}',
+ '<p>{{recipient}}님, 올리신 문서가 {{step_name}} 단계에서 보완 요청으로 되돌아왔습니다.</p><p>문서: <b>{{title}}</b></p><p><a href="{{link}}">문서 열기</a></p>'),
+('returned', 'en',
+ '[Returned] {{title}}',
+ E'Hi {{recipient}}, your request was returned for changes at step {{step_name}}.\n\nRequest: {{title}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, your request was returned for changes at step {{step_name}}.</p><p>Request: <b>{{title}}</b></p><p><a href="{{link}}">Open request</a></p>'),
+('mentioned', 'ko',
+ '[언급] {{title}}',
+ E'{{recipient}}님, 문서 의견에서 회원님을 언급했습니다.\n\n문서: {{title}}\n\n{{link}}',
+ '<p>{{recipient}}님, 문서 의견에서 회원님을 언급했습니다.</p><p>문서: <b>{{title}}</b></p><p><a href="{{link}}">문서 열기</a></p>'),
+('mentioned', 'en',
+ '[Mentioned] {{title}}',
+ E'Hi {{recipient}}, you were mentioned in a comment.\n\nRequest: {{title}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, you were mentioned in a comment.</p><p>Request: <b>{{title}}</b></p><p><a href="{{link}}">Open request</a></p>'),
+('deadline_approaching', 'ko',
+ '[기한 임박] {{title}}',
+ E'{{recipient}}님, 결재하실 문서의 기한이 다가옵니다.\n\n문서: {{title}}\n요청자: {{requester}}\n단계: {{step_name}}\n\n{{link}}',
+ '<p>{{recipient}}님, 결재하실 문서의 기한이 다가옵니다.</p><p>문서: <b>{{title}}</b><br>요청자: {{requester}}<br>단계: {{step_name}}</p><p><a href="{{link}}">문서 열기</a></p>'),
+('deadline_approaching', 'en',
+ '[Due soon] {{title}}',
+ E'Hi {{recipient}}, a request waiting for your approval is due soon.\n\nRequest: {{title}}\nRequester: {{requester}}\nStep: {{step_name}}\n\n{{link}}',
+ '<p>Hi {{recipient}}, a request waiting for your approval is due soon.</p><p>Request: <b>{{title}}</b><br>Requester: {{requester}}<br>Step: {{step_name}}</p><p><a href="{{link}}">Open request</a></p>');
+
+CREATE TABLE email_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notification_id UUID NOT NULL UNIQUE REFERENCES notifications(id) ON DELETE CASCADE,
+    -- pending -> sent | dead
+    status VARCHAR(16) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'sent', 'dead')),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_email_deliveries_due ON email_deliveries(next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,16 @@
+-- Daily digest email (opt-in)
+-- Once a day, at send_time in the user's timezone, users who opted in get one email listing
+-- the requests waiting for them (with how long each has waited) and their own requests that
+-- were approved or rejected the previous day. last_sent_on (a local date) makes the job
+-- send at most one digest per day even with several server instances.
+CREATE TABLE digest_settings (
+    user_id UUID PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    send_time TIME NOT NULL DEFAULT '08:00',
+    -- IANA name (e.g. "Asia/Seoul"); NULL = ORG_TIMEZONE
+    timezone VARCHAR(64),
+    last_sent_on DATE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_digest_settings_enabled ON digest_settings(send_time) WHERE enabled;
@@ -0,0 +1,57 @@
+-- Chat integration (Slack/Mattermost-compatible incoming webhooks)
+-- Approval notifications are posted to incoming-webhook URLs:
+--   - personal webhooks (owner_id = user): the owner's own notifications, managed by the owner
+--   - channel webhooks (owner_id NULL): everyone's notifications of the chosen types, managed by admins
+-- "Approve" / "Reject" buttons call back into POST /chat/interactions with a signed token.
+INSERT INTO permissions (code, description)
+VALUES ('chat:manage', 'Manage channel chat webhooks')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_code, permission_code)
+VALUES ('ADMIN', 'chat:manage')
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE chat_webhooks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- NULL = channel webhook
+    owner_id UUID,
+    name VARCHAR(255) NOT NULL,
+    url TEXT NOT NULL,
+    locale VARCHAR(8) NOT NULL DEFAULT 'ko' CHECK (locale IN ('ko', 'en')),
+    -- empty array = every notification type
+    notification_types TEXT[] NOT NULL DEFAULT '{}',
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_chat_webhooks_owner ON chat_webhooks(owner_id);
+
+-- Chat user id of a PXM user. Button clicks in channels are only accepted from the linked
+-- account of the current approver.
+CREATE TABLE chat_accounts (
+    user_id UUID PRIMARY KEY,
+    chat_user_id VARCHAR(255) NOT NULL UNIQUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- status: pending -> sent | dead (gave up after max attempts)
+-- One message per (webhook, outbox event, recipient, type): re-delivered events are ignored.
+CREATE TABLE chat_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    webhook_id UUID NOT NULL REFERENCES chat_webhooks(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL,
+    recipient_id UUID NOT NULL,
+    notification_type VARCHAR(32) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'sent', 'dead')),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    UNIQUE (webhook_id, event_id, recipient_id, notification_type)
+);
+
+CREATE INDEX idx_chat_deliveries_due ON chat_deliveries(next_attempt_at) WHERE status = 'pending';
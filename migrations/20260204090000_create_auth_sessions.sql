@@ -0,0 +1,31 @@
+-- Sessions and rotating refresh tokens
+-- Access tokens are short-lived JWTs carrying the session id (sid). auth_middleware rejects
+-- tokens whose session was revoked (logout, refresh-token reuse, password reset, ...).
+-- Refresh tokens are stored as SHA-256 hashes and are single-use: every refresh marks the
+-- presented token as used and issues a new one in the same session. Presenting a token that
+-- was already used means it leaked, so the whole session is revoked.
+CREATE TABLE auth_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    -- logout | logout_all | refresh_token_reused | ...
+    revoke_reason VARCHAR(32)
+);
+
+CREATE INDEX idx_auth_sessions_user ON auth_sessions(user_id) WHERE revoked_at IS NULL;
+
+CREATE TABLE refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID NOT NULL REFERENCES auth_sessions(id) ON DELETE CASCADE,
+    -- hex(SHA-256(token))
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    -- set when rotated; presenting it again = reuse
+    used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_refresh_tokens_session ON refresh_tokens(session_id);
@@ -0,0 +1,16 @@
+-- Password reset tokens
+-- POST /auth/password/forgot issues a random one-time token and stores only its SHA-256 hash.
+-- POST /auth/password/reset consumes it (used_at), sets the new password and revokes every
+-- session of the user. Issuing a new token invalidates older unused ones.
+CREATE TABLE password_reset_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- hex(SHA-256(token))
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_password_reset_tokens_user ON password_reset_tokens(user_id) WHERE used_at IS NULL;
+
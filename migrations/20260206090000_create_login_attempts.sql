@@ -0,0 +1,31 @@
+-- Login history and brute-force throttling
+-- Every login attempt (success or failure) is recorded in login_attempts.
+-- login_throttles keeps running failure counters per account (normalized email) and per source IP.
+-- Counters are keyed by the email that was typed, not by users.id, so unknown emails are throttled
+-- and locked exactly like real accounts and the responses do not reveal which emails exist.
+CREATE TABLE login_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- NULL when the email does not belong to any user
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    email VARCHAR(255) NOT NULL,
+    ip_address VARCHAR(64),
+    user_agent TEXT,
+    success BOOLEAN NOT NULL,
+    -- invalid_credentials | account_locked | ip_blocked | inactive
+    failure_reason VARCHAR(32),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_login_attempts_user ON login_attempts(user_id, created_at DESC);
+CREATE INDEX idx_login_attempts_email ON login_attempts(email, created_at DESC);
+
+CREATE TABLE login_throttles (
+    -- 'email' | 'ip'
+    scope VARCHAR(8) NOT NULL,
+    key VARCHAR(255) NOT NULL,
+    -- failures since the last success / lockout, within the counting window
+    failures INT NOT NULL DEFAULT 0,
+    last_failure_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMPTZ,
+    PRIMARY KEY (scope, key)
+);
@@ -0,0 +1,12 @@
+-- Chat account link codes
+-- POST /chat/account/link issues a short one-time code (only its SHA-256 hash is stored).
+-- The user sends "/pxm link <code>" in chat; the chat server signs that request and supplies
+-- the user id, so chat_accounts only ever holds ids the platform has vouched for.
+-- One outstanding code per user: issuing a new code replaces the old one.
+CREATE TABLE chat_link_codes (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    -- hex(SHA-256(code))
+    code_hash CHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,14 @@
+-- Daily digest email templates
+-- The digest is rendered from email_templates (notification_type = 'digest') like the other
+-- notification emails, so admins can edit its wording per locale.
+-- Extra placeholders: {{date}} {{pending_count}} {{pending_items}} {{status_changes}}
+INSERT INTO email_templates (notification_type, locale, subject, body_text, body_html) VALUES
+('digest', 'ko',
+ '[PXM] {{date}} 결재 요약: 대기 {{pending_count}}건',
+ E'{{recipient}}님, 오늘의 결재 요약입니다.\n\n■ 결재 대기 ({{pending_count}}건)\n{{pending_items}}\n■ 어제 처리된 내 문서\n{{status_changes}}',
+ '<p>{{recipient}}님, 오늘의 결재 요약입니다.</p><h3>결재 대기 ({{pending_count}}건)</h3>{{pending_items}}<h3>어제 처리된 내 문서</h3>{{status_changes}}'),
+('digest', 'en',
+ '[PXM] Daily digest {{date}}: {{pending_count}} waiting for you',
+ E'Hi {{recipient}},\n\n■ Waiting for your approval ({{pending_count}})\n{{pending_items}}\n■ Your requests updated yesterday\n{{status_changes}}',
+ '<p>Hi {{recipient}},</p><h3>Waiting for your approval ({{pending_count}})</h3>{{pending_items}}<h3>Your requests updated yesterday</h3>{{status_changes}}')
+ON CONFLICT (notification_type, locale) DO NOTHING;
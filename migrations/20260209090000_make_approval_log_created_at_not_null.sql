@@ -0,0 +1,8 @@
+-- approval_logs.created_at is the keyset cursor for the log timeline, so it must always be set.
+-- Backfill missing values with the request's creation time before adding the constraint.
+UPDATE approval_logs l
+SET created_at = COALESCE(r.created_at, NOW())
+FROM pxm_approval_requests r
+WHERE l.approval_id = r.id AND l.created_at IS NULL;
+
+ALTER TABLE approval_logs ALTER COLUMN created_at SET NOT NULL;
@@ -0,0 +1,4 @@
+-- 'org:manage' was seeded for department management, but there are no department write routes
+-- and every org write route is gated by 'user:manage'. Drop it so no role appears to grant it.
+-- role_permissions rows are removed by ON DELETE CASCADE.
+DELETE FROM permissions WHERE code = 'org:manage';
@@ -63,5 +63,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     .await?;
     println!("Seeded Park Director");
 
+    // 3. System Admin (SQL seed) - 검증할 수 없는 placeholder hash였던 경우를 대비해 비밀번호를 맞춰 둡니다.
+    sqlx::query!(
+        "UPDATE users SET password_hash = $1, updated_at = NOW() WHERE email = 'admin@pxm.com'",
+        password_hash
+    )
+    .execute(&pool)
+    .await?;
+    println!("Reset System Admin password");
+
     Ok(())
 }
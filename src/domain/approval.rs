@@ -1,3 +1,4 @@
+use super::pagination::{Cursor, KeysetValue, SortOrder};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::types::Json;
@@ -18,6 +19,9 @@ pub enum ApprovalAction {
 pub struct FlowProcess {
     pub current_step: i32,
     pub steps: Vec<ApprovalStep>,
+    // 참조자 (결재 권한은 없지만 문서를 열람할 수 있는 사용자)
+    #[serde(default)]
+    pub references: Vec<Uuid>,
 }
 
 impl FlowProcess {
@@ -53,15 +57,15 @@ impl FlowProcess {
                 // 다음 단계로 이동 확인
                 if self.current_step < self.steps.len() as i32 {
                     self.current_step += 1;
-                    return Ok("moved_to_next_step".to_string());
+                    Ok("moved_to_next_step".to_string())
                 } else {
-                    return Ok("completed".to_string());
+                    Ok("completed".to_string())
                 }
             }
             ApprovalAction::Reject => {
                 step.status = "rejected".to_string();
                 step.timestamp = Some(Utc::now());
-                return Ok("rejected".to_string());
+                Ok("rejected".to_string())
             }
         }
     }
@@ -79,7 +83,7 @@ pub struct ApprovalStep {
 // [Rust Guide]
 // sqlx::FromRow: DB 조회 결과를 이 구조체에 자동으로 매핑해줍니다.
 // Json<T>: PostgreSQL의 JSONB 컬럼을 Rust의 타입(T)으로 자동 변환해주는 래퍼(Wrapper)입니다.
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ApprovalRequest {
     pub id: Uuid,
     pub title: String,
@@ -93,16 +97,149 @@ pub struct ApprovalRequest {
     // 폼 데이터는 구조가 가변적이므로 serde_json::Value를 사용하여 유연하게 처리합니다.
     pub form_data: Json<serde_json::Value>,
 
+    // 템플릿으로 생성된 경우 원본 템플릿 ID (직접 생성 시 None)
+    pub template_id: Option<Uuid>,
+
+    // 결재 기한 (요청자가 지정, 없으면 None)
+    pub due_at: Option<DateTime<Utc>>,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+impl ApprovalRequest {
+    // 진행 중인 문서의 현재 결재자 (아직 처리하지 않은 단계)
+    // 회수된 문서는 회수 시점에 결재 차례였던 사용자를 돌려줍니다.
+    pub fn current_approver(&self) -> Option<Uuid> {
+        if self.status != "pending" && self.status != "withdrawn" {
+            return None;
+        }
+        let step_idx = (self.flow_process.current_step - 1) as usize;
+        self.flow_process
+            .steps
+            .get(step_idx)
+            .filter(|step| step.status == "pending")
+            .map(|step| step.approver_id)
+    }
+
+    // 문서번호: 기안일(조직 타임존) + ID 앞 8자리 (예: "20260125-3F2A9C1D")
+    pub fn document_number(&self, tz: chrono_tz::Tz) -> String {
+        format!(
+            "{}-{}",
+            self.created_at.with_timezone(&tz).format("%Y%m%d"),
+            self.id.simple().to_string()[..8].to_ascii_uppercase()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ApprovalLog {
     pub id: Uuid,
     pub approval_id: Uuid,
     pub actor_id: Uuid,
     pub action_type: String,
     pub content: Option<String>,
-    pub created_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// [Inbox]
+// 개인 문서함 구분
+// - pending:   내가 현재 결재할 차례인 문서 (결재 대기)
+// - requested: 내가 상신한 문서 (임시저장 제외)
+// - processed: 내가 승인/반려한 문서
+// - completed: 내가 관여한(요청/결재/참조) 완료 문서
+// - drafts:    임시저장 문서
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InboxBox {
+    Pending,
+    Requested,
+    Processed,
+    Completed,
+    Drafts,
+}
+
+impl InboxBox {
+    pub const ALL: [InboxBox; 5] = [
+        InboxBox::Pending,
+        InboxBox::Requested,
+        InboxBox::Processed,
+        InboxBox::Completed,
+        InboxBox::Drafts,
+    ];
+}
+
+// 문서함별 건수 (배지 표시용)
+#[derive(Debug, Serialize, Default, sqlx::FromRow)]
+pub struct InboxCounts {
+    pub pending: i64,
+    pub requested: i64,
+    pub processed: i64,
+    pub completed: i64,
+    pub drafts: i64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+impl ApprovalSort {
+    // cursor key 겸 정렬 컬럼명
+    pub fn key(&self) -> &'static str {
+        match self {
+            ApprovalSort::CreatedAt => "created_at",
+            ApprovalSort::UpdatedAt => "updated_at",
+            ApprovalSort::Title => "title",
+        }
+    }
+
+    pub fn cursor_of(&self, request: &ApprovalRequest) -> Cursor {
+        match self {
+            ApprovalSort::CreatedAt => Cursor::at(self.key(), request.created_at, request.id),
+            ApprovalSort::UpdatedAt => Cursor::at(self.key(), request.updated_at, request.id),
+            ApprovalSort::Title => Cursor::new(self.key(), request.title.clone(), request.id),
+        }
+    }
+}
+
+// 결재 목록 공통 필터/정렬/페이지 파라미터
+// 예: GET /approvals?status=pending&template_id=...&created_from=2026-01-01T00:00:00Z&sort=title&order=asc
+#[derive(Debug, Default, Deserialize)]
+pub struct ApprovalListQuery {
+    pub status: Option<String>,
+    pub requester_id: Option<Uuid>,
+    pub approver_id: Option<Uuid>,
+    pub template_id: Option<Uuid>,
+    // 요청자 소속 부서 (하위 부서 포함)
+    pub department_id: Option<Uuid>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sort: ApprovalSort,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl ApprovalListQuery {
+    // cursor를 정렬 기준에 맞는 keyset 값으로 변환합니다.
+    pub fn keyset(&self) -> Result<Option<(KeysetValue, Uuid)>, String> {
+        let Some(raw) = self.cursor.as_deref() else {
+            return Ok(None);
+        };
+        let cursor = Cursor::decode(raw, self.sort.key())?;
+        let value = match self.sort {
+            ApprovalSort::CreatedAt | ApprovalSort::UpdatedAt => {
+                KeysetValue::Timestamp(cursor.timestamp()?)
+            }
+            ApprovalSort::Title => KeysetValue::Text(cursor.value.clone()),
+        };
+        Ok(Some((value, cursor.id)))
+    }
 }
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// [Attachment]
+// 결재 요청에 첨부하는 파일(영수증, 견적서 등)의 메타데이터입니다.
+// 파일 본문은 AttachmentStorage(services::storage)에 storage_key로 저장합니다.
+// 첨부/삭제는 임시저장(draft) 상태에서만 가능하며, 상신 후에는 변경할 수 없습니다.
+
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+pub const DEFAULT_ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "text/plain",
+    "text/csv",
+    "application/zip",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "application/haansofthwp",
+    "application/x-hwp",
+];
+
+#[derive(Debug, Serialize, FromRow, Clone)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub approval_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    #[serde(skip_serializing)] // 저장소 내부 경로는 노출하지 않음
+    pub storage_key: String,
+    pub uploaded_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+// 업로드 제한 (ATTACHMENT_MAX_BYTES, ATTACHMENT_ALLOWED_TYPES=comma,separated)
+#[derive(Debug, Clone)]
+pub struct AttachmentPolicy {
+    pub max_bytes: usize,
+    pub allowed_content_types: Vec<String>,
+}
+
+impl Default for AttachmentPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_ATTACHMENT_BYTES,
+            allowed_content_types: DEFAULT_ALLOWED_CONTENT_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl AttachmentPolicy {
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Ok(raw) = std::env::var("ATTACHMENT_MAX_BYTES") {
+            match raw.parse() {
+                Ok(max_bytes) => policy.max_bytes = max_bytes,
+                Err(_) => eprintln!("Invalid ATTACHMENT_MAX_BYTES '{}', using default", raw),
+            }
+        }
+        if let Ok(raw) = std::env::var("ATTACHMENT_ALLOWED_TYPES") {
+            policy.allowed_content_types = raw
+                .split(',')
+                .map(|t| t.trim().to_ascii_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+        policy
+    }
+
+    pub fn check(&self, content_type: &str, size: usize) -> Result<(), String> {
+        if size == 0 {
+            return Err("File is empty".to_string());
+        }
+        if size > self.max_bytes {
+            return Err(format!(
+                "File exceeds the maximum size of {} bytes",
+                self.max_bytes
+            ));
+        }
+        if !self
+            .allowed_content_types
+            .iter()
+            .any(|allowed| allowed == content_type)
+        {
+            return Err(format!("File type '{}' is not allowed", content_type));
+        }
+        Ok(())
+    }
+}
+
+// 업로드된 본문으로 판별한 content type (클라이언트가 보낸 Content-Type은 신뢰하지 않음)
+// - 시그니처(magic bytes)가 있는 형식은 본문으로 판별합니다.
+// - ZIP/OLE 컨테이너(OOXML, HWP)와 텍스트(txt/csv)는 확장자로 구분합니다.
+// 판별하지 못하면 application/octet-stream (기본 허용 목록에 없으므로 거부됨)
+pub fn detect_content_type(file_name: &str, bytes: &[u8]) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if bytes.starts_with(b"%PDF-") {
+        return "application/pdf";
+    }
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return "image/png";
+    }
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return "image/jpeg";
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return "image/gif";
+    }
+    if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return match extension.as_str() {
+            "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            _ => "application/zip",
+        };
+    }
+    if bytes.starts_with(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+        return match extension.as_str() {
+            "hwp" => "application/x-hwp",
+            _ => "application/octet-stream",
+        };
+    }
+
+    // 시그니처가 없는 텍스트: 제어 문자 없는 UTF-8만
+    let is_text = std::str::from_utf8(bytes).is_ok_and(|text| {
+        !text
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\t' | '\r' | '\n'))
+    });
+    match extension.as_str() {
+        "csv" if is_text => "text/csv",
+        "txt" if is_text => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+// 경로 구분자/제어 문자를 제거한 파일 이름 (최대 255자)
+pub fn sanitize_file_name(raw: &str) -> String {
+    let base = raw.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = base
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(255)
+        .collect();
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        "attachment".to_string()
+    } else {
+        name.to_string()
+    }
+}
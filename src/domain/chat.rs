@@ -0,0 +1,404 @@
+use super::notification::NotificationType;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+// [Chat Integration]
+// 알림을 채팅(Slack/Mattermost 호환) incoming webhook으로 보냅니다.
+// - 개인 webhook (owner_id = 사용자): 본인 알림만, 본인이 관리
+// - 채널 webhook (owner_id = NULL): 모든 사용자의 알림 중 선택한 종류, 관리자가 관리 (chat:manage)
+// 결재 배정 메시지에는 "승인"/"반려" 버튼이 붙고, 버튼은 서명된 토큰과 함께
+// POST /chat/interactions 로 돌아와 FlowProcess::handle_action으로 처리됩니다.
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ChatWebhook {
+    pub id: Uuid,
+    pub owner_id: Option<Uuid>,
+    pub name: String,
+    pub url: String,
+    pub locale: String,
+    pub notification_types: Vec<String>,
+    pub is_active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChatWebhook {
+    pub fn accepts(&self, notification_type: NotificationType) -> bool {
+        self.notification_types.is_empty()
+            || self
+                .notification_types
+                .iter()
+                .any(|t| t == notification_type.as_str())
+    }
+}
+
+// POST /chat/webhooks, POST /chat/channels
+// Body: { "name": "My DM", "url": "https://chat.example.com/hooks/xxx",
+//         "locale": "en", "notification_types": ["assigned"] }
+#[derive(Debug, Deserialize)]
+pub struct CreateChatWebhookDto {
+    pub name: String,
+    pub url: String,
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub notification_types: Vec<NotificationType>,
+}
+
+// 채팅 계정 연결: POST /chat/account/link 로 받은 일회용 코드를 채팅에서 "/pxm link {code}"로 보내면
+// 채팅 서버가 검증한 사용자 ID로 연결됩니다. (본문의 사용자 ID를 그대로 믿지 않기 위해)
+pub const LINK_COMMAND: &str = "/pxm link";
+const LINK_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const LINK_CODE_LEN: usize = 8;
+
+pub fn generate_link_code() -> String {
+    use rand::Rng;
+    let mut rng = rand::rngs::OsRng;
+    (0..LINK_CODE_LEN)
+        .map(|_| LINK_CODE_ALPHABET[rng.gen_range(0..LINK_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+// 입력할 때의 대소문자/공백 차이는 무시합니다.
+pub fn normalize_link_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatAction {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ChatTokenError {
+    #[error("Malformed interaction token")]
+    Malformed,
+    #[error("Invalid interaction signature")]
+    BadSignature,
+    #[error("Interaction token expired")]
+    Expired,
+    #[error("Chat request could not be verified")]
+    Unverified,
+    #[error("Chat request timestamp is outside the replay window")]
+    Stale,
+}
+
+// Slack 요청 서명: X-Slack-Signature = "v0=" + hex(HMAC-SHA256(signing secret, "v0:{ts}:{raw body}"))
+// X-Slack-Request-Timestamp가 이 범위를 벗어나면 재전송(replay)으로 보고 거부합니다.
+pub const SLACK_REPLAY_WINDOW_SECS: i64 = 5 * 60;
+
+pub fn slack_signature(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mac = slack_mac(secret, timestamp, body);
+    format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+pub fn verify_slack_signature(
+    secret: &str,
+    timestamp: &str,
+    signature: &str,
+    body: &[u8],
+    now: i64,
+) -> Result<(), ChatTokenError> {
+    let sent_at: i64 = timestamp.parse().map_err(|_| ChatTokenError::Unverified)?;
+    if (now - sent_at).abs() > SLACK_REPLAY_WINDOW_SECS {
+        return Err(ChatTokenError::Stale);
+    }
+    let signature = signature
+        .strip_prefix("v0=")
+        .and_then(|hex_sig| hex::decode(hex_sig).ok())
+        .ok_or(ChatTokenError::Unverified)?;
+    // 상수 시간 비교
+    slack_mac(secret, timestamp, body)
+        .verify_slice(&signature)
+        .map_err(|_| ChatTokenError::Unverified)
+}
+
+fn slack_mac(secret: &str, timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("v0:{}:", timestamp).as_bytes());
+    mac.update(body);
+    mac
+}
+
+// Mattermost는 요청에 서명하지 않으므로 공유 토큰을 비교합니다.
+// (slash command의 token 필드, 버튼 integration.context의 verify_token)
+pub fn verify_shared_token(expected: &str, received: Option<&str>) -> Result<(), ChatTokenError> {
+    let received = received.ok_or(ChatTokenError::Unverified)?;
+    // 길이가 달라도 상수 시간으로 비교되도록 HMAC으로 감쌉니다.
+    let digest = |value: &str| mac(expected, value).finalize().into_bytes();
+    let expected_digest = digest(expected);
+    mac(expected, received)
+        .verify_slice(&expected_digest)
+        .map_err(|_| ChatTokenError::Unverified)
+}
+
+// 버튼 하나에 담기는 서명된 토큰: "{base64url(JSON)}.{hex(HMAC-SHA256)}"
+// 결재 단계(step)까지 묶어 두어, 이미 처리된 단계의 버튼으로 다음 단계를 처리하지 못하게 합니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionToken {
+    pub approval_id: Uuid,
+    pub step: i32,
+    pub approver_id: Uuid,
+    pub action: ChatAction,
+    // 채널 메시지의 버튼인지 (개인/채널 모두 누른 사람이 결재자의 연결된 채팅 계정인지 확인합니다)
+    pub channel: bool,
+    // unix seconds
+    pub expires_at: i64,
+}
+
+impl InteractionToken {
+    pub fn sign(&self, secret: &str) -> String {
+        let claims = serde_json::to_vec(self).unwrap_or_default();
+        let encoded = URL_SAFE_NO_PAD.encode(claims);
+        let signature = hex::encode(mac(secret, &encoded).finalize().into_bytes());
+        format!("{}.{}", encoded, signature)
+    }
+
+    pub fn verify(token: &str, secret: &str, now: i64) -> Result<Self, ChatTokenError> {
+        let (encoded, signature) = token.split_once('.').ok_or(ChatTokenError::Malformed)?;
+        let signature = hex::decode(signature).map_err(|_| ChatTokenError::Malformed)?;
+        // 상수 시간 비교
+        mac(secret, encoded)
+            .verify_slice(&signature)
+            .map_err(|_| ChatTokenError::BadSignature)?;
+        let claims = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| ChatTokenError::Malformed)?;
+        let token: Self = serde_json::from_slice(&claims).map_err(|_| ChatTokenError::Malformed)?;
+        if token.expires_at < now {
+            return Err(ChatTokenError::Expired);
+        }
+        Ok(token)
+    }
+}
+
+fn mac(secret: &str, encoded: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(encoded.as_bytes());
+    mac
+}
+
+// 메시지에 들어갈 값
+#[derive(Debug, Clone, Default)]
+pub struct ChatMessage {
+    pub title: String,
+    pub requester: String,
+    pub actor: String,
+    pub step_name: String,
+    pub reason: Option<String>,
+    pub link: String,
+}
+
+// 버튼 (콜백 URL + 승인/반려 토큰)
+#[derive(Debug, Clone)]
+pub struct ChatButtons {
+    pub callback_url: String,
+    pub approve_token: String,
+    pub reject_token: String,
+    // Mattermost 공유 토큰: integration.context는 클라이언트에 내려가지 않으므로
+    // 콜백이 Mattermost 서버를 거쳐 왔는지 확인하는 데 씁니다.
+    pub verify_token: Option<String>,
+}
+
+impl ChatMessage {
+    // Slack/Mattermost 공통 incoming webhook 형식 (attachments + actions)
+    // - Mattermost: actions[].integration.url 로 context를 POST
+    // - Slack: app의 Interactivity Request URL로 actions[].value를 전달
+    pub fn payload(
+        &self,
+        notification_type: NotificationType,
+        locale: &str,
+        buttons: Option<&ChatButtons>,
+    ) -> serde_json::Value {
+        let en = locale == "en";
+        let (headline, color) = match (notification_type, en) {
+            (NotificationType::Assigned, true) => ("Approval needed".to_string(), "#1d72f3"),
+            (NotificationType::Assigned, false) => ("결재 요청".to_string(), "#1d72f3"),
+            (NotificationType::Approved, true) => ("Approved".to_string(), "#2eb67d"),
+            (NotificationType::Approved, false) => ("최종 승인".to_string(), "#2eb67d"),
+            (NotificationType::Rejected, true) => ("Rejected".to_string(), "#e01e5a"),
+            (NotificationType::Rejected, false) => ("반려".to_string(), "#e01e5a"),
+            (NotificationType::Returned, true) => ("Returned".to_string(), "#ecb22e"),
+            (NotificationType::Returned, false) => ("반송".to_string(), "#ecb22e"),
+            (NotificationType::Mentioned, true) => {
+                (format!("{} mentioned you", self.actor), "#8d8d8d")
+            }
+            (NotificationType::Mentioned, false) => {
+                (format!("{}님이 언급했습니다", self.actor), "#8d8d8d")
+            }
+            (NotificationType::DeadlineApproaching, true) => ("Due soon".to_string(), "#ecb22e"),
+            (NotificationType::DeadlineApproaching, false) => {
+                ("결재 기한 임박".to_string(), "#ecb22e")
+            }
+        };
+
+        let mut fields = vec![serde_json::json!({
+            "title": if en { "Requester" } else { "기안자" },
+            "value": self.requester,
+            "short": true,
+        })];
+        if !self.step_name.is_empty() {
+            fields.push(serde_json::json!({
+                "title": if en { "Step" } else { "결재 단계" },
+                "value": self.step_name,
+                "short": true,
+            }));
+        }
+        if let Some(reason) = &self.reason {
+            fields.push(serde_json::json!({
+                "title": if en { "Reason" } else { "사유" },
+                "value": reason,
+                "short": false,
+            }));
+        }
+
+        let mut attachment = serde_json::json!({
+            "fallback": format!("{}: {}", headline, self.title),
+            "color": color,
+            "pretext": headline,
+            "title": self.title,
+            "title_link": self.link,
+            "fields": fields,
+        });
+        if let Some(buttons) = buttons {
+            let button = |id: &str, label: &str, style: &str, token: &str| {
+                let mut context = serde_json::json!({ "token": token });
+                if let Some(verify_token) = &buttons.verify_token {
+                    context["verify_token"] = serde_json::json!(verify_token);
+                }
+                serde_json::json!({
+                    "id": id,
+                    "name": label,
+                    "text": label,
+                    "type": "button",
+                    "style": style,
+                    "value": token,
+                    "integration": {
+                        "url": buttons.callback_url,
+                        "context": context,
+                    },
+                })
+            };
+            attachment["callback_id"] = serde_json::json!("pxm_approval");
+            attachment["actions"] = serde_json::json!([
+                button(
+                    "approve",
+                    if en { "Approve" } else { "승인" },
+                    "primary",
+                    &buttons.approve_token
+                ),
+                button(
+                    "reject",
+                    if en { "Reject" } else { "반려" },
+                    "danger",
+                    &buttons.reject_token
+                ),
+            ]);
+        }
+
+        serde_json::json!({
+            "text": format!("{}: {}", headline, self.title),
+            "attachments": [attachment],
+        })
+    }
+}
+
+// 버튼 클릭 콜백에서 꺼낸 값
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub token: String,
+    // 버튼을 누른 채팅 사용자 (채팅 서버가 채운 값)
+    pub chat_user_id: Option<String>,
+    // Mattermost: integration.context.verify_token
+    pub verify_token: Option<String>,
+}
+
+impl Interaction {
+    // - Mattermost: JSON { "user_id": "...", "context": { "token": "..." } }
+    // - Slack: form "payload={ "user": { "id": "..." }, "actions": [{ "value": "..." }] }"
+    pub fn parse(content_type: &str, body: &[u8]) -> Option<Self> {
+        if content_type.starts_with("application/x-www-form-urlencoded") {
+            let body = std::str::from_utf8(body).ok()?;
+            let raw = body
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("payload="))?
+                .replace('+', " ");
+            let payload: serde_json::Value = serde_json::from_str(
+                &percent_encoding::percent_decode_str(&raw)
+                    .decode_utf8()
+                    .ok()?,
+            )
+            .ok()?;
+            return Some(Self {
+                token: payload["actions"][0]["value"].as_str()?.to_string(),
+                chat_user_id: payload["user"]["id"].as_str().map(str::to_string),
+                verify_token: None,
+            });
+        }
+        let payload: serde_json::Value = serde_json::from_slice(body).ok()?;
+        Some(Self {
+            token: payload["context"]["token"].as_str()?.to_string(),
+            chat_user_id: payload["user_id"].as_str().map(str::to_string),
+            verify_token: payload["context"]["verify_token"]
+                .as_str()
+                .map(str::to_string),
+        })
+    }
+}
+
+// slash command 요청 (Slack/Mattermost 모두 form: token, user_id, command, text, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlashCommand {
+    pub token: Option<String>,
+    pub user_id: String,
+    pub command: String,
+    pub text: String,
+}
+
+impl SlashCommand {
+    pub fn parse(body: &[u8]) -> Option<Self> {
+        let body = std::str::from_utf8(body).ok()?;
+        let field = |name: &str| {
+            body.split('&').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                if key != name {
+                    return None;
+                }
+                percent_encoding::percent_decode_str(&value.replace('+', " "))
+                    .decode_utf8()
+                    .ok()
+                    .map(|value| value.into_owned())
+            })
+        };
+        Some(Self {
+            token: field("token"),
+            user_id: field("user_id").filter(|id| !id.is_empty())?,
+            command: field("command").unwrap_or_default(),
+            text: field("text").unwrap_or_default(),
+        })
+    }
+}
+
+// slash command 응답 (명령을 보낸 사람에게만 표시)
+pub fn command_response(text: &str) -> serde_json::Value {
+    serde_json::json!({ "response_type": "ephemeral", "text": text })
+}
+
+// 콜백 응답: 원래 메시지를 결과로 바꿉니다. (Mattermost: update, Slack: replace_original)
+pub fn interaction_response(text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "update": { "message": text, "props": { "attachments": [] } },
+        "replace_original": true,
+        "text": text,
+    })
+}
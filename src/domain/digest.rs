@@ -0,0 +1,210 @@
+use super::email::{
+    EmailBlock, EmailContext, EmailTemplate, EmailTemplateError, RenderedEmail, escape_html,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// [Daily Digest]
+// 매일 사용자가 정한 시각(사용자 타임존 기준)에 한 통으로 보내는 요약 메일입니다.
+// - 나에게 결재 대기 중인 문서 (대기 시간 포함, 오래 기다린 순)
+// - 어제 승인/반려로 상태가 바뀐 내 문서
+// 제목/인사/머리글은 email_templates의 "digest" 템플릿(언어별)로, 목록만 여기서 만듭니다.
+
+pub const DEFAULT_SEND_TIME: &str = "08:00";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestSettings {
+    pub user_id: Uuid,
+    pub enabled: bool,
+    pub send_time: NaiveTime,
+    // None이면 조직 타임존 (ORG_TIMEZONE)
+    pub timezone: Option<String>,
+    pub last_sent_on: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DigestSettingsResponse {
+    pub enabled: bool,
+    // "HH:MM"
+    pub send_time: String,
+    pub timezone: Option<String>,
+    // 실제로 적용되는 타임존 (timezone이 없으면 조직 타임존)
+    pub effective_timezone: String,
+    pub last_sent_on: Option<NaiveDate>,
+}
+
+// 보낸 값만 바뀝니다. timezone을 null로 보내면 조직 타임존을 따릅니다.
+#[derive(Debug, Deserialize)]
+pub struct UpdateDigestSettingsDto {
+    pub enabled: Option<bool>,
+    pub send_time: Option<String>,
+    #[serde(default, deserialize_with = "super::double_option")]
+    pub timezone: Option<Option<String>>,
+}
+
+// "08:30" 또는 "08:30:00"
+pub fn parse_send_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| format!("Invalid send_time '{}' (expected HH:MM)", value))
+}
+
+// 결재 대기 문서
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PendingDigestItem {
+    pub id: Uuid,
+    pub title: String,
+    pub requester_name: Option<String>,
+    pub step_name: Option<String>,
+    // 현재 단계가 된 시각 (상신 또는 이전 단계 승인)
+    pub waiting_since: DateTime<Utc>,
+}
+
+// 상태가 바뀐 내 문서 (승인/반려 이력 1건)
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct StatusChangeItem {
+    pub id: Uuid,
+    pub title: String,
+    // 문서의 현재 상태
+    pub status: String,
+    // APPROVED | REJECTED
+    pub action_type: String,
+    pub actor_name: Option<String>,
+    pub acted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Digest {
+    pub pending: Vec<PendingDigestItem>,
+    // 목록은 최대 건수까지만 담고, 전체 건수는 따로 표시합니다.
+    pub pending_total: i64,
+    pub changes: Vec<StatusChangeItem>,
+}
+
+impl Digest {
+    pub fn is_empty(&self) -> bool {
+        self.pending_total == 0 && self.changes.is_empty()
+    }
+
+    // template: email_templates의 "digest" 템플릿 (사용자 언어)
+    // locale: "ko" | "en" (목록 항목 표시용), date: 요약 기준일(사용자 타임존), base_url: 문서 링크 기준 URL
+    pub fn render(
+        &self,
+        template: &EmailTemplate,
+        locale: &str,
+        recipient: &str,
+        date: NaiveDate,
+        base_url: &str,
+        now: DateTime<Utc>,
+    ) -> Result<RenderedEmail, EmailTemplateError> {
+        let en = locale == "en";
+        let link = |id: Uuid| format!("{}/approvals/{}", base_url, id);
+
+        let mut pending = EmailBlock::default();
+        if self.pending.is_empty() {
+            let none = if en {
+                "Nothing is waiting."
+            } else {
+                "대기 중인 문서가 없습니다."
+            };
+            push_line(&mut pending, none);
+        }
+        for item in &self.pending {
+            let waited = format_wait(now - item.waiting_since, en);
+            let detail = if en {
+                format!(
+                    "{} · {} · waiting {}",
+                    item.requester_name.as_deref().unwrap_or("-"),
+                    item.step_name.as_deref().unwrap_or("-"),
+                    waited
+                )
+            } else {
+                format!(
+                    "{} · {} · {} 대기",
+                    item.requester_name.as_deref().unwrap_or("-"),
+                    item.step_name.as_deref().unwrap_or("-"),
+                    waited
+                )
+            };
+            push_item(&mut pending, &item.title, &detail, &link(item.id));
+        }
+        let more = self.pending_total - self.pending.len() as i64;
+        if more > 0 {
+            let line = if en {
+                format!("... and {} more", more)
+            } else {
+                format!("... 외 {}건", more)
+            };
+            push_line(&mut pending, &line);
+        }
+
+        let mut changes = EmailBlock::default();
+        if self.changes.is_empty() {
+            push_line(&mut changes, if en { "None." } else { "없음" });
+        }
+        for change in &self.changes {
+            let action = match (change.action_type.as_str(), change.status.as_str(), en) {
+                ("REJECTED", _, true) => "rejected",
+                ("REJECTED", _, false) => "반려",
+                (_, "approved", true) => "approved (final)",
+                (_, "approved", false) => "최종 승인",
+                (_, _, true) => "approved a step",
+                (_, _, false) => "단계 승인",
+            };
+            let detail = format!(
+                "{} · {}",
+                change.actor_name.as_deref().unwrap_or("-"),
+                action
+            );
+            push_item(&mut changes, &change.title, &detail, &link(change.id));
+        }
+
+        for block in [&mut pending, &mut changes] {
+            block.html = format!("<ul>{}</ul>", block.html);
+        }
+        template.render(&EmailContext {
+            recipient: recipient.to_string(),
+            link: format!("{}/approvals", base_url),
+            date: date.format("%Y-%m-%d").to_string(),
+            pending_count: self.pending_total.to_string(),
+            pending_items: pending,
+            status_changes: changes,
+            ..EmailContext::default()
+        })
+    }
+}
+
+fn push_line(block: &mut EmailBlock, line: &str) {
+    block.text.push_str(&format!("- {}\n", line));
+    block
+        .html
+        .push_str(&format!("<li>{}</li>", escape_html(line)));
+}
+
+fn push_item(block: &mut EmailBlock, title: &str, detail: &str, link: &str) {
+    block
+        .text
+        .push_str(&format!("- {} ({})\n  {}\n", title, detail, link));
+    block.html.push_str(&format!(
+        r#"<li><a href="{}">{}</a> <small>{}</small></li>"#,
+        escape_html(link),
+        escape_html(title),
+        escape_html(detail)
+    ));
+}
+
+// 대기 시간 표시 (예: "2일 3시간" / "2d 3h", 1시간 미만은 분)
+pub fn format_wait(waited: chrono::Duration, en: bool) -> String {
+    let minutes = waited.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    match (days, hours, en) {
+        (0, 0, true) => format!("{}m", mins),
+        (0, 0, false) => format!("{}분", mins),
+        (0, h, true) => format!("{}h", h),
+        (0, h, false) => format!("{}시간", h),
+        (d, h, true) => format!("{}d {}h", d, h),
+        (d, h, false) => format!("{}일 {}시간", d, h),
+    }
+}
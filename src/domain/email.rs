@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+// [Email Templates]
+// 알림 유형(notification_type) x 언어(locale)별 메일 템플릿입니다. (email_templates)
+// 제목/본문(text, html)에 아래 placeholder를 쓸 수 있습니다.
+// - {{title}}      문서 제목
+// - {{requester}}  요청자 이름
+// - {{step_name}}  결재 단계 이름 (알림 시점의 현재 단계)
+// - {{link}}       문서 바로가기 URL (APP_BASE_URL/approvals/:id)
+// - {{recipient}}  받는 사람 이름
+// 요약 메일(notification_type = "digest") 전용:
+// - {{date}}            요약 기준일
+// - {{pending_count}}   결재 대기 건수
+// - {{pending_items}}   결재 대기 목록 (html은 <ul> 목록)
+// - {{status_changes}}  어제 처리된 내 문서 목록 (html은 <ul> 목록)
+// html 본문에 들어가는 값은 HTML escape 됩니다. (목록은 escape된 항목으로 만든 HTML)
+
+pub const SUPPORTED_LOCALES: [&str; 2] = ["ko", "en"];
+pub const DEFAULT_LOCALE: &str = "ko";
+// 요약 메일 템플릿의 notification_type
+pub const DIGEST_TEMPLATE: &str = "digest";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum EmailTemplateError {
+    #[error("Unclosed placeholder at position {0}")]
+    Unclosed(usize),
+    #[error("Unknown placeholder '{{{{{0}}}}}'")]
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct EmailTemplate {
+    pub notification_type: String,
+    pub locale: String,
+    pub subject: String,
+    pub body_text: String,
+    pub body_html: String,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateEmailTemplateDto {
+    pub subject: String,
+    pub body_text: String,
+    pub body_html: String,
+}
+
+impl UpdateEmailTemplateDto {
+    // 저장 전에 세 부분 모두 placeholder를 검증합니다.
+    pub fn validate(&self) -> Result<(), EmailTemplateError> {
+        for text in [&self.subject, &self.body_text, &self.body_html] {
+            EmailText::parse(text)?;
+        }
+        Ok(())
+    }
+}
+
+// placeholder 값
+#[derive(Debug, Clone, Default)]
+pub struct EmailContext {
+    pub title: String,
+    pub requester: String,
+    pub step_name: String,
+    pub link: String,
+    pub recipient: String,
+    pub date: String,
+    pub pending_count: String,
+    pub pending_items: EmailBlock,
+    pub status_changes: EmailBlock,
+}
+
+// 텍스트/HTML 본문에 각각 그대로 넣는 목록 (html은 이미 escape된 값으로 만든 것)
+#[derive(Debug, Clone, Default)]
+pub struct EmailBlock {
+    pub text: String,
+    pub html: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+impl EmailTemplate {
+    pub fn render(&self, ctx: &EmailContext) -> Result<RenderedEmail, EmailTemplateError> {
+        Ok(RenderedEmail {
+            // 제목은 한 줄이어야 합니다.
+            subject: EmailText::parse(&self.subject)?
+                .render(ctx, false)
+                .replace(['\r', '\n'], " "),
+            text: EmailText::parse(&self.body_text)?.render(ctx, false),
+            html: EmailText::parse(&self.body_html)?.render(ctx, true),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    Title,
+    Requester,
+    StepName,
+    Link,
+    Recipient,
+    Date,
+    PendingCount,
+    PendingItems,
+    StatusChanges,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct EmailText {
+    segments: Vec<Segment>,
+}
+
+impl EmailText {
+    fn parse(text: &str) -> Result<Self, EmailTemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = text;
+        let mut offset = 0;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let after_open = &rest[start + 2..];
+            let end = after_open
+                .find("}}")
+                .ok_or(EmailTemplateError::Unclosed(offset + start))?;
+
+            let placeholder = match after_open[..end].trim() {
+                "title" => Placeholder::Title,
+                "requester" => Placeholder::Requester,
+                "step_name" => Placeholder::StepName,
+                "link" => Placeholder::Link,
+                "recipient" => Placeholder::Recipient,
+                "date" => Placeholder::Date,
+                "pending_count" => Placeholder::PendingCount,
+                "pending_items" => Placeholder::PendingItems,
+                "status_changes" => Placeholder::StatusChanges,
+                other => return Err(EmailTemplateError::Unknown(other.to_string())),
+            };
+            segments.push(Segment::Placeholder(placeholder));
+
+            let consumed = start + 2 + end + 2;
+            offset += consumed;
+            rest = &rest[consumed..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+
+    fn render(&self, ctx: &EmailContext, html: bool) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            let value = match segment {
+                Segment::Literal(text) => {
+                    out.push_str(text);
+                    continue;
+                }
+                Segment::Placeholder(Placeholder::Title) => &ctx.title,
+                Segment::Placeholder(Placeholder::Requester) => &ctx.requester,
+                Segment::Placeholder(Placeholder::StepName) => &ctx.step_name,
+                Segment::Placeholder(Placeholder::Link) => &ctx.link,
+                Segment::Placeholder(Placeholder::Recipient) => &ctx.recipient,
+                Segment::Placeholder(Placeholder::Date) => &ctx.date,
+                Segment::Placeholder(Placeholder::PendingCount) => &ctx.pending_count,
+                Segment::Placeholder(Placeholder::PendingItems) => {
+                    out.push_str(block(&ctx.pending_items, html));
+                    continue;
+                }
+                Segment::Placeholder(Placeholder::StatusChanges) => {
+                    out.push_str(block(&ctx.status_changes, html));
+                    continue;
+                }
+            };
+            if html {
+                out.push_str(&escape_html(value));
+            } else {
+                out.push_str(value);
+            }
+        }
+        out
+    }
+}
+
+fn block(block: &EmailBlock, html: bool) -> &str {
+    if html { &block.html } else { &block.text }
+}
+
+// HTML 본문에 넣을 값 (메일 템플릿, digest, 비밀번호 재설정 메일 공통)
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,84 @@
+use super::approval::{ApprovalLog, ApprovalRequest};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+// [Domain Events]
+// 결재 문서의 상태 변경을 나타내는 이벤트입니다.
+// ApprovalRepository가 상태 변경과 같은 트랜잭션에서 outbox_events에 기록하고,
+// services::outbox::OutboxDispatcher가 등록된 consumer(webhook 등)에 전달합니다.
+// 각 이벤트는 변경 직후의 문서 스냅샷(approval)을 담습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    // 생성 (임시저장이면 approval.status = "draft", 바로 상신이면 "pending")
+    RequestCreated {
+        approval: ApprovalRequest,
+    },
+    // 임시저장 문서 상신
+    RequestSubmitted {
+        approval: ApprovalRequest,
+    },
+    // 중간 단계 승인 (다음 결재자로 이동)
+    StepApproved {
+        approval: ApprovalRequest,
+    },
+    // 마지막 단계 승인 (최종 승인)
+    RequestCompleted {
+        approval: ApprovalRequest,
+    },
+    RequestRejected {
+        approval: ApprovalRequest,
+        reason: Option<String>,
+    },
+    RequestWithdrawn {
+        approval: ApprovalRequest,
+    },
+    CommentAdded {
+        approval: ApprovalRequest,
+        comment: ApprovalLog,
+    },
+}
+
+impl DomainEvent {
+    // outbox_events.event_type (payload의 "type"과 같은 값)
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::RequestCreated { .. } => "request_created",
+            DomainEvent::RequestSubmitted { .. } => "request_submitted",
+            DomainEvent::StepApproved { .. } => "step_approved",
+            DomainEvent::RequestCompleted { .. } => "request_completed",
+            DomainEvent::RequestRejected { .. } => "request_rejected",
+            DomainEvent::RequestWithdrawn { .. } => "request_withdrawn",
+            DomainEvent::CommentAdded { .. } => "comment_added",
+        }
+    }
+
+    pub fn approval(&self) -> &ApprovalRequest {
+        match self {
+            DomainEvent::RequestCreated { approval }
+            | DomainEvent::RequestSubmitted { approval }
+            | DomainEvent::StepApproved { approval }
+            | DomainEvent::RequestCompleted { approval }
+            | DomainEvent::RequestRejected { approval, .. }
+            | DomainEvent::RequestWithdrawn { approval }
+            | DomainEvent::CommentAdded { approval, .. } => approval,
+        }
+    }
+}
+
+// outbox_events 행
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub approval_id: Uuid,
+    pub actor_id: Uuid,
+    pub event_type: String,
+    pub payload: Json<DomainEvent>,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,149 @@
+use serde_json::Value;
+
+// [Form Schema]
+// 템플릿의 form_schema에서 입력 필드 목록(JSON 경로 + 라벨)을 뽑아냅니다.
+// PDF 출력, CSV/XLSX 내보내기 등 form_data를 "필드 순서대로" 보여줘야 하는 곳에서 사용합니다.
+//
+// 지원하는 형태:
+// 1. 필드 배열: { "fields": [ { "key": "amount", "label": "금액" }, ... ] }
+//    - key 대신 name/id, label 대신 title도 허용, 중첩은 "fields" 또는 "key": "trip.city"
+// 2. JSON Schema: { "properties": { "amount": { "title": "금액" }, "trip": { "properties": {...} } } }
+//    - "ui:order" 배열이 있으면 그 순서, 없으면 키 이름 순
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormField {
+    pub path: Vec<String>,
+    pub label: String,
+}
+
+impl FormField {
+    // 점으로 연결한 경로 (예: "trip.city")
+    pub fn key(&self) -> String {
+        self.path.join(".")
+    }
+
+    pub fn value_in<'a>(&self, form_data: &'a Value) -> Option<&'a Value> {
+        self.path
+            .iter()
+            .try_fold(form_data, |value, segment| value.get(segment))
+    }
+}
+
+pub fn fields_of(form_schema: &Value) -> Vec<FormField> {
+    let mut fields = Vec::new();
+    collect_fields(form_schema, &[], &mut fields);
+    fields
+}
+
+// 스키마에 없는 form_data 값까지 포함한 필드 목록 (스키마 필드가 먼저)
+pub fn fields_with_extras(form_schema: &Value, form_data: &Value) -> Vec<FormField> {
+    let mut fields = fields_of(form_schema);
+    let mut extras = Vec::new();
+    collect_data_fields(form_data, &[], &mut extras);
+    for extra in extras {
+        let covered = fields
+            .iter()
+            .any(|f| extra.path.starts_with(&f.path) || f.path.starts_with(&extra.path));
+        if !covered {
+            fields.push(extra);
+        }
+    }
+    fields
+}
+
+// 표시용 문자열 (배열은 ", "로 연결)
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(true) => "Y".to_string(),
+        Value::Bool(false) => "N".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(_) => value.to_string(),
+    }
+}
+
+fn collect_fields(schema: &Value, prefix: &[String], out: &mut Vec<FormField>) {
+    if let Some(items) = schema.get("fields").and_then(Value::as_array) {
+        for item in items {
+            let Some(key) = ["key", "name", "id"]
+                .iter()
+                .find_map(|k| item.get(*k).and_then(Value::as_str))
+            else {
+                continue;
+            };
+            let mut path = prefix.to_vec();
+            path.extend(key.split('.').map(str::to_string));
+
+            if item.get("fields").is_some() || item.get("properties").is_some() {
+                collect_fields(item, &path, out);
+            } else {
+                out.push(FormField {
+                    label: label_of(item).unwrap_or(key).to_string(),
+                    path,
+                });
+            }
+        }
+        return;
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        let mut keys: Vec<&String> = Vec::new();
+        if let Some(order) = schema.get("ui:order").and_then(Value::as_array) {
+            keys.extend(
+                order
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter_map(|k| properties.get_key_value(k).map(|(k, _)| k)),
+            );
+        }
+        for key in properties.keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        for key in keys {
+            let property = &properties[key];
+            let mut path = prefix.to_vec();
+            path.push(key.clone());
+
+            if property.get("properties").is_some() {
+                collect_fields(property, &path, out);
+            } else {
+                out.push(FormField {
+                    label: label_of(property).unwrap_or(key).to_string(),
+                    path,
+                });
+            }
+        }
+    }
+}
+
+fn collect_data_fields(data: &Value, prefix: &[String], out: &mut Vec<FormField>) {
+    match data {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let mut path = prefix.to_vec();
+                path.push(key.clone());
+                collect_data_fields(value, &path, out);
+            }
+        }
+        _ if !prefix.is_empty() => out.push(FormField {
+            label: prefix.join("."),
+            path: prefix.to_vec(),
+        }),
+        _ => {}
+    }
+}
+
+fn label_of(item: &Value) -> Option<&str> {
+    ["label", "title"]
+        .iter()
+        .find_map(|k| item.get(*k).and_then(Value::as_str))
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+// [Login Throttling]
+// 로그인 실패를 계정(입력한 이메일)과 접속 IP별로 셉니다.
+// - 실패가 쌓이면 다음 시도부터 점점 길게 기다리게 합니다. (progressive delay)
+// - 계정: max_failures번 실패하면 lockout 동안 잠금 (응답은 그대로 "Invalid credentials")
+// - IP: ip_max_failures번 실패하면 lockout 동안 429
+// 없는 이메일도 같은 방식으로 세므로 잠금 여부로 가입 여부를 알 수 없습니다.
+
+pub const SCOPE_EMAIL: &str = "email";
+pub const SCOPE_IP: &str = "ip";
+
+// login_attempts.failure_reason
+pub const FAILURE_INVALID_CREDENTIALS: &str = "invalid_credentials";
+pub const FAILURE_ACCOUNT_LOCKED: &str = "account_locked";
+pub const FAILURE_IP_BLOCKED: &str = "ip_blocked";
+pub const FAILURE_INACTIVE: &str = "inactive";
+
+// 계정 실패 횟수의 key (대소문자/앞뒤 공백 무시)
+pub fn account_key(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+// 계정 또는 IP의 현재 실패 상태 (login_throttles)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThrottleState {
+    pub failures: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl ThrottleState {
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+}
+
+// LOGIN_MAX_FAILURES, LOGIN_IP_MAX_FAILURES, LOGIN_LOCKOUT_MINUTES, LOGIN_FAILURE_WINDOW_MINUTES
+#[derive(Debug, Clone)]
+pub struct LoginThrottleConfig {
+    // 계정 잠금까지의 연속 실패 횟수
+    pub max_failures: i32,
+    // IP 차단까지의 실패 횟수 (여러 계정 합산)
+    pub ip_max_failures: i32,
+    pub lockout: Duration,
+    // 마지막 실패 후 이 시간이 지나면 실패 횟수를 다시 셉니다.
+    pub failure_window: Duration,
+    // 이 횟수까지의 실패는 지연 없음
+    pub free_failures: i32,
+    pub delay_base: Duration,
+    pub delay_cap: Duration,
+}
+
+impl Default for LoginThrottleConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            ip_max_failures: 20,
+            lockout: Duration::from_secs(15 * 60),
+            failure_window: Duration::from_secs(15 * 60),
+            free_failures: 2,
+            delay_base: Duration::from_millis(500),
+            delay_cap: Duration::from_secs(4),
+        }
+    }
+}
+
+impl LoginThrottleConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            max_failures: env_u64("LOGIN_MAX_FAILURES")
+                .map(|v| v.clamp(1, 1000) as i32)
+                .unwrap_or(default.max_failures),
+            ip_max_failures: env_u64("LOGIN_IP_MAX_FAILURES")
+                .map(|v| v.clamp(1, 100_000) as i32)
+                .unwrap_or(default.ip_max_failures),
+            lockout: env_u64("LOGIN_LOCKOUT_MINUTES")
+                .map(|v| Duration::from_secs(v.max(1) * 60))
+                .unwrap_or(default.lockout),
+            failure_window: env_u64("LOGIN_FAILURE_WINDOW_MINUTES")
+                .map(|v| Duration::from_secs(v.max(1) * 60))
+                .unwrap_or(default.failure_window),
+            ..default
+        }
+    }
+
+    // 이전 실패가 failures번일 때 이번 시도 전에 기다릴 시간: base * 2^(failures - free - 1), 최대 cap
+    pub fn delay(&self, failures: i32) -> Duration {
+        if failures <= self.free_failures {
+            return Duration::ZERO;
+        }
+        let exponent = (failures - self.free_failures - 1).clamp(0, 20) as u32;
+        self.delay_base
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.delay_cap)
+    }
+}
@@ -1,3 +1,33 @@
 pub mod approval;
+pub mod attachment;
+pub mod chat;
+pub mod department;
+pub mod digest;
+pub mod email;
+pub mod event;
+pub mod form_schema;
+pub mod login;
+pub mod notification;
+pub mod pagination;
+pub mod realtime;
+pub mod role;
+pub mod search;
+pub mod session;
+pub mod stats;
 pub mod template;
+pub mod template_bundle;
+pub mod title_pattern;
 pub mod user;
+pub mod webhook;
+
+use serde::{Deserialize, Deserializer};
+
+// PATCH DTO용: 필드가 없으면 None, null이면 Some(None)
+// 필드에 #[serde(default, deserialize_with = "...")]와 함께 사용합니다.
+pub(crate) fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,153 @@
+use super::event::DomainEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+// [Notification Center]
+// 실시간 push(realtime)와 별개로 사용자별 알림 목록을 보관합니다. (읽음/안읽음)
+// - 결재 흐름 알림은 outbox 이벤트에서 만들어집니다. (services::notification::NotificationConsumer)
+// - 기한 임박 알림은 주기적으로 due_at을 확인해 만듭니다. (services::notification::DeadlineNotifier)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    // 내가 결재할 차례가 됨
+    Assigned,
+    // 내가 올린 문서가 최종 승인됨
+    Approved,
+    // 내가 올린 문서가 반려됨
+    Rejected,
+    // 내가 올린 문서가 보완 요청으로 되돌아옴
+    // (결재선에 아직 "되돌리기" 처리가 없어 현재는 만들어지지 않으며, 수신 설정만 가능합니다.)
+    Returned,
+    // 의견에서 @이메일 로 언급됨
+    Mentioned,
+    // 내가 결재할 문서의 기한이 다가옴
+    DeadlineApproaching,
+}
+
+impl NotificationType {
+    pub const ALL: [NotificationType; 6] = [
+        NotificationType::Assigned,
+        NotificationType::Approved,
+        NotificationType::Rejected,
+        NotificationType::Returned,
+        NotificationType::Mentioned,
+        NotificationType::DeadlineApproaching,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::Assigned => "assigned",
+            NotificationType::Approved => "approved",
+            NotificationType::Rejected => "rejected",
+            NotificationType::Returned => "returned",
+            NotificationType::Mentioned => "mentioned",
+            NotificationType::DeadlineApproaching => "deadline_approaching",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub notification_type: String,
+    pub approval_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    // 문서 제목
+    pub title: String,
+    pub data: Json<serde_json::Value>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// 저장 전 알림 (수신 설정에서 끈 유형은 저장 시 건너뜁니다)
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub notification_type: NotificationType,
+    pub approval_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub title: String,
+    pub data: serde_json::Value,
+    // 같은 사용자/유형에 같은 key는 한 번만 저장됩니다.
+    pub dedupe_key: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct NotificationListQuery {
+    // true면 안 읽은 알림만
+    #[serde(default)]
+    pub unread_only: bool,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationPreference {
+    pub notification_type: NotificationType,
+    pub enabled: bool,
+}
+
+// 도메인 이벤트로 생기는 알림 (행위자 본인은 제외)
+// mentioned: 의견에서 언급된 사용자 (CommentAdded일 때만 사용)
+pub fn notifications_for(
+    event: &DomainEvent,
+    actor_id: Uuid,
+    mentioned: &[Uuid],
+) -> Vec<(Uuid, NotificationType)> {
+    let approval = event.approval();
+    let mut recipients = Vec::new();
+    match event {
+        DomainEvent::RequestCreated { .. }
+        | DomainEvent::RequestSubmitted { .. }
+        | DomainEvent::StepApproved { .. } => {
+            if approval.status == "pending"
+                && let Some(approver_id) = approval.current_approver()
+            {
+                recipients.push((approver_id, NotificationType::Assigned));
+            }
+        }
+        DomainEvent::RequestCompleted { .. } => {
+            recipients.push((approval.requester_id, NotificationType::Approved));
+        }
+        DomainEvent::RequestRejected { .. } => {
+            recipients.push((approval.requester_id, NotificationType::Rejected));
+        }
+        DomainEvent::RequestWithdrawn { .. } => {}
+        DomainEvent::CommentAdded { .. } => {
+            for user_id in mentioned {
+                if !recipients.iter().any(|(id, _)| id == user_id) {
+                    recipients.push((*user_id, NotificationType::Mentioned));
+                }
+            }
+        }
+    }
+    recipients.retain(|(user_id, _)| *user_id != actor_id);
+    recipients
+}
+
+// 의견 내용에서 "@이메일" 형식의 언급을 찾습니다. (예: "@kim@pxm.com 확인 부탁드립니다")
+pub fn mentioned_emails(content: &str) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    for token in content.split_whitespace() {
+        // 문장 부호로 감싼 경우 (예: "@kim@pxm.com," / "(@kim@pxm.com)")
+        let token = token.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '@');
+        let Some(rest) = token.strip_prefix('@') else {
+            continue;
+        };
+        let email = rest
+            .trim_end_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        let valid = email
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'));
+        if valid && !emails.contains(&email) {
+            emails.push(email);
+        }
+    }
+    emails
+}
@@ -0,0 +1,156 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+// [Pagination]
+// 모든 목록 API는 keyset(cursor) 페이지네이션과 같은 응답 형식(Page)을 사용합니다.
+// - 요청: ?limit=20&cursor=<next_cursor>
+// - 응답: { "items": [...], "next_cursor": "..." | null, "total_count": 123 }
+// cursor는 (정렬 컬럼 값, id)를 base64url(JSON)로 인코딩한 값이며, 클라이언트는 그대로 되돌려 보내기만 합니다.
+
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total_count: i64,
+}
+
+impl<T> Page<T> {
+    // limit + 1건을 조회한 결과로 페이지를 만듭니다. 초과분이 있으면 마지막 항목으로 next_cursor를 만듭니다.
+    pub fn from_rows(
+        mut rows: Vec<T>,
+        limit: i64,
+        total_count: i64,
+        cursor_of: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+        let next_cursor = if has_more {
+            rows.last().map(|last| cursor_of(last).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items: rows,
+            next_cursor,
+            total_count,
+        }
+    }
+}
+
+// 단순 목록용 쿼리 파라미터
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+pub fn page_size(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor {
+    pub key: String, // 정렬 기준 (예: "created_at")
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(key: &str, value: impl Into<String>, id: Uuid) -> Self {
+        Self {
+            key: key.to_string(),
+            value: value.into(),
+            id,
+        }
+    }
+
+    // 마이크로초까지 보존해야 같은 시각의 행을 건너뛰지 않습니다.
+    pub fn at(key: &str, value: DateTime<Utc>, id: Uuid) -> Self {
+        Self::new(key, value.to_rfc3339_opts(SecondsFormat::AutoSi, true), id)
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    // 요청한 정렬 기준으로 만든 cursor인지까지 확인합니다.
+    pub fn decode(raw: &str, expected_key: &str) -> Result<Self, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(raw)
+            .map_err(|_| "Invalid cursor".to_string())?;
+        let cursor: Cursor =
+            serde_json::from_slice(&bytes).map_err(|_| "Invalid cursor".to_string())?;
+        if cursor.key != expected_key {
+            return Err("Cursor does not match the requested sort".to_string());
+        }
+        Ok(cursor)
+    }
+
+    pub fn timestamp(&self) -> Result<DateTime<Utc>, String> {
+        DateTime::parse_from_rfc3339(&self.value)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|_| "Invalid cursor".to_string())
+    }
+}
+
+// 시각 기준 정렬 목록의 cursor를 (value, id)로 풉니다. cursor가 없으면 첫 페이지입니다.
+pub fn timestamp_after(
+    raw: Option<&str>,
+    key: &str,
+) -> Result<Option<(DateTime<Utc>, Uuid)>, String> {
+    raw.map(|raw| {
+        let cursor = Cursor::decode(raw, key)?;
+        Ok((cursor.timestamp()?, cursor.id))
+    })
+    .transpose()
+}
+
+pub enum KeysetValue {
+    Timestamp(DateTime<Utc>),
+    Text(String),
+}
+
+// "(column, id) > (value, id)" 조건을 추가합니다. (DESC면 <)
+pub fn push_keyset(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    column: &str,
+    id_column: &str,
+    order: SortOrder,
+    value: KeysetValue,
+    id: Uuid,
+) {
+    let op = match order {
+        SortOrder::Asc => ">",
+        SortOrder::Desc => "<",
+    };
+    qb.push(format!("({column}, {id_column}) {op} ("));
+    match value {
+        KeysetValue::Timestamp(ts) => qb.push_bind(ts),
+        KeysetValue::Text(text) => qb.push_bind(text),
+    };
+    qb.push(", ").push_bind(id).push(")");
+}
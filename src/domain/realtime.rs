@@ -0,0 +1,95 @@
+use super::event::DomainEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+// [Realtime]
+// 도메인 이벤트를 "누구에게 무엇을 알릴지"로 바꿔 user_events에 저장하고,
+// GET /events/stream(SSE)으로 해당 사용자에게 보냅니다.
+
+// SSE event 이름
+pub const INBOX_ADDED: &str = "inbox.added"; // 내가 결재할 문서가 들어옴
+pub const INBOX_REMOVED: &str = "inbox.removed"; // 결재 대기 중이던 문서가 회수됨
+pub const REQUEST_UPDATED: &str = "request.updated"; // 내가 올린 문서가 진행/승인/반려됨
+pub const COMMENT_ADDED: &str = "comment.added"; // 내가 관여한 문서에 의견이 달림
+
+// NOTIFY payload(최대 8000 bytes)에 들어가도록 의견 내용은 앞부분만 보냅니다.
+const COMMENT_PREVIEW_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserEvent {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub event_type: String,
+    pub data: Json<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+// 이벤트를 받을 사용자와 SSE event 이름 (행위자 본인은 제외)
+pub fn recipients_of(event: &DomainEvent, actor_id: Uuid) -> Vec<(Uuid, &'static str)> {
+    let approval = event.approval();
+    let mut recipients = Vec::new();
+    match event {
+        DomainEvent::RequestCreated { .. } | DomainEvent::RequestSubmitted { .. } => {
+            if let Some(approver_id) = approval.current_approver() {
+                recipients.push((approver_id, INBOX_ADDED));
+            }
+        }
+        DomainEvent::StepApproved { .. } => {
+            if let Some(approver_id) = approval.current_approver() {
+                recipients.push((approver_id, INBOX_ADDED));
+            }
+            recipients.push((approval.requester_id, REQUEST_UPDATED));
+        }
+        DomainEvent::RequestCompleted { .. } | DomainEvent::RequestRejected { .. } => {
+            recipients.push((approval.requester_id, REQUEST_UPDATED));
+        }
+        DomainEvent::RequestWithdrawn { .. } => {
+            if let Some(approver_id) = approval.current_approver() {
+                recipients.push((approver_id, INBOX_REMOVED));
+            }
+        }
+        DomainEvent::CommentAdded { .. } => {
+            let participants = std::iter::once(approval.requester_id)
+                .chain(approval.flow_process.steps.iter().map(|s| s.approver_id))
+                .chain(approval.flow_process.references.iter().copied());
+            for user_id in participants {
+                if !recipients.iter().any(|(id, _)| *id == user_id) {
+                    recipients.push((user_id, COMMENT_ADDED));
+                }
+            }
+        }
+    }
+    // 임시저장 문서는 요청자 본인만 열람할 수 있습니다. (services::access_policy)
+    if approval.status == "draft" {
+        recipients.retain(|(user_id, _)| *user_id == approval.requester_id);
+    }
+    recipients.retain(|(user_id, _)| *user_id != actor_id);
+    recipients
+}
+
+// 클라이언트가 목록/배지를 갱신하는 데 필요한 최소한의 정보
+pub fn summary_of(event: &DomainEvent, actor_id: Uuid) -> serde_json::Value {
+    let approval = event.approval();
+    let mut data = serde_json::json!({
+        "source": event.event_type(),
+        "approval_id": approval.id,
+        "title": approval.title,
+        "status": approval.status,
+        "current_step": approval.flow_process.current_step,
+        "actor_id": actor_id,
+    });
+    if let DomainEvent::CommentAdded { comment, .. } = event {
+        let preview: String = comment
+            .content
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(COMMENT_PREVIEW_CHARS)
+            .collect();
+        data["comment"] = serde_json::json!({ "id": comment.id, "preview": preview });
+    }
+    data
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// [RBAC]
+// 권한 코드는 DB의 permissions 테이블과 1:1로 대응합니다.
+// 라우트는 main.rs에서 require_permission 레이어로 필요한 권한을 선언합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    TemplateManage,
+    UserManage,
+    RoleManage,
+    ApprovalReadAll,
+    StatsReadAll,
+    WebhookManage,
+    EmailTemplateManage,
+    ChatManage,
+}
+
+impl Permission {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Permission::TemplateManage => "template:manage",
+            Permission::UserManage => "user:manage",
+            Permission::RoleManage => "role:manage",
+            Permission::ApprovalReadAll => "approval:read_all",
+            Permission::StatsReadAll => "stats:read_all",
+            Permission::WebhookManage => "webhook:manage",
+            Permission::EmailTemplateManage => "email_template:manage",
+            Permission::ChatManage => "chat:manage",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Role {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+// 인증된 사용자의 역할/권한 (auth_middleware가 요청 Extension으로 주입)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UserRoles {
+    pub roles: Vec<String>,
+    pub permissions: HashSet<String>,
+}
+
+impl UserRoles {
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions.contains(permission.code())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignRolesDto {
+    pub roles: Vec<String>,
+}
@@ -0,0 +1,158 @@
+use super::approval::ApprovalRequest;
+use super::email::escape_html;
+use serde::{Deserialize, Serialize};
+
+// [Search]
+// GET /approvals/search?q=출장 호텔
+// - 검색어는 공백으로 나눈 단어들의 AND 조건입니다.
+// - 단어 단위 매칭: search_vector @@ '출장:* & 호텔:*' (접두어 매칭이라 "출장을"도 찾음)
+// - 부분 문자열 매칭: search_text ILIKE '%요청%' (pg_trgm 인덱스, 한국어 복합어 대응)
+
+pub const MAX_QUERY_TERMS: usize = 8;
+const SNIPPET_CONTEXT: usize = 40; // 첫 매칭 앞뒤로 보여줄 글자 수
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let terms: Vec<String> = raw
+            .split_whitespace()
+            .map(|term| {
+                // tsquery 연산자로 해석되는 문자는 제거
+                term.chars()
+                    .filter(|c| !"&|!():*<>'\\".contains(*c))
+                    .collect::<String>()
+            })
+            .filter(|term| !term.is_empty())
+            .take(MAX_QUERY_TERMS)
+            .collect();
+
+        if terms.is_empty() {
+            return Err("Search query is empty".to_string());
+        }
+        Ok(Self { terms })
+    }
+
+    // to_tsquery('simple', ...) 입력: 단어별 접두어 매칭의 AND
+    pub fn tsquery(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| format!("'{}':*", term))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+
+    // ILIKE 패턴 (%, _ 이스케이프)
+    pub fn like_patterns(&self) -> Vec<String> {
+        self.terms
+            .iter()
+            .map(|term| {
+                let escaped = term
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{}%", escaped)
+            })
+            .collect()
+    }
+
+    pub fn text(&self) -> String {
+        self.terms.join(" ")
+    }
+
+    // 첫 매칭 주변을 잘라 검색어를 <mark>로 감쌉니다. (대소문자 무시)
+    // 결과는 HTML로 렌더링되므로 <mark> 외의 원문은 모두 이스케이프합니다.
+    pub fn highlight(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+        // to_lowercase가 글자 수를 바꾸는 경우(일부 특수 문자)는 원문 그대로 비교
+        let haystack = if lower.len() == chars.len() {
+            lower
+        } else {
+            chars.clone()
+        };
+        let needles: Vec<Vec<char>> = self
+            .terms
+            .iter()
+            .map(|t| t.to_lowercase().chars().collect())
+            .collect();
+
+        let matches_at = |idx: usize| {
+            needles
+                .iter()
+                .filter(|n| haystack[idx..].starts_with(n))
+                .map(|n| n.len())
+                .max()
+        };
+
+        let first = (0..chars.len()).find(|&idx| matches_at(idx).is_some());
+        let (start, end) = match first {
+            Some(idx) => (
+                idx.saturating_sub(SNIPPET_CONTEXT),
+                (idx + SNIPPET_CONTEXT * 2).min(chars.len()),
+            ),
+            None => (0, (SNIPPET_CONTEXT * 2).min(chars.len())),
+        };
+
+        let mut out = String::new();
+        if start > 0 {
+            out.push('…');
+        }
+        let mut plain = String::new();
+        let mut idx = start;
+        while idx < end {
+            match matches_at(idx) {
+                Some(len) => {
+                    let stop = (idx + len).min(chars.len());
+                    out.push_str(&escape_html(&std::mem::take(&mut plain)));
+                    out.push_str(HIGHLIGHT_START);
+                    out.push_str(&escape_html(&chars[idx..stop].iter().collect::<String>()));
+                    out.push_str(HIGHLIGHT_END);
+                    idx = stop;
+                }
+                None => {
+                    plain.push(if chars[idx] == '\n' { ' ' } else { chars[idx] });
+                    idx += 1;
+                }
+            }
+        }
+        out.push_str(&escape_html(&plain));
+        if idx < chars.len() {
+            out.push('…');
+        }
+        out
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SearchRow {
+    #[sqlx(flatten)]
+    pub request: ApprovalRequest,
+    pub rank: f32,
+    pub search_text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub request: ApprovalRequest,
+    pub rank: f32,
+    pub highlight: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub items: Vec<SearchHit>,
+    pub total_count: i64,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// [Session]
+// 로그인 1회 = 세션 1개. access token(JWT)에는 세션 id(sid)가 들어가고,
+// refresh token은 세션에 묶여 매번 새 값으로 교체(rotation)됩니다.
+// 세션이 폐기되면(로그아웃, refresh token 재사용 등) 그 세션의 access token도 바로 거부됩니다.
+
+// 폐기 사유 (auth_sessions.revoke_reason)
+pub const REVOKE_LOGOUT: &str = "logout";
+pub const REVOKE_LOGOUT_ALL: &str = "logout_all";
+pub const REVOKE_REFRESH_REUSED: &str = "refresh_token_reused";
+pub const REVOKE_DEACTIVATED: &str = "deactivated";
+pub const REVOKE_PASSWORD_CHANGED: &str = "password_changed";
+pub const REVOKE_PASSWORD_RESET: &str = "password_reset";
+
+// refresh token 교체 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefreshOutcome {
+    Rotated { session_id: Uuid, user_id: Uuid },
+    // 이미 사용된 토큰 -> 세션 전체 폐기
+    Reused { session_id: Uuid },
+    // 없거나 만료되었거나 폐기된 세션
+    Invalid,
+}
+
+// POST /auth/refresh
+#[derive(Debug, Deserialize)]
+pub struct RefreshDto {
+    pub refresh_token: String,
+}
+
+// POST /auth/logout (everywhere = true면 내 모든 세션)
+#[derive(Debug, Default, Deserialize)]
+pub struct LogoutDto {
+    #[serde(default)]
+    pub everywhere: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenResponse {
+    pub token: String,
+    pub refresh_token: String,
+    // access token 유효 시간 (초)
+    pub expires_in: u64,
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// [Approval Analytics]
+// 상신 시각: SUBMITTED 이력(임시저장 후 상신) 또는 생성 시각
+// 완료 시각: 승인/반려된 문서에서 마지막으로 처리된 결재 단계의 timestamp
+// 단계 소요 시간: 이전 단계 처리 시각(1단계는 상신 시각) ~ 해당 단계 처리 시각
+// 시간 단위는 모두 hour(실수)입니다.
+
+// GET /stats/...?from=2026-01-01T00:00:00Z&to=2026-02-01T00:00:00Z&department_id=...
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsQuery {
+    // 상신 시각 기준 [from, to)
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    // 요청자 소속 부서 (하위 부서 포함)
+    pub department_id: Option<Uuid>,
+}
+
+// 조회 범위: 전사(stats:read_all) 또는 본인이 부서장인 부서(하위 부서 포함)
+#[derive(Debug, Clone, Copy)]
+pub enum StatsScope {
+    All,
+    ManagedBy(Uuid),
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TemplateStats {
+    pub template_id: Option<Uuid>,
+    pub template_name: Option<String>,
+    pub submitted: i64,
+    pub completed: i64,
+    pub rejected: i64,
+    pub rejection_rate: Option<f64>, // rejected / completed
+    pub avg_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct StepStats {
+    pub template_id: Option<Uuid>,
+    pub template_name: Option<String>,
+    pub seq: i64,
+    pub step_name: String,
+    pub processed: i64,
+    pub rejected: i64,
+    pub rejection_rate: Option<f64>,
+    pub avg_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ApproverStats {
+    pub approver_id: Uuid,
+    pub approver_name: Option<String>,
+    pub processed: i64,
+    pub rejected: i64,
+    pub rejection_rate: Option<f64>,
+    pub avg_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+// 현재 결재 대기 건수 (기간 필터와 무관하게 지금 쌓여 있는 문서)
+#[derive(Debug, Serialize, FromRow)]
+pub struct BacklogStats {
+    pub approver_id: Uuid,
+    pub approver_name: Option<String>,
+    pub pending: i64,
+    pub oldest_waiting_since: Option<DateTime<Utc>>,
+    pub avg_waiting_hours: Option<f64>,
+}
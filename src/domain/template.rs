@@ -16,6 +16,18 @@ pub struct Template {
     // 이 템플릿으로 생성될 때 기본적으로 적용될 결재선
     pub workflow_snapshot: Json<FlowProcess>,
 
+    pub category_code: Option<String>,
+
+    // 결재 제목 패턴 (domain::title_pattern 참고). 없으면 "{템플릿명} - {날짜}"
+    pub title_pattern: Option<String>,
+
+    // 이 템플릿을 사용할 수 있는 부서 목록 (하위 부서 포함). 비어 있으면 전체 공개.
+    pub visible_department_ids: Vec<Uuid>,
+
+    // 보관(archive)된 템플릿은 목록에서 숨겨지고 새 결재 요청에 사용할 수 없습니다.
+    pub is_archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -26,4 +38,42 @@ pub struct CreateTemplateDto {
     pub description: Option<String>,
     pub form_schema: serde_json::Value,
     pub workflow_snapshot: FlowProcess,
+    pub category_code: Option<String>,
+    pub title_pattern: Option<String>,
+    #[serde(default)]
+    pub visible_department_ids: Vec<Uuid>,
+}
+
+// PATCH용 DTO: 전달된 필드만 변경합니다.
+#[derive(Debug, Deserialize)]
+pub struct PatchTemplateDto {
+    pub name: Option<String>,
+    // null이면 설명을 지웁니다.
+    #[serde(default, deserialize_with = "super::double_option")]
+    pub description: Option<Option<String>>,
+    pub form_schema: Option<serde_json::Value>,
+    pub workflow_snapshot: Option<FlowProcess>,
+    // null이면 카테고리를 해제합니다.
+    #[serde(default, deserialize_with = "super::double_option")]
+    pub category_code: Option<Option<String>>,
+    // null이면 기본 제목 형식으로 되돌립니다.
+    #[serde(default, deserialize_with = "super::double_option")]
+    pub title_pattern: Option<Option<String>>,
+    pub visible_department_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListTemplatesQuery {
+    #[serde(default)]
+    pub include_archived: bool,
+    pub category: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TemplateCategory {
+    pub code: String,
+    pub name: String,
+    pub sort_order: i32,
 }
@@ -0,0 +1,142 @@
+use super::template::Template;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// [Template Bundle]
+// 환경(dev/staging/prod) 간 템플릿을 옮기기 위한 이식 가능한(portable) 포맷입니다.
+// 환경마다 다른 user UUID 대신 email / 부서 코드를 사용하고, import 시 다시 UUID로 변환합니다.
+
+pub const BUNDLE_FORMAT: &str = "pxm.template-bundle";
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub template: BundleTemplate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub category_code: Option<String>,
+    pub title_pattern: Option<String>,
+    pub form_schema: serde_json::Value,
+    pub workflow: BundleWorkflow,
+    // 공개 부서는 부서 코드로 표현합니다 (예: "IT00")
+    #[serde(default)]
+    pub visible_departments: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleWorkflow {
+    pub steps: Vec<BundleStep>,
+    // 참조자
+    #[serde(default)]
+    pub references: Vec<PortableApprover>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleStep {
+    pub seq: i32,
+    pub name: String,
+    pub approver: PortableApprover,
+}
+
+// 결재자 식별자
+// - email: 사용자 이메일 (export 기본값)
+// - department_manager: 해당 부서 코드의 부서장 (import 시점에 해석)
+// 환경마다 다른 UUID는 번들에 쓰지 않습니다. 원본에서 사용자를 찾지 못하면 export가 실패합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PortableApprover {
+    Email { email: String },
+    DepartmentManager { department_code: String },
+}
+
+impl PortableApprover {
+    pub fn identifier(&self) -> String {
+        match self {
+            PortableApprover::Email { email } => email.clone(),
+            PortableApprover::DepartmentManager { department_code } => {
+                format!("manager of {}", department_code)
+            }
+        }
+    }
+}
+
+impl TemplateBundle {
+    pub fn new(
+        template: &Template,
+        workflow: BundleWorkflow,
+        visible_departments: Vec<String>,
+    ) -> Self {
+        Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            template: BundleTemplate {
+                name: template.name.clone(),
+                description: template.description.clone(),
+                category_code: template.category_code.clone(),
+                title_pattern: template.title_pattern.clone(),
+                form_schema: template.form_schema.0.clone(),
+                workflow,
+                visible_departments,
+            },
+        }
+    }
+
+    // 지원하지 않는 포맷/버전은 import 전에 거부합니다.
+    pub fn check_version(&self) -> Result<(), String> {
+        if self.format != BUNDLE_FORMAT {
+            return Err(format!("Unsupported bundle format '{}'", self.format));
+        }
+        if self.version == 0 || self.version > BUNDLE_VERSION {
+            return Err(format!("Unsupported bundle version {}", self.version));
+        }
+        Ok(())
+    }
+}
+
+// Export 실패 리포트: 이식 가능한 식별자로 바꾸지 못한 참조 목록
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportReport {
+    pub unresolved: Vec<UnresolvedReference>,
+}
+
+// Import 결과 리포트 (dry-run 시에도 동일하게 반환)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub resolved: Vec<ResolvedReference>,
+    pub unresolved: Vec<UnresolvedReference>,
+    #[serde(default)]
+    pub conflicts: Vec<ImportConflict>,
+    pub template: Option<Template>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedReference {
+    pub path: String, // e.g. "workflow.steps[0].approver"
+    pub identifier: String,
+    pub resolved_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    pub path: String,
+    pub identifier: String,
+    pub reason: String,
+}
+
+// 대상 환경에 이미 같은 이름의 (보관되지 않은) 템플릿이 있는 경우
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub path: String,
+    pub identifier: String,
+    pub existing_id: Uuid,
+}
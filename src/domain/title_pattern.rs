@@ -0,0 +1,179 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, TimeZone};
+use std::fmt::{Display, Write};
+use thiserror::Error;
+
+// [Title Pattern]
+// 템플릿에 정의된 제목 패턴을 form_data, 요청자 정보로 렌더링합니다.
+// 예: "[Expense] {{form.purpose}} - {{requester.full_name}} ({{date:%Y-%m-%d}})"
+//
+// 지원하는 placeholder:
+// - {{form.<path>}}          form_data의 값 (중첩 객체는 "a.b.c")
+// - {{requester.full_name}}  {{requester.email}}  {{requester.position}}
+// - {{department.name}}      {{department.code}}
+// - {{template.name}}
+// - {{date}} / {{date:<strftime>}}  조직 타임존 기준 현재 시각 (기본 "%Y-%m-%d")
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+// pxm_approval_requests.title 컬럼 길이 (VARCHAR(255))
+const MAX_TITLE_LENGTH: usize = 255;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TitlePatternError {
+    #[error("Unclosed placeholder at position {0}")]
+    Unclosed(usize),
+    #[error("Empty placeholder at position {0}")]
+    Empty(usize),
+    #[error("Unknown placeholder '{{{{{0}}}}}'")]
+    Unknown(String),
+    #[error("Invalid date format '{0}'")]
+    InvalidDateFormat(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Placeholder {
+    Form(Vec<String>),
+    RequesterFullName,
+    RequesterEmail,
+    RequesterPosition,
+    DepartmentName,
+    DepartmentCode,
+    TemplateName,
+    Date(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitlePattern {
+    segments: Vec<Segment>,
+}
+
+// 렌더링에 필요한 값들
+pub struct TitleContext<'a, Tz: TimeZone> {
+    pub form_data: &'a serde_json::Value,
+    pub requester_full_name: &'a str,
+    pub requester_email: &'a str,
+    pub requester_position: Option<&'a str>,
+    pub department_name: Option<&'a str>,
+    pub department_code: Option<&'a str>,
+    pub template_name: &'a str,
+    pub now: DateTime<Tz>,
+}
+
+impl TitlePattern {
+    pub fn parse(pattern: &str) -> Result<Self, TitlePatternError> {
+        let mut segments = Vec::new();
+        let mut rest = pattern;
+        let mut offset = 0;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let after_open = &rest[start + 2..];
+            let end = after_open
+                .find("}}")
+                .ok_or(TitlePatternError::Unclosed(offset + start))?;
+
+            let expr = after_open[..end].trim();
+            if expr.is_empty() {
+                return Err(TitlePatternError::Empty(offset + start));
+            }
+            segments.push(Segment::Placeholder(parse_placeholder(expr)?));
+
+            let consumed = start + 2 + end + 2;
+            offset += consumed;
+            rest = &rest[consumed..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+
+    pub fn render<Tz: TimeZone>(&self, ctx: &TitleContext<'_, Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        let mut title = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => title.push_str(text),
+                Segment::Placeholder(placeholder) => match placeholder {
+                    Placeholder::Form(path) => title.push_str(&form_value(ctx.form_data, path)),
+                    Placeholder::RequesterFullName => title.push_str(ctx.requester_full_name),
+                    Placeholder::RequesterEmail => title.push_str(ctx.requester_email),
+                    Placeholder::RequesterPosition => {
+                        title.push_str(ctx.requester_position.unwrap_or_default())
+                    }
+                    Placeholder::DepartmentName => {
+                        title.push_str(ctx.department_name.unwrap_or_default())
+                    }
+                    Placeholder::DepartmentCode => {
+                        title.push_str(ctx.department_code.unwrap_or_default())
+                    }
+                    Placeholder::TemplateName => title.push_str(ctx.template_name),
+                    // 저장 시 검증되지만, 잘못된 포맷이 DB에 있어도 panic 대신 생략합니다.
+                    Placeholder::Date(format) => {
+                        let _ = write!(title, "{}", ctx.now.format(format));
+                    }
+                },
+            }
+        }
+
+        let title = title.trim();
+        match title.char_indices().nth(MAX_TITLE_LENGTH) {
+            Some((idx, _)) => title[..idx].to_string(),
+            None => title.to_string(),
+        }
+    }
+}
+
+fn parse_placeholder(expr: &str) -> Result<Placeholder, TitlePatternError> {
+    if let Some(path) = expr.strip_prefix("form.") {
+        let keys: Vec<String> = path.split('.').map(|k| k.trim().to_string()).collect();
+        if keys.iter().any(|k| k.is_empty()) {
+            return Err(TitlePatternError::Unknown(expr.to_string()));
+        }
+        return Ok(Placeholder::Form(keys));
+    }
+
+    if expr == "date" {
+        return Ok(Placeholder::Date(DEFAULT_DATE_FORMAT.to_string()));
+    }
+    if let Some(format) = expr.strip_prefix("date:") {
+        let valid = !format.is_empty()
+            && StrftimeItems::new(format).all(|item| !matches!(item, Item::Error));
+        if !valid {
+            return Err(TitlePatternError::InvalidDateFormat(format.to_string()));
+        }
+        return Ok(Placeholder::Date(format.to_string()));
+    }
+
+    match expr {
+        "requester.full_name" => Ok(Placeholder::RequesterFullName),
+        "requester.email" => Ok(Placeholder::RequesterEmail),
+        "requester.position" => Ok(Placeholder::RequesterPosition),
+        "department.name" => Ok(Placeholder::DepartmentName),
+        "department.code" => Ok(Placeholder::DepartmentCode),
+        "template.name" => Ok(Placeholder::TemplateName),
+        _ => Err(TitlePatternError::Unknown(expr.to_string())),
+    }
+}
+
+// 값이 없으면 빈 문자열, 문자열은 따옴표 없이, 그 외에는 JSON 표현을 사용합니다.
+fn form_value(form_data: &serde_json::Value, path: &[String]) -> String {
+    let value = path.iter().try_fold(form_data, |value, key| value.get(key));
+    match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
@@ -1,3 +1,4 @@
+use super::role::{Permission, UserRoles};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -17,6 +18,43 @@ pub struct User {
     pub last_login_at: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>, // Made Option to match sometimes missing in DB if default
     pub updated_at: Option<DateTime<Utc>>,
+    // 알림 메일 언어 ("ko" | "en")
+    pub locale: String,
+}
+
+pub const STATUS_ACTIVE: &str = "ACTIVE";
+pub const USER_STATUSES: [&str; 3] = [STATUS_ACTIVE, "INACTIVE", "SUSPENDED"];
+
+// PUT /org/users/:id/status { "status": "SUSPENDED" }
+#[derive(Debug, Deserialize)]
+pub struct UserStatusDto {
+    pub status: String,
+}
+
+// 인증된 요청의 사용자 (auth_middleware가 Extension으로 주입)
+// 핸들러 예: Extension(AuthUser { id: user_id, roles, .. }): Extension<AuthUser>
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub department_id: Option<Uuid>,
+    pub roles: UserRoles,
+    // 현재 세션 (auth_sessions.id)
+    pub session_id: Uuid,
+}
+
+impl AuthUser {
+    pub fn has(&self, permission: Permission) -> bool {
+        self.roles.has(permission)
+    }
+}
+
+// 사용자 목록용 (조직도 검색 등)
+#[derive(Debug, Serialize, FromRow)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub full_name: String,
+    pub email: String,
+    pub position: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -37,8 +75,34 @@ pub struct LoginDto {
     pub password: String,
 }
 
+// POST /auth/password/change (현재 세션 외의 세션은 모두 폐기)
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangePasswordDto {
+    pub old_password: String,
+    #[validate(length(min = 6))]
+    pub new_password: String,
+}
+
+// POST /auth/password/forgot
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordDto {
+    pub email: String,
+}
+
+// POST /auth/password/reset (token은 메일 등으로 받은 재설정 토큰 원문)
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResetPasswordDto {
+    pub token: String,
+    #[validate(length(min = 6))]
+    pub new_password: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
+    // 짧게 유효한 access token (JWT)
     pub token: String,
+    // POST /auth/refresh 로 새 access token을 받을 때 사용 (한 번 쓰면 교체됨)
+    pub refresh_token: String,
+    pub expires_in: u64,
     pub user: User,
 }
@@ -0,0 +1,186 @@
+use super::event::{DomainEvent, OutboxEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+// [Webhook]
+// 결재 상태가 바뀔 때마다 외부 시스템(ERP, HR 등)에 알리는 outgoing webhook입니다.
+// outbox의 도메인 이벤트(services::webhook::WebhookConsumer)에서 만들어지며, 이벤트 하나는 조건(event_types, template_id)에 맞는 구독마다 webhook_deliveries 한 행이 되고,
+// services::webhook::WebhookDispatcher가 서명(HMAC-SHA256)해서 전송/재시도합니다.
+
+// 상태 전이 1건 = 이벤트 1건 (이벤트 이름은 전이 후 상태)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "approval.drafted")]
+    Drafted,
+    #[serde(rename = "approval.submitted")]
+    Submitted,
+    #[serde(rename = "approval.step_approved")]
+    StepApproved,
+    #[serde(rename = "approval.approved")]
+    Approved,
+    #[serde(rename = "approval.rejected")]
+    Rejected,
+    #[serde(rename = "approval.withdrawn")]
+    Withdrawn,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::Drafted => "approval.drafted",
+            WebhookEventType::Submitted => "approval.submitted",
+            WebhookEventType::StepApproved => "approval.step_approved",
+            WebhookEventType::Approved => "approval.approved",
+            WebhookEventType::Rejected => "approval.rejected",
+            WebhookEventType::Withdrawn => "approval.withdrawn",
+        }
+    }
+
+    // 도메인 이벤트 -> webhook 이벤트 (상태 변경이 아닌 이벤트는 None)
+    pub fn for_domain(event: &DomainEvent) -> Option<Self> {
+        match event {
+            DomainEvent::RequestCreated { approval } if approval.status == "draft" => {
+                Some(WebhookEventType::Drafted)
+            }
+            DomainEvent::RequestCreated { .. } | DomainEvent::RequestSubmitted { .. } => {
+                Some(WebhookEventType::Submitted)
+            }
+            DomainEvent::StepApproved { .. } => Some(WebhookEventType::StepApproved),
+            DomainEvent::RequestCompleted { .. } => Some(WebhookEventType::Approved),
+            DomainEvent::RequestRejected { .. } => Some(WebhookEventType::Rejected),
+            DomainEvent::RequestWithdrawn { .. } => Some(WebhookEventType::Withdrawn),
+            DomainEvent::CommentAdded { .. } => None,
+        }
+    }
+}
+
+// 구독에 전달할 이벤트 (payload는 구독과 무관하게 한 번만 만들어 모든 delivery가 공유)
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub template_id: Option<Uuid>,
+    pub payload: serde_json::Value,
+}
+
+impl WebhookEvent {
+    // id는 outbox 이벤트 id를 그대로 사용합니다. (같은 이벤트를 다시 받아도 delivery는 한 번만 생성)
+    // payload 예:
+    // { "id": "...", "type": "approval.approved", "occurred_at": "...", "actor_id": "...",
+    //   "data": { "approval": { ...ApprovalRequest } } }
+    pub fn from_outbox(event: &OutboxEvent) -> Option<Self> {
+        let domain_event = &event.payload.0;
+        let event_type = WebhookEventType::for_domain(domain_event)?;
+        let approval = domain_event.approval();
+        let payload = serde_json::json!({
+            "id": event.id,
+            "type": event_type.as_str(),
+            "occurred_at": event.created_at,
+            "actor_id": event.actor_id,
+            "data": { "approval": approval },
+        });
+        Some(Self {
+            id: event.id,
+            event_type,
+            template_id: approval.template_id,
+            payload,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing)] // 생성 응답에서만 한 번 보여줍니다.
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub template_id: Option<Uuid>,
+    pub is_active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// POST /webhooks
+// Body: { "name": "ERP", "url": "https://erp.example.com/hooks/pxm",
+//         "event_types": ["approval.approved"], "template_id": null }
+// secret을 생략하면 서버가 생성합니다.
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookDto {
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub event_types: Vec<WebhookEventType>,
+    pub template_id: Option<Uuid>,
+}
+
+// PATCH /webhooks/:id - 보낸 필드만 변경
+#[derive(Debug, Default, Deserialize)]
+pub struct PatchWebhookDto {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub event_types: Option<Vec<WebhookEventType>>,
+    pub template_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Succeeded,
+    Dead,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Succeeded => "succeeded",
+            DeliveryStatus::Dead => "dead",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: Json<serde_json::Value>,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// GET /webhooks/:id/deliveries?status=dead&cursor=...&limit=20
+#[derive(Debug, Default, Deserialize)]
+pub struct DeliveryListQuery {
+    pub status: Option<DeliveryStatus>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// 전송할 차례가 된 delivery (구독의 url/secret 포함)
+#[derive(Debug, FromRow)]
+pub struct DueDelivery {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: Json<serde_json::Value>,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
@@ -1,12 +1,20 @@
 use crate::{
-    domain::approval::{ApprovalAction, FlowProcess},
-    repositories::approval_repository::ApprovalRepository,
+    domain::{
+        approval::{ApprovalAction, ApprovalListQuery, ApprovalRequest, FlowProcess, InboxBox},
+        pagination::{PageQuery, page_size, timestamp_after},
+        role::UserRoles,
+        search::{SearchParams, SearchQuery},
+        user::AuthUser,
+    },
+    repositories::approval_repository::{ApprovalRepository, ApprovalScope},
+    services::access_policy::ApprovalAccessPolicy,
 };
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -17,32 +25,41 @@ pub struct CreateApprovalRequestDto {
     // requester_id is removed from DTO, will use Auth
     pub form_data: serde_json::Value,
     pub flow_process: FlowProcess,
+    // true면 임시저장 (POST /approvals/:id/submit 으로 상신)
+    #[serde(default)]
+    pub draft: bool,
 }
 
 pub async fn create_approval(
     State(pool): State<PgPool>,
-    Extension(user_id): Extension<Uuid>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
     Json(payload): Json<CreateApprovalRequestDto>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
+    // 생성 이력(CREATED)과 RequestCreated 이벤트는 저장과 같은 트랜잭션에서 기록됩니다.
     let repo = ApprovalRepository::new(pool);
 
-    let request = repo
-        .create(
+    let created = if payload.draft {
+        repo.create_draft(
+            payload.title,
+            user_id,
+            payload.form_data,
+            payload.flow_process,
+            None,
+        )
+        .await
+    } else {
+        repo.create(
             payload.title,
             user_id, // Use authenticated user
             payload.form_data,
             payload.flow_process,
         )
         .await
-        .map_err(|e| {
-            eprintln!("Failed to create approval request: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    // Log creation
-    let _ = repo
-        .add_log(request.id, user_id, "CREATED".to_string(), None)
-        .await;
+    };
+    let request = created.map_err(|e| {
+        eprintln!("Failed to create approval request: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     Ok(Json(serde_json::json!(request)))
 }
@@ -50,28 +67,49 @@ pub async fn create_approval(
 pub async fn get_approval(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
-    Extension(_user_id): Extension<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let repo = ApprovalRepository::new(pool);
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let request = find_visible_request(&pool, id, user_id, &roles).await?;
+    Ok(Json(serde_json::json!(request)))
+}
 
-    match repo.find_by_id(id).await {
-        Ok(Some(request)) => {
-            // TODO: Use user_id to filter permissions if needed (e.g. only involved people can view)
-            Ok(Json(serde_json::json!(request)))
-        }
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(e) => {
+// 결재 요청을 조회하고 열람 권한을 확인합니다. (services::access_policy)
+pub(crate) async fn find_visible_request(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    roles: &UserRoles,
+) -> Result<ApprovalRequest, (StatusCode, String)> {
+    let request = ApprovalRepository::new(pool.clone())
+        .find_by_id(id)
+        .await
+        .map_err(|e| {
             eprintln!("Failed to get approval request: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
+
+    let can_view = ApprovalAccessPolicy::new(pool.clone())
+        .can_view(user_id, roles, &request)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !can_view {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "You are not allowed to view this request".to_string(),
+        ));
     }
+
+    Ok(request)
 }
 
 // Handler for APPROVE
 pub async fn approve_request(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
-    Extension(user_id): Extension<Uuid>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     process_action_internal(id, ApprovalAction::Approve, None, pool, user_id).await
 }
@@ -85,7 +123,7 @@ pub struct RejectDto {
 pub async fn reject_request(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
-    Extension(user_id): Extension<Uuid>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
     Json(payload): Json<RejectDto>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     process_action_internal(
@@ -106,8 +144,19 @@ async fn process_action_internal(
     pool: PgPool,
     actor_id: Uuid,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    let repo = ApprovalRepository::new(pool);
+    let updated =
+        apply_action(&ApprovalRepository::new(pool), id, action, reason, actor_id).await?;
+    Ok(Json(serde_json::json!(updated)))
+}
 
+// 현재 결재자의 승인/반려 처리 (REST API와 채팅 버튼 콜백이 함께 사용)
+pub(crate) async fn apply_action(
+    repo: &ApprovalRepository,
+    id: Uuid,
+    action: ApprovalAction,
+    reason: Option<String>,
+    actor_id: Uuid,
+) -> Result<ApprovalRequest, (StatusCode, String)> {
     // 1. Fetch
     let mut request = repo
         .find_by_id(id)
@@ -115,6 +164,14 @@ async fn process_action_internal(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
 
+    // 진행 중(pending)인 문서만 결재할 수 있습니다. (임시저장/완료 문서 제외)
+    if request.status != "pending" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Request is not in progress".to_string(),
+        ));
+    }
+
     // Validate that actor_id matches the current approver
     let current_step_idx = (request.flow_process.0.current_step - 1) as usize;
     let expected_approver_id = match request.flow_process.0.steps.get(current_step_idx) {
@@ -142,20 +199,120 @@ async fn process_action_internal(
         request.status = "rejected".to_string();
     }
 
-    // 3. Update DB
-    let updated = repo
-        .update(request)
+    // 3. Update DB + Log + Event (한 트랜잭션)
+    // 동시에 들어온 다른 승인/반려가 먼저 저장됐다면 409
+    repo.record_action(request, actor_id, action, reason)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "Request was already processed by another action".to_string(),
+        ))
+}
 
-    // 4. Log
-    let log_action = match action {
-        ApprovalAction::Approve => "APPROVED",
-        ApprovalAction::Reject => "REJECTED",
-    };
-    let _ = repo
-        .add_log(id, actor_id, log_action.to_string(), reason)
-        .await;
+// POST /approvals/:id/submit - 임시저장 문서 상신 (요청자 본인만)
+pub async fn submit_approval(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let repo = ApprovalRepository::new(pool);
+
+    let request = repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
+
+    if request.requester_id != user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the requester can submit this request".to_string(),
+        ));
+    }
+
+    let submitted = repo
+        .submit(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "Only draft requests can be submitted".to_string(),
+        ))?;
+
+    Ok(Json(serde_json::json!(submitted)))
+}
+
+// POST /approvals/:id/withdraw - 진행 중(pending)인 문서 회수 (요청자 본인만)
+pub async fn withdraw_approval(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let repo = ApprovalRepository::new(pool);
+
+    let request = repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
+
+    if request.requester_id != user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the requester can withdraw this request".to_string(),
+        ));
+    }
+
+    let withdrawn = repo
+        .withdraw(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "Only requests in progress can be withdrawn".to_string(),
+        ))?;
+
+    Ok(Json(serde_json::json!(withdrawn)))
+}
+
+#[derive(Deserialize)]
+pub struct DueDateDto {
+    // null이면 기한 해제
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+// PUT /approvals/:id/due-date - 결재 기한 지정/변경/해제 (요청자 본인만, 임시저장/진행 중 문서)
+// 현재 결재자에게 기한 임박 알림(deadline_approaching)이 갑니다.
+pub async fn set_due_date(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<DueDateDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let repo = ApprovalRepository::new(pool);
+
+    let request = repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
+
+    if request.requester_id != user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the requester can set the due date".to_string(),
+        ));
+    }
+
+    let updated = repo
+        .set_due_at(id, payload.due_at)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "Due date can only be set on drafts or requests in progress".to_string(),
+        ))?;
 
     Ok(Json(serde_json::json!(updated)))
 }
@@ -168,59 +325,133 @@ pub struct CommentDto {
 pub async fn add_comment(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
-    Extension(user_id): Extension<Uuid>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
     Json(payload): Json<CommentDto>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    // Ensure request exists and is visible to the commenter
+    find_visible_request(&pool, id, user_id, &roles).await?;
+
     let repo = ApprovalRepository::new(pool);
 
-    // Ensure request exists
-    let _request = repo
-        .find_by_id(id)
+    // Log comment (+ CommentAdded 이벤트)
+    let log = repo
+        .add_comment(id, user_id, payload.content)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
 
-    // Log comment
-    let log = repo
-        .add_log(
-            id,
-            user_id, // Use authenticated user
-            "COMMENT".to_string(),
-            Some(payload.content),
-        )
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
     Ok(Json(serde_json::json!(log)))
 }
 
+// GET /approvals/:id/logs?limit=&cursor= (작성 순)
 pub async fn get_logs(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    find_visible_request(&pool, id, user_id, &roles).await?;
+
+    let after = timestamp_after(params.cursor.as_deref(), "created_at")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
     let repo = ApprovalRepository::new(pool);
-    match repo.get_logs(id).await {
-        Ok(logs) => Ok(Json(serde_json::json!(logs))),
+    match repo.get_logs_page(id, after, page_size(params.limit)).await {
+        Ok(page) => Ok(Json(serde_json::json!(page))),
         Err(e) => {
             eprintln!("Failed to fetch logs: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
         }
     }
 }
 
+// 열람 권한이 있는 결재 요청만 반환합니다.
+// GET /approvals?status=&requester_id=&approver_id=&template_id=&department_id=&created_from=&created_to=&sort=&order=&limit=&cursor=
 pub async fn list_approvals(
     State(pool): State<PgPool>,
-    Extension(user_id): Extension<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<ApprovalListQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    list_page(
+        pool,
+        ApprovalScope::Visible {
+            viewer_id: user_id,
+            roles: &roles,
+        },
+        params,
+    )
+    .await
+}
+
+// GET /approvals/search?q=출장 호텔&limit=20
+// 제목, form_data 문자열 값, 코멘트를 대상으로 관련도 순으로 검색합니다. (열람 권한 적용)
+pub async fn search_approvals(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let query = SearchQuery::parse(&params.q).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
     let repo = ApprovalRepository::new(pool);
+    match repo
+        .search(user_id, &roles, &query, page_size(params.limit))
+        .await
+    {
+        Ok(result) => Ok(Json(serde_json::json!(result))),
+        Err(e) => {
+            eprintln!("Failed to search approvals: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        }
+    }
+}
+
+// GET /inbox/:box (pending | requested | processed | completed | drafts)
+// 목록 필터/정렬/페이지 파라미터는 GET /approvals와 같습니다.
+pub async fn list_inbox(
+    Path(inbox): Path<InboxBox>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Query(params): Query<ApprovalListQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    list_page(pool, ApprovalScope::Inbox { user_id, inbox }, params).await
+}
 
-    // TODO: Filter by user_id (My Inbox)
-    // For now, list all but we should filter where user is requester OR approver
-    let _ = user_id;
-    match repo.find_all().await {
-        Ok(requests) => Ok(Json(serde_json::json!(requests))),
+async fn list_page(
+    pool: PgPool,
+    scope: ApprovalScope<'_>,
+    params: ApprovalListQuery,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let keyset = params.keyset().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let repo = ApprovalRepository::new(pool);
+    match repo.find_page(scope, &params, keyset).await {
+        Ok(page) => Ok(Json(serde_json::json!(page))),
         Err(e) => {
             eprintln!("Failed to list approvals: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        }
+    }
+}
+
+// GET /inbox/counts - 문서함별 배지 숫자
+pub async fn inbox_counts(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let repo = ApprovalRepository::new(pool);
+
+    match repo.count_inbox(user_id).await {
+        Ok(counts) => Ok(Json(serde_json::json!(counts))),
+        Err(e) => {
+            eprintln!("Failed to count inbox: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
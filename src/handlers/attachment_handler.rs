@@ -0,0 +1,242 @@
+use crate::{
+    domain::{
+        attachment::{Attachment, detect_content_type, sanitize_file_name},
+        user::AuthUser,
+    },
+    handlers::approval_handler::find_visible_request,
+    repositories::attachment_repository::{AttachmentRepository, NewAttachment},
+    services::storage::StorageError,
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Extension, Multipart, Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// POST /approvals/:id/attachments (multipart/form-data, "file" 필드 여러 개 가능)
+// 요청자 본인이 임시저장(draft) 상태의 문서에만 첨부할 수 있습니다.
+pub async fn upload_attachments(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Vec<Attachment>>), (StatusCode, String)> {
+    let request = find_visible_request(&state.pool, id, user_id, &roles).await?;
+    if request.requester_id != user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the requester can attach files".to_string(),
+        ));
+    }
+    ensure_unlocked(&request.status)?;
+
+    let repo = AttachmentRepository::new(state.pool.clone());
+    let mut saved = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = sanitize_file_name(field.file_name().unwrap_or_default());
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()))?;
+        // 클라이언트가 보낸 Content-Type 대신 본문/확장자로 판별한 값을 검사하고 저장합니다.
+        let content_type = detect_content_type(&file_name, &bytes).to_string();
+
+        state
+            .attachment_policy
+            .check(&content_type, bytes.len())
+            .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+        // 1. 저장소에 본문 저장
+        let attachment_id = Uuid::new_v4();
+        let storage_key = format!("{}/{}", id, attachment_id);
+        state
+            .storage
+            .put(&storage_key, &bytes)
+            .await
+            .map_err(storage_error)?;
+
+        // 2. 메타데이터 저장 (그 사이 상신됐다면 본문도 지웁니다)
+        let created = repo
+            .create(NewAttachment {
+                id: attachment_id,
+                approval_id: id,
+                file_name,
+                content_type,
+                size_bytes: bytes.len() as i64,
+                sha256: hex::encode(Sha256::digest(&bytes)),
+                storage_key: storage_key.clone(),
+                uploaded_by: user_id,
+            })
+            .await;
+        match created {
+            Ok(Some(attachment)) => saved.push(attachment),
+            Ok(None) => {
+                let _ = state.storage.delete(&storage_key).await;
+                return Err(locked());
+            }
+            Err(e) => {
+                let _ = state.storage.delete(&storage_key).await;
+                eprintln!("Failed to save attachment: {:?}", e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+            }
+        }
+    }
+
+    if saved.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "No 'file' field in the request".to_string(),
+        ));
+    }
+
+    Ok((StatusCode::CREATED, Json(saved)))
+}
+
+// GET /approvals/:id/attachments
+pub async fn list_attachments(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+) -> Result<Json<Vec<Attachment>>, (StatusCode, String)> {
+    find_visible_request(&state.pool, id, user_id, &roles).await?;
+
+    AttachmentRepository::new(state.pool)
+        .find_by_approval(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+// GET /approvals/:id/attachments/:attachment_id
+// 결재 문서 열람 권한이 있어야 하며, 저장 시 체크섬과 다르면 내려주지 않습니다.
+pub async fn download_attachment(
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+) -> Result<Response, (StatusCode, String)> {
+    find_visible_request(&state.pool, id, user_id, &roles).await?;
+
+    let attachment = AttachmentRepository::new(state.pool.clone())
+        .find_by_id(id, attachment_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Attachment not found".to_string()))?;
+
+    let bytes = state
+        .storage
+        .get(&attachment.storage_key)
+        .await
+        .map_err(storage_error)?;
+    if hex::encode(Sha256::digest(&bytes)) != attachment.sha256 {
+        eprintln!("Attachment checksum mismatch: {}", attachment.id);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Attachment is corrupted".to_string(),
+        ));
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type.clone()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition(&attachment.file_name),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+// DELETE /approvals/:id/attachments/:attachment_id (요청자, 임시저장 상태에서만)
+pub async fn delete_attachment(
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let request = find_visible_request(&state.pool, id, user_id, &roles).await?;
+    if request.requester_id != user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the requester can remove attachments".to_string(),
+        ));
+    }
+    ensure_unlocked(&request.status)?;
+
+    let deleted = AttachmentRepository::new(state.pool.clone())
+        .delete(id, attachment_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    match deleted {
+        Some(attachment) => {
+            if let Err(e) = state.storage.delete(&attachment.storage_key).await {
+                eprintln!("Failed to delete attachment file: {:?}", e);
+            }
+            Ok(StatusCode::NO_CONTENT)
+        }
+        None => Err((StatusCode::NOT_FOUND, "Attachment not found".to_string())),
+    }
+}
+
+// 상신 후에는 첨부파일을 변경할 수 없습니다.
+fn ensure_unlocked(status: &str) -> Result<(), (StatusCode, String)> {
+    if status == "draft" {
+        Ok(())
+    } else {
+        Err(locked())
+    }
+}
+
+fn locked() -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        "Attachments are locked once the request is submitted".to_string(),
+    )
+}
+
+fn storage_error(e: StorageError) -> (StatusCode, String) {
+    eprintln!("Attachment storage error: {:?}", e);
+    match e {
+        StorageError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            "Attachment file not found".to_string(),
+        ),
+        e => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+// 한글 파일명을 위해 RFC 5987 filename* 도 함께 내려줍니다.
+fn content_disposition(file_name: &str) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii() { c } else { '_' })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        ascii,
+        utf8_percent_encode(file_name, NON_ALPHANUMERIC)
+    )
+}
@@ -1,15 +1,46 @@
 use crate::{
-    domain::user::{AuthResponse, CreateUserDto, LoginDto},
-    repositories::user_repository::UserRepository,
-    utils::auth::{create_jwt, hash_password, verify_password},
+    domain::{
+        login::{
+            FAILURE_ACCOUNT_LOCKED, FAILURE_INACTIVE, FAILURE_INVALID_CREDENTIALS,
+            FAILURE_IP_BLOCKED, account_key,
+        },
+        session::{
+            LogoutDto, REVOKE_LOGOUT, REVOKE_LOGOUT_ALL, REVOKE_PASSWORD_CHANGED, RefreshDto,
+            RefreshOutcome, TokenResponse,
+        },
+        user::{
+            AuthResponse, AuthUser, ChangePasswordDto, CreateUserDto, ForgotPasswordDto, LoginDto,
+            ResetPasswordDto, STATUS_ACTIVE,
+        },
+    },
+    repositories::{
+        login_attempt_repository::NewLoginAttempt,
+        password_reset_repository::PasswordResetRepository, session_repository::SessionRepository,
+        user_repository::UserRepository,
+    },
+    services::{login_guard::LoginGuard, password_reset::PasswordResetService},
+    utils::auth::{
+        create_jwt, dummy_password_hash, generate_refresh_token, generate_token, hash_password,
+        hash_token, token_ttl, verify_password,
+    },
+    utils::client_ip::ClientIp,
+    utils::jwt_keys::{JwkSet, jwt_keys},
 };
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::{HeaderMap, StatusCode, header},
+};
+use chrono::Utc;
 use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
 use validator::Validate;
 
 // Register Handler
 pub async fn register(
     State(pool): State<PgPool>,
+    headers: HeaderMap,
     Json(payload): Json<CreateUserDto>,
 ) -> Result<Json<AuthResponse>, (StatusCode, String)> {
     // 1. Validate Payload
@@ -17,7 +48,7 @@ pub async fn register(
         return Err((StatusCode::BAD_REQUEST, format!("Validation error: {}", e)));
     }
 
-    let repo = UserRepository::new(pool);
+    let repo = UserRepository::new(pool.clone());
 
     // 2. Check if user exists
     if let Ok(Some(_)) = repo.find_by_email(&payload.email).await {
@@ -40,33 +71,318 @@ pub async fn register(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    // 5. Generate Token
-    let token = create_jwt(user.id).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    // 5. Generate Tokens (새 세션)
+    let tokens = start_session(&pool, user.id, &headers).await?;
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(Json(AuthResponse {
+        token: tokens.token,
+        refresh_token: tokens.refresh_token,
+        expires_in: tokens.expires_in,
+        user,
+    }))
 }
 
 // Login Handler
+// 실패는 계정(이메일)/IP별로 집계해 지연과 잠금을 적용합니다. (services::login_guard)
+// 잠긴 계정이나 없는 이메일도 응답은 똑같이 "Invalid credentials"입니다.
 pub async fn login(
     State(pool): State<PgPool>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     Json(payload): Json<LoginDto>,
 ) -> Result<Json<AuthResponse>, (StatusCode, String)> {
-    let repo = UserRepository::new(pool);
+    let repo = UserRepository::new(pool.clone());
+    let guard = LoginGuard::new(pool.clone());
+    let account = account_key(&payload.email);
+    let ip = ip.map(|ip| ip.to_string());
+    let user_agent = user_agent(&headers);
+    let mut attempt = NewLoginAttempt {
+        user_id: None,
+        email: &account,
+        ip_address: ip.as_deref(),
+        user_agent: user_agent.as_deref(),
+        success: false,
+        failure_reason: None,
+    };
+
+    // 1. 시도를 먼저 셈 (IP 차단 / 계정 잠금 / 실패 누적에 따른 지연)
+    let check = guard
+        .reserve(&account, attempt.ip_address)
+        .await
+        .map_err(db_error)?;
+    if check.ip_blocked {
+        attempt.failure_reason = Some(FAILURE_IP_BLOCKED);
+        guard.record(&attempt).await.map_err(db_error)?;
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many login attempts. Try again later.".to_string(),
+        ));
+    }
+    tokio::time::sleep(check.delay).await;
+
+    // 2. Find User
+    let user = repo.find_by_email(&payload.email).await.map_err(db_error)?;
+    attempt.user_id = user.as_ref().map(|user| user.id);
 
-    // 1. Find User
+    // 3. Verify Password (없는 계정도 같은 시간이 걸리도록 검증)
+    let verified = verify_password(
+        user.as_ref()
+            .map_or(dummy_password_hash(), |user| user.password_hash.as_str()),
+        &payload.password,
+    );
+    let user = match user {
+        Some(user) if verified && !check.account_locked => user,
+        _ => {
+            attempt.failure_reason = Some(if check.account_locked {
+                FAILURE_ACCOUNT_LOCKED
+            } else {
+                FAILURE_INVALID_CREDENTIALS
+            });
+            guard.record(&attempt).await.map_err(db_error)?;
+            return Err(invalid_credentials()); // Generic error for security
+        }
+    };
+
+    // 4. 정지/비활성 계정은 로그인 불가
+    if user.status != STATUS_ACTIVE {
+        attempt.failure_reason = Some(FAILURE_INACTIVE);
+        guard.release(&attempt).await.map_err(db_error)?;
+        return Err((StatusCode::FORBIDDEN, "Account is not active".to_string()));
+    }
+
+    // 5. 성공: 계정 실패 횟수 초기화, 마지막 로그인 시각
+    attempt.success = true;
+    guard.record_success(&attempt).await.map_err(db_error)?;
     let user = repo
-        .find_by_email(&payload.email)
+        .record_login(user.id)
+        .await
+        .map_err(db_error)?
+        .unwrap_or(user);
+
+    // 6. Generate Tokens (새 세션)
+    let tokens = start_session(&pool, user.id, &headers).await?;
+
+    Ok(Json(AuthResponse {
+        token: tokens.token,
+        refresh_token: tokens.refresh_token,
+        expires_in: tokens.expires_in,
+        user,
+    }))
+}
+
+// POST /auth/refresh (Public) - refresh token을 새 access/refresh token으로 교체
+// 이미 사용된 refresh token이 다시 오면 세션 전체를 폐기합니다. (탈취 의심)
+pub async fn refresh(
+    State(pool): State<PgPool>,
+    Json(payload): Json<RefreshDto>,
+) -> Result<Json<TokenResponse>, (StatusCode, String)> {
+    let (refresh_token, new_hash) = generate_refresh_token();
+    let outcome = SessionRepository::new(pool)
+        .rotate(
+            &hash_token(&payload.refresh_token),
+            &new_hash,
+            refresh_expires_at(),
+        )
+        .await
+        .map_err(db_error)?;
+
+    match outcome {
+        RefreshOutcome::Rotated {
+            session_id,
+            user_id,
+        } => Ok(Json(TokenResponse {
+            token: create_jwt(user_id, session_id)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?,
+            refresh_token,
+            expires_in: token_ttl().access.as_secs(),
+        })),
+        RefreshOutcome::Reused { session_id } => {
+            eprintln!(
+                "Refresh token reuse detected, session {} revoked",
+                session_id
+            );
+            Err(invalid_refresh_token())
+        }
+        RefreshOutcome::Invalid => Err(invalid_refresh_token()),
+    }
+}
+
+// POST /auth/logout - 현재 세션 폐기 ({"everywhere": true}면 내 모든 세션)
+pub async fn logout(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id,
+        session_id,
+        ..
+    }): Extension<AuthUser>,
+    payload: Option<Json<LogoutDto>>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let repo = SessionRepository::new(pool);
+    let everywhere = payload.is_some_and(|Json(dto)| dto.everywhere);
+    if everywhere {
+        repo.revoke_all(user_id, REVOKE_LOGOUT_ALL)
+            .await
+            .map_err(db_error)?;
+    } else {
+        repo.revoke(session_id, REVOKE_LOGOUT)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// POST /auth/password/change - 현재 비밀번호 확인 후 변경, 지금 세션 외의 세션은 모두 폐기
+pub async fn change_password(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id,
+        session_id,
+        ..
+    }): Extension<AuthUser>,
+    Json(payload): Json<ChangePasswordDto>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if let Err(e) = payload.validate() {
+        return Err((StatusCode::BAD_REQUEST, format!("Validation error: {}", e)));
+    }
+
+    let repo = UserRepository::new(pool.clone());
+    let user = repo
+        .find_by_id(user_id)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::UNAUTHORIZED, "User not found".to_string()))?;
+    if !verify_password(&user.password_hash, &payload.old_password) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Current password is incorrect".to_string(),
+        ));
+    }
+
+    let password_hash =
+        hash_password(&payload.new_password).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    repo.update_password(user_id, &password_hash)
+        .await
+        .map_err(db_error)?;
+    SessionRepository::new(pool)
+        .revoke_others(user_id, session_id, REVOKE_PASSWORD_CHANGED)
+        .await
+        .map_err(db_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// POST /auth/password/forgot (Public) - 일회용 재설정 토큰을 만들어 notifier로 전달
+// 가입 여부를 알 수 없도록 항상 202를 반환합니다.
+pub async fn forgot_password(
+    State(pool): State<PgPool>,
+    State(password_reset): State<Arc<PasswordResetService>>,
+    Json(payload): Json<ForgotPasswordDto>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let user = UserRepository::new(pool.clone())
+        .find_by_email(payload.email.trim())
+        .await
+        .map_err(db_error)?;
+    let Some(user) = user.filter(|user| user.status == STATUS_ACTIVE) else {
+        return Ok(StatusCode::ACCEPTED);
+    };
+
+    let (token, hash) = generate_token();
+    let expires_at = Utc::now()
+        + chrono::Duration::from_std(password_reset.token_ttl()).unwrap_or(chrono::Duration::MAX);
+    PasswordResetRepository::new(pool)
+        .create(user.id, &hash, expires_at)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string()))?; // Generic error for security
+        .map_err(db_error)?;
+    password_reset.notify(user, token);
 
-    // 2. Verify Password
-    if !verify_password(&user.password_hash, &payload.password) {
-        return Err((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string()));
+    Ok(StatusCode::ACCEPTED)
+}
+
+// POST /auth/password/reset (Public) - 토큰은 한 번만 사용 가능, 성공하면 모든 세션/refresh token 폐기
+pub async fn reset_password(
+    State(pool): State<PgPool>,
+    Json(payload): Json<ResetPasswordDto>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if let Err(e) = payload.validate() {
+        return Err((StatusCode::BAD_REQUEST, format!("Validation error: {}", e)));
     }
 
-    // 3. Generate Token
-    let token = create_jwt(user.id).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let password_hash =
+        hash_password(&payload.new_password).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let reset = PasswordResetRepository::new(pool)
+        .reset_password(&hash_token(&payload.token), &password_hash)
+        .await
+        .map_err(db_error)?;
+    if reset.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Invalid or expired reset token".to_string(),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// GET /.well-known/jwks.json (Public) - 다른 서비스가 access token을 검증할 공개키 (HS256이면 빈 목록)
+pub async fn jwks() -> Result<([(header::HeaderName, &'static str); 1], Json<JwkSet>), StatusCode> {
+    let keys = jwt_keys().map_err(|e| {
+        eprintln!("Failed to publish JWKS: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok((
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(keys.jwks()),
+    ))
+}
+
+// 새 세션을 만들고 access/refresh token을 발급합니다.
+async fn start_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    headers: &HeaderMap,
+) -> Result<TokenResponse, (StatusCode, String)> {
+    let user_agent = user_agent(headers);
+    let (refresh_token, hash) = generate_refresh_token();
+    let session_id = SessionRepository::new(pool.clone())
+        .create(user_id, user_agent, &hash, refresh_expires_at())
+        .await
+        .map_err(db_error)?;
+    let token =
+        create_jwt(user_id, session_id).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(TokenResponse {
+        token,
+        refresh_token,
+        expires_in: token_ttl().access.as_secs(),
+    })
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(500).collect())
+}
+
+fn refresh_expires_at() -> chrono::DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(token_ttl().refresh).unwrap_or(chrono::Duration::MAX)
+}
+
+fn invalid_credentials() -> (StatusCode, String) {
+    (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
+}
+
+fn invalid_refresh_token() -> (StatusCode, String) {
+    (
+        StatusCode::UNAUTHORIZED,
+        "Invalid refresh token".to_string(),
+    )
+}
 
-    Ok(Json(AuthResponse { token, user }))
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Auth query failed: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
 }
@@ -0,0 +1,397 @@
+use crate::{
+    domain::{
+        approval::ApprovalAction,
+        chat::{
+            ChatAction, ChatTokenError, CreateChatWebhookDto, Interaction, InteractionToken,
+            LINK_COMMAND, SlashCommand, command_response, generate_link_code, interaction_response,
+            normalize_link_code, verify_shared_token, verify_slack_signature,
+        },
+        email::{DEFAULT_LOCALE, SUPPORTED_LOCALES},
+        notification::NotificationType,
+        user::{AuthUser, STATUS_ACTIVE},
+    },
+    handlers::approval_handler::apply_action,
+    repositories::{
+        approval_repository::ApprovalRepository, chat_repository::ChatRepository,
+        user_repository::UserRepository,
+    },
+    services::chat::ChatConfig,
+    utils::auth::hash_token,
+};
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Extension, Path, State},
+    http::{HeaderMap, StatusCode, header},
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// [Chat Integration]
+// - /chat/webhooks: 본인 개인 webhook (로그인 사용자)
+// - /chat/channels: 채널 webhook (chat:manage)
+// - /chat/interactions: 채팅 서버가 호출하는 버튼 콜백 (인증 대신 채팅 서버 서명 + 서명된 토큰)
+// - /chat/commands: 채팅 서버가 호출하는 slash command (/pxm link {code})
+
+// GET /chat/webhooks
+pub async fn list_my_chat_webhooks(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    list_webhooks(pool, Some(user_id)).await
+}
+
+// POST /chat/webhooks
+pub async fn create_my_chat_webhook(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<CreateChatWebhookDto>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    create_webhook(pool, Some(user_id), user_id, payload).await
+}
+
+// DELETE /chat/webhooks/:id
+pub async fn delete_my_chat_webhook(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    delete_webhook(pool, id, Some(user_id)).await
+}
+
+// GET /chat/channels
+pub async fn list_chat_channels(
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    list_webhooks(pool, None).await
+}
+
+// POST /chat/channels
+pub async fn create_chat_channel(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<CreateChatWebhookDto>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    create_webhook(pool, None, user_id, payload).await
+}
+
+// DELETE /chat/channels/:id
+pub async fn delete_chat_channel(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    delete_webhook(pool, id, None).await
+}
+
+// POST /chat/account/link - 채팅 계정 연결 코드 발급
+// 채팅에서 "/pxm link {code}"를 보내면 채팅 서버가 확인한 사용자 ID로 연결됩니다.
+pub async fn create_chat_link_code(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<ChatConfig>>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    let code = generate_link_code();
+    let expires_at = Utc::now()
+        + chrono::Duration::from_std(config.link_code_ttl).unwrap_or(chrono::Duration::MAX);
+    ChatRepository::new(pool)
+        .create_link_code(user_id, &hash_token(&code), expires_at)
+        .await
+        .map_err(db_error)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "code": code,
+            "command": format!("{} {}", LINK_COMMAND, code),
+            "expires_at": expires_at,
+        })),
+    ))
+}
+
+// DELETE /chat/account - 채팅 계정 연결 해제
+pub async fn delete_chat_account(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let deleted = ChatRepository::new(pool)
+        .delete_account(user_id)
+        .await
+        .map_err(db_error)?;
+    if !deleted {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "No chat account is linked".to_string(),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// POST /chat/commands (인증 없음, 채팅 서버 서명 확인)
+// "/pxm link {code}": 코드를 발급받은 사용자에 명령을 보낸 채팅 사용자를 연결합니다.
+pub async fn chat_command(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<ChatConfig>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let command = SlashCommand::parse(&body).ok_or((
+        StatusCode::BAD_REQUEST,
+        "Invalid command payload".to_string(),
+    ))?;
+    verify_platform(&config, &headers, &body, command.token.as_deref())?;
+
+    let mut words = command.text.split_whitespace();
+    let code = match (words.next(), words.next(), words.next()) {
+        (Some("link"), Some(code), None) => normalize_link_code(code),
+        _ => {
+            return Ok(Json(command_response(&format!(
+                "사용법 (Usage): {} <code>",
+                LINK_COMMAND
+            ))));
+        }
+    };
+
+    let chat = ChatRepository::new(pool.clone());
+    let Some(user_id) = chat
+        .consume_link_code(&hash_token(&code))
+        .await
+        .map_err(db_error)?
+    else {
+        return Ok(Json(command_response(
+            "코드가 올바르지 않거나 만료되었습니다. (Invalid or expired code)",
+        )));
+    };
+    match chat.set_account(user_id, &command.user_id).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Ok(Json(command_response(
+                "이 채팅 계정은 다른 사용자에 연결되어 있습니다. (This chat account is linked to another user)",
+            )));
+        }
+        Err(e) => return Err(db_error(e)),
+    }
+
+    let locale = user_locale(&pool, user_id).await?;
+    let text = if locale == "en" {
+        "🔗 Your chat account is linked."
+    } else {
+        "🔗 채팅 계정이 연결되었습니다."
+    };
+    Ok(Json(command_response(text)))
+}
+
+// POST /chat/interactions (인증 없음)
+// 채팅 서버가 보낸 요청인지 확인한 뒤, 버튼 토큰의 서명/만료를 확인하고 토큰의 결재자로 승인/반려합니다.
+// - 이미 처리되었거나 다음 단계로 넘어간 문서의 버튼: 409
+// - 누른 사람(채팅 서버가 채운 사용자 ID)이 결재자의 연결된 채팅 계정과 같아야 함
+// - 비활성(INACTIVE/SUSPENDED) 계정은 웹과 마찬가지로 결재할 수 없음: 403
+pub async fn chat_interaction(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<ChatConfig>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let interaction = Interaction::parse(content_type, &body).ok_or((
+        StatusCode::BAD_REQUEST,
+        "Invalid interaction payload".to_string(),
+    ))?;
+    verify_platform(
+        &config,
+        &headers,
+        &body,
+        interaction.verify_token.as_deref(),
+    )?;
+    let token =
+        InteractionToken::verify(&interaction.token, &config.secret, Utc::now().timestamp())
+            .map_err(|e| match e {
+                ChatTokenError::Malformed => (StatusCode::BAD_REQUEST, e.to_string()),
+                _ => (StatusCode::UNAUTHORIZED, e.to_string()),
+            })?;
+
+    let repo = ApprovalRepository::new(pool.clone());
+    let request = repo
+        .find_by_id(token.approval_id)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Request not found".to_string()))?;
+    if request.status != "pending"
+        || request.flow_process.0.current_step != token.step
+        || request.current_approver() != Some(token.approver_id)
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            "This step has already been processed".to_string(),
+        ));
+    }
+
+    let linked = ChatRepository::new(pool.clone())
+        .find_account(token.approver_id)
+        .await
+        .map_err(db_error)?;
+    let verified = matches!(
+        (&linked, &interaction.chat_user_id),
+        (Some(linked), Some(clicked)) if linked == clicked
+    );
+    if !verified {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the current approver can use this button (link your chat account first)"
+                .to_string(),
+        ));
+    }
+
+    let approver = UserRepository::new(pool.clone())
+        .find_by_id(token.approver_id)
+        .await
+        .map_err(db_error)?
+        .filter(|user| user.status == STATUS_ACTIVE)
+        .ok_or((
+            StatusCode::FORBIDDEN,
+            "Your account is not active".to_string(),
+        ))?;
+
+    let action = match token.action {
+        ChatAction::Approve => ApprovalAction::Approve,
+        ChatAction::Reject => ApprovalAction::Reject,
+    };
+    let updated = apply_action(&repo, request.id, action, None, approver.id).await?;
+
+    let text = match (token.action, approver.locale.as_str()) {
+        (ChatAction::Approve, "en") => format!("✅ Approved: {}", updated.title),
+        (ChatAction::Approve, _) => format!("✅ 승인했습니다: {}", updated.title),
+        (ChatAction::Reject, "en") => format!("❌ Rejected: {}", updated.title),
+        (ChatAction::Reject, _) => format!("❌ 반려했습니다: {}", updated.title),
+    };
+    Ok(Json(interaction_response(&text)))
+}
+
+// 채팅 서버가 보낸 요청인지 확인합니다.
+// - X-Slack-Signature가 있으면: 원문 본문의 Slack 서명 + X-Slack-Request-Timestamp (replay window)
+// - 없으면: Mattermost 공유 토큰 (slash command token / 버튼 context.verify_token)
+fn verify_platform(
+    config: &ChatConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+    shared_token: Option<&str>,
+) -> Result<(), (StatusCode, String)> {
+    let header_value = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let result = match (
+        header_value("x-slack-signature"),
+        &config.slack_signing_secret,
+        &config.mattermost_token,
+    ) {
+        (Some(signature), Some(secret), _) => verify_slack_signature(
+            secret,
+            header_value("x-slack-request-timestamp").unwrap_or_default(),
+            signature,
+            body,
+            Utc::now().timestamp(),
+        ),
+        (None, _, Some(token)) => verify_shared_token(token, shared_token),
+        _ => Err(ChatTokenError::Unverified),
+    };
+    result.map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))
+}
+
+async fn user_locale(pool: &PgPool, user_id: Uuid) -> Result<String, (StatusCode, String)> {
+    Ok(UserRepository::new(pool.clone())
+        .find_by_id(user_id)
+        .await
+        .map_err(db_error)?
+        .map(|user| user.locale)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string()))
+}
+
+async fn list_webhooks(
+    pool: PgPool,
+    owner_id: Option<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let webhooks = ChatRepository::new(pool)
+        .find_webhooks(owner_id)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!(webhooks)))
+}
+
+async fn create_webhook(
+    pool: PgPool,
+    owner_id: Option<Uuid>,
+    created_by: Uuid,
+    payload: CreateChatWebhookDto,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    if !(payload.url.starts_with("https://") || payload.url.starts_with("http://")) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "url must be an http(s) URL".to_string(),
+        ));
+    }
+    if payload.name.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "name is required".to_string()));
+    }
+    // 개인 webhook은 사용자 언어를 기본값으로 사용합니다.
+    let locale = match (payload.locale, owner_id) {
+        (Some(locale), _) => locale,
+        (None, Some(owner_id)) => UserRepository::new(pool.clone())
+            .find_by_id(owner_id)
+            .await
+            .map_err(db_error)?
+            .map(|user| user.locale)
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string()),
+        (None, None) => DEFAULT_LOCALE.to_string(),
+    };
+    if !SUPPORTED_LOCALES.contains(&locale.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Supported locales: {}", SUPPORTED_LOCALES.join(", ")),
+        ));
+    }
+
+    let webhook = ChatRepository::new(pool)
+        .create_webhook(
+            owner_id,
+            payload.name,
+            payload.url,
+            locale,
+            type_codes(&payload.notification_types),
+            created_by,
+        )
+        .await
+        .map_err(db_error)?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!(webhook))))
+}
+
+async fn delete_webhook(
+    pool: PgPool,
+    id: Uuid,
+    owner_id: Option<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let deleted = ChatRepository::new(pool)
+        .delete_webhook(id, owner_id)
+        .await
+        .map_err(db_error)?;
+    if !deleted {
+        return Err((StatusCode::NOT_FOUND, "Chat webhook not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 빈 배열 = 모든 알림
+fn type_codes(types: &[NotificationType]) -> Vec<String> {
+    let mut codes: Vec<String> = types.iter().map(|t| t.as_str().to_string()).collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Chat query failed: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
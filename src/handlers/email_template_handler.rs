@@ -0,0 +1,94 @@
+use crate::{
+    domain::{
+        email::{DIGEST_TEMPLATE, EmailTemplate, SUPPORTED_LOCALES, UpdateEmailTemplateDto},
+        notification::NotificationType,
+        user::AuthUser,
+    },
+    repositories::email_repository::EmailRepository,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+};
+use sqlx::PgPool;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Email template query failed: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+// 경로의 유형/언어가 지원하는 값인지 확인합니다. (알림 유형 + 요약 메일)
+fn validate_key(notification_type: &str, locale: &str) -> Result<(), (StatusCode, String)> {
+    if notification_type != DIGEST_TEMPLATE
+        && !NotificationType::ALL
+            .iter()
+            .any(|t| t.as_str() == notification_type)
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("Unknown notification type '{}'", notification_type),
+        ));
+    }
+    if !SUPPORTED_LOCALES.contains(&locale) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("Unsupported locale '{}'", locale),
+        ));
+    }
+    Ok(())
+}
+
+// GET /email-templates
+pub async fn list_email_templates(
+    State(pool): State<PgPool>,
+) -> Result<Json<Vec<EmailTemplate>>, (StatusCode, String)> {
+    let templates = EmailRepository::new(pool)
+        .find_templates()
+        .await
+        .map_err(db_error)?;
+    Ok(Json(templates))
+}
+
+// GET /email-templates/:notification_type/:locale
+pub async fn get_email_template(
+    Path((notification_type, locale)): Path<(String, String)>,
+    State(pool): State<PgPool>,
+) -> Result<Json<EmailTemplate>, (StatusCode, String)> {
+    validate_key(&notification_type, &locale)?;
+    let template = EmailRepository::new(pool)
+        .find_template(&notification_type, &locale)
+        .await
+        .map_err(db_error)?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Email template not found".to_string(),
+        ))?;
+    Ok(Json(template))
+}
+
+// PUT /email-templates/:notification_type/:locale
+// 알 수 없는 placeholder나 닫히지 않은 {{ 가 있으면 400
+pub async fn update_email_template(
+    Path((notification_type, locale)): Path<(String, String)>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<UpdateEmailTemplateDto>,
+) -> Result<Json<EmailTemplate>, (StatusCode, String)> {
+    validate_key(&notification_type, &locale)?;
+    if payload.subject.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Subject is required".to_string()));
+    }
+    payload
+        .validate()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let template = EmailRepository::new(pool)
+        .upsert_template(&notification_type, &locale, payload, user_id)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(template))
+}
@@ -0,0 +1,339 @@
+use crate::{
+    domain::{
+        approval::ApprovalListQuery,
+        form_schema::{display_value, fields_with_extras},
+        role::UserRoles,
+        user::AuthUser,
+    },
+    handlers::approval_handler::find_visible_request,
+    repositories::{
+        approval_repository::{ApprovalRepository, ApprovalScope},
+        department_repository::DepartmentRepository,
+        template_repository::TemplateRepository,
+        user_repository::UserRepository,
+    },
+    services::{
+        export::{ExportColumns, ExportFormat, ExportOptions, UserNames, csv_cell},
+        pdf::{ApprovalDocument, CommentEntry, PdfError, StampCell, render_approval_pdf},
+    },
+    utils::timezone::org_timezone,
+};
+use axum::{
+    body::Body,
+    extract::{Extension, Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use rust_xlsxwriter::Workbook;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+// GET /approvals/:id/pdf
+// 문서 정보, form_schema 순서의 양식 내용, 결재란(FlowProcess.steps), 결재 이력을 PDF로 내려줍니다.
+pub async fn export_pdf(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+) -> Result<Response, (StatusCode, String)> {
+    let request = find_visible_request(&pool, id, user_id, &roles).await?;
+    let db_error = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let tz = org_timezone();
+    let format_time = |t: DateTime<Utc>| t.with_timezone(&tz).format("%Y-%m-%d %H:%M").to_string();
+
+    let logs = ApprovalRepository::new(pool.clone())
+        .get_logs(id)
+        .await
+        .map_err(db_error)?;
+
+    // 1. 이름 조회 (요청자, 결재자, 이력 작성자)
+    let user_repo = UserRepository::new(pool.clone());
+    let mut names: HashMap<Uuid, String> = HashMap::new();
+    let user_ids = std::iter::once(request.requester_id)
+        .chain(request.flow_process.steps.iter().map(|s| s.approver_id))
+        .chain(logs.iter().map(|l| l.actor_id));
+    let mut department_id = None;
+    for user_id in user_ids {
+        if names.contains_key(&user_id) {
+            continue;
+        }
+        let name = match user_repo.find_by_id(user_id).await.map_err(db_error)? {
+            Some(user) => {
+                if user.id == request.requester_id {
+                    department_id = user.department_id;
+                }
+                user.full_name
+            }
+            None => user_id.to_string(),
+        };
+        names.insert(user_id, name);
+    }
+    let name_of = |id: Uuid| names.get(&id).cloned().unwrap_or_else(|| id.to_string());
+
+    let department_name = match department_id {
+        Some(department_id) => DepartmentRepository::new(pool.clone())
+            .find_by_id(department_id)
+            .await
+            .map_err(db_error)?
+            .map(|d| d.name),
+        None => None,
+    };
+
+    // 2. 양식 내용 (템플릿이 없으면 form_data 키 순서)
+    let template = match request.template_id {
+        Some(template_id) => TemplateRepository::new(pool.clone())
+            .find_by_id(template_id)
+            .await
+            .map_err(db_error)?,
+        None => None,
+    };
+    let form_schema = template
+        .as_ref()
+        .map(|t| t.form_schema.0.clone())
+        .unwrap_or_default();
+    let fields = fields_with_extras(&form_schema, &request.form_data.0)
+        .into_iter()
+        .map(|field| {
+            let value = field
+                .value_in(&request.form_data.0)
+                .map(display_value)
+                .unwrap_or_default();
+            (field.label, value)
+        })
+        .collect();
+
+    let document = ApprovalDocument {
+        title: request.title.clone(),
+        document_number: request.document_number(tz),
+        status: request.status.clone(),
+        template_name: template.map(|t| t.name),
+        requester_name: name_of(request.requester_id),
+        department_name,
+        created_at: format_time(request.created_at),
+        fields,
+        stamps: request
+            .flow_process
+            .steps
+            .iter()
+            .map(|step| StampCell {
+                step_name: step.name.clone(),
+                approver_name: name_of(step.approver_id),
+                status: step.status.clone(),
+                processed_at: step.timestamp.map(format_time),
+            })
+            .collect(),
+        comments: logs
+            .iter()
+            .map(|log| CommentEntry {
+                actor_name: name_of(log.actor_id),
+                action_type: log.action_type.clone(),
+                content: log.content.clone(),
+                created_at: format_time(log.created_at),
+            })
+            .collect(),
+    };
+
+    // 3. 렌더링 (CPU 작업이므로 blocking 스레드에서)
+    let bytes = tokio::task::spawn_blocking(move || render_approval_pdf(&document))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(|e| match e {
+            // 한글 폰트(PDF_FONT_PATH) 없이 한글 문서를 내보내려 함: '?'로 깨진 PDF 대신 거부
+            PdfError::MissingFont(_) => (StatusCode::SERVICE_UNAVAILABLE, e.to_string()),
+            PdfError::Render(_) => {
+                eprintln!("Failed to render PDF: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}.pdf\"", request.document_number(tz)),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+// GET /approvals/export?format=csv|xlsx&fields=amount,trip.city
+// 목록 API(GET /approvals)와 같은 필터/정렬을 사용하며, cursor/limit 없이 조건에 맞는 전체를 내보냅니다.
+// - CSV: DB 커서에서 한 행씩 읽어 바로 응답으로 흘려보냅니다.
+// - XLSX: 행을 constant-memory 워크시트(임시 파일)에 쓴 뒤, 완성된 파일을 스트리밍합니다.
+pub async fn export_approvals(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<ApprovalListQuery>,
+    Query(options): Query<ExportOptions>,
+) -> Result<Response, (StatusCode, String)> {
+    let tz = org_timezone();
+
+    // template_id로 필터링하면 해당 양식의 필드 라벨/순서를 사용
+    let form_schema = match params.template_id {
+        Some(template_id) => TemplateRepository::new(pool.clone())
+            .find_by_id(template_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .map(|t| t.form_schema.0),
+        None => None,
+    };
+    let columns = ExportColumns::new(tz, form_schema.as_ref(), options.fields.as_deref());
+    let file_stem = format!(
+        "approvals-{}",
+        Utc::now().with_timezone(&tz).format("%Y%m%d")
+    );
+
+    let (body, content_type, extension) = match options.format {
+        ExportFormat::Csv => (
+            csv_body(pool, user_id, roles, params, columns),
+            "text/csv; charset=utf-8",
+            "csv",
+        ),
+        ExportFormat::Xlsx => (
+            xlsx_body(pool, user_id, roles, params, columns).await?,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "xlsx",
+        ),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.{}\"", file_stem, extension),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+fn csv_body(
+    pool: PgPool,
+    user_id: Uuid,
+    roles: UserRoles,
+    params: ApprovalListQuery,
+    columns: ExportColumns,
+) -> Body {
+    Body::from_stream(async_stream::stream! {
+        let repo = ApprovalRepository::new(pool.clone());
+        let mut names = UserNames::new(UserRepository::new(pool));
+        let rows = repo.stream_all(
+            ApprovalScope::Visible {
+                viewer_id: user_id,
+                roles: &roles,
+            },
+            &params,
+        );
+        futures::pin_mut!(rows);
+
+        // 엑셀에서 한글이 깨지지 않도록 UTF-8 BOM
+        yield csv_line(&columns.header()).map(|line| [b"\xEF\xBB\xBF".as_slice(), &line].concat());
+
+        while let Some(row) = rows.next().await {
+            let line = match row {
+                Ok(request) => match names.load_for(&request).await {
+                    Ok(names) => csv_line(&columns.row(&request, names)),
+                    Err(e) => Err(std::io::Error::other(e)),
+                },
+                Err(e) => Err(std::io::Error::other(e)),
+            };
+            if let Err(e) = &line {
+                // 헤더가 이미 나간 뒤라 상태 코드를 바꿀 수 없으므로 스트림을 중단합니다.
+                eprintln!("Failed to export approvals: {:?}", e);
+            }
+            let failed = line.is_err();
+            yield line;
+            if failed {
+                break;
+            }
+        }
+    })
+}
+
+// 한 행을 CSV로 인코딩합니다. (수식 이스케이프는 services::export::csv_cell)
+fn csv_line(values: &[String]) -> std::io::Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(values.iter().map(|value| csv_cell(value)))?;
+    writer
+        .into_inner()
+        .map_err(|e| std::io::Error::other(e.to_string()))
+}
+
+async fn xlsx_body(
+    pool: PgPool,
+    user_id: Uuid,
+    roles: UserRoles,
+    params: ApprovalListQuery,
+    columns: ExportColumns,
+) -> Result<Body, (StatusCode, String)> {
+    let internal = |e: String| {
+        eprintln!("Failed to export approvals: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e)
+    };
+
+    let mut workbook = Workbook::new();
+    {
+        let sheet = workbook.add_worksheet_with_constant_memory();
+        sheet
+            .set_name("Approvals")
+            .map_err(|e| internal(e.to_string()))?;
+        for (col, value) in columns.header().iter().enumerate() {
+            sheet
+                .write_string(0, col as u16, value)
+                .map_err(|e| internal(e.to_string()))?;
+        }
+
+        let repo = ApprovalRepository::new(pool.clone());
+        let mut names = UserNames::new(UserRepository::new(pool));
+        let rows = repo.stream_all(
+            ApprovalScope::Visible {
+                viewer_id: user_id,
+                roles: &roles,
+            },
+            &params,
+        );
+        futures::pin_mut!(rows);
+
+        let mut row_idx = 1u32;
+        while let Some(row) = rows.next().await {
+            let request = row.map_err(|e| internal(e.to_string()))?;
+            let names = names
+                .load_for(&request)
+                .await
+                .map_err(|e| internal(e.to_string()))?;
+            for (col, value) in columns.row(&request, names).iter().enumerate() {
+                sheet
+                    .write_string(row_idx, col as u16, value)
+                    .map_err(|e| internal(e.to_string()))?;
+            }
+            row_idx += 1;
+        }
+    }
+
+    // 임시 파일로 저장 후 열어둔 채 삭제하고(파일 핸들은 유지), 파일 내용을 스트리밍합니다.
+    let path = std::env::temp_dir().join(format!("pxm-export-{}.xlsx", Uuid::new_v4()));
+    let save_path = path.clone();
+    tokio::task::spawn_blocking(move || workbook.save(&save_path))
+        .await
+        .map_err(|e| internal(e.to_string()))?
+        .map_err(|e| internal(e.to_string()))?;
+
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|e| internal(e.to_string()))?;
+    let _ = tokio::fs::remove_file(&path).await;
+
+    Ok(Body::from_stream(ReaderStream::new(file)))
+}
@@ -1,4 +1,14 @@
 pub mod approval_handler;
+pub mod attachment_handler;
 pub mod auth_handler;
+pub mod chat_handler;
+pub mod email_template_handler;
+pub mod export_handler;
+pub mod notification_handler;
 pub mod org_handler;
+pub mod realtime_handler;
+pub mod role_handler;
+pub mod stats_handler;
+pub mod template_bundle_handler;
 pub mod template_handler;
+pub mod webhook_handler;
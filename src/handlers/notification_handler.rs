@@ -0,0 +1,234 @@
+use crate::{
+    domain::{
+        digest::{
+            DEFAULT_SEND_TIME, DigestSettings, DigestSettingsResponse, UpdateDigestSettingsDto,
+            parse_send_time,
+        },
+        email::SUPPORTED_LOCALES,
+        notification::{NotificationListQuery, NotificationPreference, NotificationType},
+        pagination::{page_size, timestamp_after},
+        user::AuthUser,
+    },
+    repositories::{
+        digest_repository::DigestRepository, email_repository::EmailRepository,
+        notification_repository::NotificationRepository,
+    },
+    utils::timezone::org_timezone,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Notification query failed: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+// GET /notifications?unread_only=true&limit=&cursor= (최신순)
+pub async fn list_notifications(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Query(params): Query<NotificationListQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let before = timestamp_after(params.cursor.as_deref(), "created_at")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let page = NotificationRepository::new(pool)
+        .find_page(user_id, params.unread_only, before, page_size(params.limit))
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!(page)))
+}
+
+// GET /notifications/unread-count - 배지 표시용
+pub async fn unread_count(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let count = NotificationRepository::new(pool)
+        .count_unread(user_id)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!({ "unread_count": count })))
+}
+
+// POST /notifications/:id/read
+pub async fn mark_read(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let notification = NotificationRepository::new(pool)
+        .mark_read(user_id, id)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Notification not found".to_string()))?;
+    Ok(Json(serde_json::json!(notification)))
+}
+
+// POST /notifications/read-all
+pub async fn mark_all_read(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let updated = NotificationRepository::new(pool)
+        .mark_all_read(user_id)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!({ "updated": updated })))
+}
+
+// GET /notifications/preferences - 모든 유형의 수신 여부
+pub async fn get_preferences(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<Vec<NotificationPreference>>, (StatusCode, String)> {
+    let repo = NotificationRepository::new(pool);
+    load_preferences(&repo, user_id).await.map(Json)
+}
+
+// PUT /notifications/preferences  { "assigned": true, "mentioned": false, ... }
+// 보낸 유형만 바뀌고 나머지는 그대로입니다.
+pub async fn update_preferences(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<HashMap<NotificationType, bool>>,
+) -> Result<Json<Vec<NotificationPreference>>, (StatusCode, String)> {
+    let repo = NotificationRepository::new(pool);
+    let changes: Vec<(NotificationType, bool)> = payload.into_iter().collect();
+    repo.set_preferences(user_id, &changes)
+        .await
+        .map_err(db_error)?;
+    load_preferences(&repo, user_id).await.map(Json)
+}
+
+#[derive(Deserialize)]
+pub struct LocaleDto {
+    pub locale: String,
+}
+
+// PUT /notifications/locale  { "locale": "en" } - 알림 메일 언어
+pub async fn update_locale(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<LocaleDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    if !SUPPORTED_LOCALES.contains(&payload.locale.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Supported locales: {}", SUPPORTED_LOCALES.join(", ")),
+        ));
+    }
+    let updated = EmailRepository::new(pool)
+        .set_user_locale(user_id, &payload.locale)
+        .await
+        .map_err(db_error)?;
+    if !updated {
+        return Err((StatusCode::NOT_FOUND, "User not found".to_string()));
+    }
+    Ok(Json(serde_json::json!({ "locale": payload.locale })))
+}
+
+// GET /notifications/digest (설정이 없으면 꺼진 기본값)
+pub async fn get_digest_settings(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+) -> Result<Json<DigestSettingsResponse>, (StatusCode, String)> {
+    let settings = DigestRepository::new(pool)
+        .find_settings(user_id)
+        .await
+        .map_err(db_error)?
+        .unwrap_or_else(|| default_digest_settings(user_id));
+    Ok(Json(digest_response(settings)))
+}
+
+// PUT /notifications/digest {"enabled": true, "send_time": "08:30", "timezone": "Asia/Seoul"}
+pub async fn update_digest_settings(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<UpdateDigestSettingsDto>,
+) -> Result<Json<DigestSettingsResponse>, (StatusCode, String)> {
+    let repo = DigestRepository::new(pool);
+    let current = repo
+        .find_settings(user_id)
+        .await
+        .map_err(db_error)?
+        .unwrap_or_else(|| default_digest_settings(user_id));
+
+    let send_time = match payload.send_time.as_deref() {
+        Some(value) => parse_send_time(value).map_err(|e| (StatusCode::BAD_REQUEST, e))?,
+        None => current.send_time,
+    };
+    let timezone = match payload.timezone {
+        Some(Some(name)) => {
+            // 잘못된 이름은 스케줄러 쿼리를 깨뜨리므로 저장 전에 확인합니다.
+            let tz: Tz = name.parse().map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Unknown timezone '{}'", name),
+                )
+            })?;
+            Some(tz.name().to_string())
+        }
+        Some(None) => None,
+        None => current.timezone,
+    };
+
+    let settings = repo
+        .upsert_settings(
+            user_id,
+            payload.enabled.unwrap_or(current.enabled),
+            send_time,
+            timezone.as_deref(),
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(Json(digest_response(settings)))
+}
+
+fn default_digest_settings(user_id: Uuid) -> DigestSettings {
+    DigestSettings {
+        user_id,
+        enabled: false,
+        send_time: parse_send_time(DEFAULT_SEND_TIME).unwrap_or_default(),
+        timezone: None,
+        last_sent_on: None,
+    }
+}
+
+fn digest_response(settings: DigestSettings) -> DigestSettingsResponse {
+    DigestSettingsResponse {
+        enabled: settings.enabled,
+        send_time: settings.send_time.format("%H:%M").to_string(),
+        effective_timezone: settings
+            .timezone
+            .clone()
+            .unwrap_or_else(|| org_timezone().name().to_string()),
+        timezone: settings.timezone,
+        last_sent_on: settings.last_sent_on,
+    }
+}
+
+async fn load_preferences(
+    repo: &NotificationRepository,
+    user_id: Uuid,
+) -> Result<Vec<NotificationPreference>, (StatusCode, String)> {
+    let disabled = repo.find_disabled_types(user_id).await.map_err(db_error)?;
+    Ok(NotificationType::ALL
+        .into_iter()
+        .map(|notification_type| NotificationPreference {
+            notification_type,
+            enabled: !disabled.iter().any(|t| t == notification_type.as_str()),
+        })
+        .collect())
+}
@@ -1,7 +1,15 @@
 // use crate::repositories::user_repository::UserRepository;
+use crate::domain::{
+    login::account_key,
+    pagination::{Cursor, Page, PageQuery, page_size},
+    session::REVOKE_DEACTIVATED,
+    user::{STATUS_ACTIVE, USER_STATUSES, UserStatusDto, UserSummary},
+};
+use crate::repositories::{session_repository::SessionRepository, user_repository::UserRepository};
+use crate::services::{auth_context::auth_cache, login_guard::LoginGuard};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
 use serde::Serialize;
@@ -59,35 +67,99 @@ pub async fn get_my_manager(
 }
 
 // Simple User Search/List
+// GET /org/users?limit=&cursor= (이름 순)
 pub async fn list_users(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<serde_json::Value>>, StatusCode> {
-    let users = sqlx::query!(
+    Query(params): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let limit = page_size(params.limit);
+    let after = params
+        .cursor
+        .as_deref()
+        .map(|raw| Cursor::decode(raw, "full_name"))
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let (after_name, after_id) = after.map(|c| (c.value, c.id)).unzip();
+
+    let db_error = |e: sqlx::Error| {
+        eprintln!("Failed to list users: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    };
+
+    let total_count =
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!" FROM users WHERE status = 'ACTIVE'"#)
+            .fetch_one(&pool)
+            .await
+            .map_err(db_error)?;
+
+    let users = sqlx::query_as!(
+        UserSummary,
         r#"
-        SELECT id, full_name, email, position 
-        FROM users 
-        WHERE status = 'ACTIVE' 
-        ORDER BY full_name ASC
-        "#
+        SELECT id, full_name, email, position
+        FROM users
+        WHERE status = 'ACTIVE'
+          AND ($1::text IS NULL OR (full_name, id) > ($1, $2))
+        ORDER BY full_name ASC, id ASC
+        LIMIT $3
+        "#,
+        after_name,
+        after_id,
+        limit + 1
     )
     .fetch_all(&pool)
     .await
-    .map_err(|e| {
-        eprintln!("Failed to list users: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .map_err(db_error)?;
 
-    let dtos = users
-        .into_iter()
-        .map(|u| {
-            serde_json::json!({
-                "id": u.id,
-                "full_name": u.full_name,
-                "email": u.email,
-                "position": u.position
-            })
-        })
-        .collect();
+    let page = Page::from_rows(users, limit, total_count, |u| {
+        Cursor::new("full_name", u.full_name.clone(), u.id)
+    });
+    Ok(Json(serde_json::json!(page)))
+}
+
+// PUT /org/users/:id/status (user:manage)
+// ACTIVE가 아닌 상태로 바꾸면 모든 세션을 폐기합니다. (auth_middleware도 즉시 거부)
+pub async fn set_user_status(
+    Path(user_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Json(payload): Json<UserStatusDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let status = payload.status.to_uppercase();
+    if !USER_STATUSES.contains(&status.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Supported statuses: {}", USER_STATUSES.join(", ")),
+        ));
+    }
+    let updated = UserRepository::new(pool.clone())
+        .set_status(user_id, &status)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !updated {
+        return Err((StatusCode::NOT_FOUND, "User not found".to_string()));
+    }
+    auth_cache().invalidate(user_id);
+    if status != STATUS_ACTIVE {
+        SessionRepository::new(pool)
+            .revoke_all(user_id, REVOKE_DEACTIVATED)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+    Ok(Json(serde_json::json!({ "id": user_id, "status": status })))
+}
 
-    Ok(Json(dtos))
+// POST /org/users/:id/unlock (user:manage) - 로그인 실패로 잠긴 계정 해제
+pub async fn unlock_user(
+    Path(user_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let user = UserRepository::new(pool.clone())
+        .find_by_id(user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "User not found".to_string()))?;
+    LoginGuard::new(pool)
+        .unlock(&account_key(&user.email))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::NO_CONTENT)
 }
@@ -0,0 +1,129 @@
+use crate::{
+    domain::realtime::UserEvent, domain::user::AuthUser,
+    repositories::realtime_repository::RealtimeRepository, services::realtime::HubMessage,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::Stream;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use tokio::sync::broadcast::error::RecvError;
+
+// 재연결 시 한 번에 DB에서 읽어 보낼 이벤트 수
+const REPLAY_BATCH: i64 = 500;
+// 늦게 commit된 이벤트를 중복 없이 보내기 위해 기억해 두는 최근 전송 id 수
+const SENT_HISTORY: usize = 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    // EventSource는 헤더를 직접 지정할 수 없으므로 첫 연결 때 쿼리로도 받습니다.
+    pub last_event_id: Option<i64>,
+}
+
+// GET /events/stream (text/event-stream)
+// 로그인 사용자에게 온 실시간 이벤트(inbox.added, inbox.removed, request.updated, comment.added)를 보냅니다.
+// - SSE id = user_events.id. 재연결 시 Last-Event-ID 헤더(또는 ?last_event_id=) 이후 이벤트를 먼저 다시 보냅니다.
+// - 다른 서버 인스턴스에서 발생한 이벤트도 LISTEN/NOTIFY(RealtimeHub)를 통해 전달됩니다.
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Query(params): Query<StreamQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, String)> {
+    let header_id = match headers.get("last-event-id") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .ok_or((StatusCode::BAD_REQUEST, "Invalid Last-Event-ID".to_string()))?,
+        ),
+        None => None,
+    };
+
+    // 구독을 먼저 해 두어야 DB 조회와 구독 사이에 발생한 이벤트를 놓치지 않습니다.
+    let mut receiver = state.realtime.subscribe();
+    let repo = RealtimeRepository::new(state.pool.clone());
+
+    let (mut last_id, replay) = match header_id.or(params.last_event_id) {
+        Some(id) => (id, true),
+        None => {
+            let latest = repo.latest_id(user_id).await.map_err(|e| {
+                eprintln!("Failed to load latest user event: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+            (latest, false)
+        }
+    };
+
+    let stream = async_stream::stream! {
+        let mut needs_replay = replay;
+        let mut sent = BTreeSet::new();
+        loop {
+            if needs_replay {
+                needs_replay = false;
+                loop {
+                    let events = match repo.find_after(user_id, last_id, REPLAY_BATCH).await {
+                        Ok(events) => events,
+                        Err(e) => {
+                            // 스트림을 끝내면 클라이언트가 마지막 id로 다시 연결합니다.
+                            eprintln!("Failed to replay user events: {:?}", e);
+                            yield Err(axum::Error::new(e));
+                            return;
+                        }
+                    };
+                    let done = (events.len() as i64) < REPLAY_BATCH;
+                    for event in &events {
+                        last_id = event.id;
+                        remember(&mut sent, event.id);
+                        yield to_sse(event);
+                    }
+                    if done {
+                        break;
+                    }
+                }
+            }
+
+            match receiver.recv().await {
+                Ok(HubMessage::Event(event)) if event.user_id == user_id => {
+                    if event.id > last_id {
+                        // NOTIFY 내용을 바로 보내지 않고 DB에서 last_id 이후를 다시 읽습니다.
+                        // (먼저 commit된 다른 이벤트, 놓친 NOTIFY까지 id 순으로)
+                        needs_replay = true;
+                    } else if !sent.contains(&event.id) {
+                        // id는 INSERT 때 정해지므로, 더 큰 id보다 늦게 commit된 이벤트는 따로 보냅니다.
+                        remember(&mut sent, event.id);
+                        yield to_sse(&event);
+                    }
+                }
+                Ok(HubMessage::Event(_)) => {}
+                // NOTIFY를 놓쳤을 수 있음 -> DB에서 last_id 이후를 다시 읽음
+                Ok(HubMessage::Resync) | Err(RecvError::Lagged(_)) => needs_replay = true,
+                Err(RecvError::Closed) => return,
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn remember(sent: &mut BTreeSet<i64>, id: i64) {
+    sent.insert(id);
+    while sent.len() > SENT_HISTORY {
+        sent.pop_first();
+    }
+}
+
+fn to_sse(event: &UserEvent) -> Result<Event, axum::Error> {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.event_type.as_str())
+        .json_data(event)
+}
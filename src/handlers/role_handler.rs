@@ -0,0 +1,75 @@
+use crate::{
+    domain::role::AssignRolesDto,
+    repositories::{role_repository::RoleRepository, user_repository::UserRepository},
+    services::auth_context::auth_cache,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// GET /roles - 역할 목록과 각 역할의 권한
+pub async fn list_roles(State(pool): State<PgPool>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let repo = RoleRepository::new(pool);
+
+    match repo.find_all().await {
+        Ok(roles) => Ok(Json(serde_json::json!(roles))),
+        Err(e) => {
+            eprintln!("Failed to list roles: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// GET /org/users/:id/roles
+pub async fn get_user_roles(
+    Path(user_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let repo = RoleRepository::new(pool);
+
+    match repo.find_for_user(user_id).await {
+        Ok(roles) => Ok(Json(serde_json::json!(roles))),
+        Err(e) => {
+            eprintln!("Failed to get user roles: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// PUT /org/users/:id/roles
+// Body: { "roles": ["TEMPLATE_MANAGER", "AUDITOR"] } - 기존 역할을 교체합니다.
+pub async fn set_user_roles(
+    Path(user_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Json(payload): Json<AssignRolesDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    // 1. 사용자 확인
+    UserRepository::new(pool.clone())
+        .find_by_id(user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "User not found".to_string()))?;
+
+    // 2. 역할 교체
+    let repo = RoleRepository::new(pool);
+    match repo.set_user_roles(user_id, &payload.roles).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            return Err((StatusCode::BAD_REQUEST, "Unknown role".to_string()));
+        }
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+    // 다음 요청부터 새 역할이 적용되도록 인증 캐시에서 제거
+    auth_cache().invalidate(user_id);
+
+    let roles = repo
+        .find_for_user(user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(serde_json::json!(roles)))
+}
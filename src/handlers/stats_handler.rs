@@ -0,0 +1,113 @@
+use crate::{
+    domain::{
+        role::{Permission, UserRoles},
+        stats::{StatsQuery, StatsScope},
+        user::AuthUser,
+    },
+    repositories::{
+        department_repository::DepartmentRepository, stats_repository::StatsRepository,
+    },
+};
+use axum::{
+    Json,
+    extract::{Extension, Query, State},
+    http::StatusCode,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// [Stats]
+// stats:read_all 권한이 있으면 전사, 부서장이면 관리하는 부서(하위 부서 포함)의 문서만 집계합니다.
+// 그 외 사용자는 403.
+
+// GET /stats/templates - 템플릿별 상신~완료 소요 시간(평균/중앙값/90분위), 반려율
+pub async fn template_stats(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let scope = resolve_scope(&pool, user_id, &roles).await?;
+    let stats = StatsRepository::new(pool)
+        .by_template(scope, &params)
+        .await
+        .map_err(stats_error)?;
+    Ok(Json(serde_json::json!(stats)))
+}
+
+// GET /stats/steps - 템플릿 결재 단계별 소요 시간, 반려율
+pub async fn step_stats(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let scope = resolve_scope(&pool, user_id, &roles).await?;
+    let stats = StatsRepository::new(pool)
+        .by_step(scope, &params)
+        .await
+        .map_err(stats_error)?;
+    Ok(Json(serde_json::json!(stats)))
+}
+
+// GET /stats/approvers - 결재자별 처리 건수, 소요 시간, 반려율
+pub async fn approver_stats(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let scope = resolve_scope(&pool, user_id, &roles).await?;
+    let stats = StatsRepository::new(pool)
+        .by_approver(scope, &params)
+        .await
+        .map_err(stats_error)?;
+    Ok(Json(serde_json::json!(stats)))
+}
+
+// GET /stats/backlog - 결재자별 현재 대기 건수 (department_id 필터만 적용)
+pub async fn backlog_stats(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let scope = resolve_scope(&pool, user_id, &roles).await?;
+    let stats = StatsRepository::new(pool)
+        .backlog(scope, &params)
+        .await
+        .map_err(stats_error)?;
+    Ok(Json(serde_json::json!(stats)))
+}
+
+async fn resolve_scope(
+    pool: &PgPool,
+    user_id: Uuid,
+    roles: &UserRoles,
+) -> Result<StatsScope, (StatusCode, String)> {
+    if roles.has(Permission::StatsReadAll) {
+        return Ok(StatsScope::All);
+    }
+
+    let is_manager = DepartmentRepository::new(pool.clone())
+        .is_manager(user_id)
+        .await
+        .map_err(stats_error)?;
+    if is_manager {
+        Ok(StatsScope::ManagedBy(user_id))
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            "Statistics are available to department managers and administrators".to_string(),
+        ))
+    }
+}
+
+fn stats_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Failed to load statistics: {:?}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
@@ -0,0 +1,391 @@
+use crate::{
+    domain::{
+        approval::{ApprovalStep, FlowProcess},
+        template::CreateTemplateDto,
+        template_bundle::{
+            BundleStep, BundleWorkflow, ExportReport, ImportConflict, ImportReport,
+            PortableApprover, ResolvedReference, TemplateBundle, UnresolvedReference,
+        },
+        title_pattern::TitlePattern,
+    },
+    repositories::{
+        department_repository::DepartmentRepository, template_repository::TemplateRepository,
+        user_repository::UserRepository,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: BundleFormat,
+}
+
+// GET /templates/:id/export?format=json|yaml
+// 결재자 UUID는 email로, 공개 부서는 부서 코드로 변환해 내보냅니다.
+// 변환하지 못한 참조가 있으면 번들을 만들지 않고 422와 함께 리포트를 반환합니다.
+pub async fn export_template(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let template = TemplateRepository::new(pool.clone())
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+
+    let user_repo = UserRepository::new(pool.clone());
+    let department_repo = DepartmentRepository::new(pool);
+
+    let mut unresolved = Vec::new();
+
+    // 1. 결재선/참조자: user UUID -> email
+    let flow_process = &template.workflow_snapshot.0;
+    let mut steps = Vec::new();
+    for (idx, step) in flow_process.steps.iter().enumerate() {
+        let path = format!("workflow.steps[{}].approver", idx);
+        if let Some(approver) =
+            to_portable(&user_repo, step.approver_id, path, &mut unresolved).await?
+        {
+            steps.push(BundleStep {
+                seq: step.seq,
+                name: step.name.clone(),
+                approver,
+            });
+        }
+    }
+    let mut references = Vec::new();
+    for (idx, user_id) in flow_process.references.iter().enumerate() {
+        let path = format!("workflow.references[{}]", idx);
+        if let Some(reference) = to_portable(&user_repo, *user_id, path, &mut unresolved).await? {
+            references.push(reference);
+        }
+    }
+
+    // 2. 공개 부서: department_id -> code
+    let mut visible_departments = Vec::new();
+    for (idx, department_id) in template.visible_department_ids.iter().enumerate() {
+        match department_repo
+            .find_by_id(*department_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        {
+            Some(department) => visible_departments.push(department.code),
+            None => unresolved.push(UnresolvedReference {
+                path: format!("visible_departments[{}]", idx),
+                identifier: department_id.to_string(),
+                reason: "No department with this id".to_string(),
+            }),
+        }
+    }
+
+    if !unresolved.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ExportReport { unresolved }),
+        )
+            .into_response());
+    }
+
+    let bundle = TemplateBundle::new(
+        &template,
+        BundleWorkflow { steps, references },
+        visible_departments,
+    );
+
+    let (body, content_type, extension) = match params.format {
+        BundleFormat::Json => (
+            serde_json::to_string_pretty(&bundle)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+            "application/json",
+            "json",
+        ),
+        BundleFormat::Yaml => (
+            serde_yaml::to_string(&bundle)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+            "application/yaml",
+            "yaml",
+        ),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"template-{}.{}\"",
+                    template.id, extension
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// POST /templates/import?dry_run=true
+// Body: TemplateBundle (Content-Type이 yaml이면 YAML, 그 외에는 JSON)
+// - dry_run: 매핑 결과만 리포트하고 저장하지 않습니다.
+// - 해석하지 못한 식별자가 있으면 저장하지 않고 422와 함께 리포트를 반환합니다.
+// - 같은 이름의 템플릿이 이미 있으면 저장하지 않고 409와 함께 리포트를 반환합니다.
+pub async fn import_template(
+    State(pool): State<PgPool>,
+    Query(params): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<(StatusCode, Json<ImportReport>), (StatusCode, String)> {
+    // 1. 번들 파싱
+    let is_yaml = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("yaml"));
+    let bundle: TemplateBundle = if is_yaml {
+        serde_yaml::from_str(&body).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    } else {
+        serde_json::from_str(&body).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    };
+    bundle
+        .check_version()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    if let Some(pattern) = bundle.template.title_pattern.as_deref() {
+        TitlePattern::parse(pattern).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid title_pattern: {}", e),
+            )
+        })?;
+    }
+
+    let template_repo = TemplateRepository::new(pool.clone());
+    let user_repo = UserRepository::new(pool.clone());
+    let department_repo = DepartmentRepository::new(pool);
+
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+
+    // 2. 결재자/참조자 해석
+    let mut steps = Vec::new();
+    for (idx, step) in bundle.template.workflow.steps.iter().enumerate() {
+        let path = format!("workflow.steps[{}].approver", idx);
+        let identifier = step.approver.identifier();
+
+        match resolve(&user_repo, &department_repo, &step.approver).await? {
+            Ok(resolved_id) => {
+                resolved.push(ResolvedReference {
+                    path,
+                    identifier,
+                    resolved_id,
+                });
+                steps.push(ApprovalStep {
+                    seq: step.seq,
+                    name: step.name.clone(),
+                    approver_id: resolved_id,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                });
+            }
+            Err(reason) => unresolved.push(UnresolvedReference {
+                path,
+                identifier,
+                reason: reason.to_string(),
+            }),
+        }
+    }
+
+    let mut references = Vec::new();
+    for (idx, reference) in bundle.template.workflow.references.iter().enumerate() {
+        let path = format!("workflow.references[{}]", idx);
+        match resolve(&user_repo, &department_repo, reference).await? {
+            Ok(resolved_id) => {
+                resolved.push(ResolvedReference {
+                    path,
+                    identifier: reference.identifier(),
+                    resolved_id,
+                });
+                references.push(resolved_id);
+            }
+            Err(reason) => unresolved.push(UnresolvedReference {
+                path,
+                identifier: reference.identifier(),
+                reason: reason.to_string(),
+            }),
+        }
+    }
+
+    // 3. 공개 부서 해석
+    let mut visible_department_ids = Vec::new();
+    for (idx, code) in bundle.template.visible_departments.iter().enumerate() {
+        let path = format!("visible_departments[{}]", idx);
+        match department_repo
+            .find_by_code(code)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        {
+            Some(department) => {
+                resolved.push(ResolvedReference {
+                    path,
+                    identifier: code.clone(),
+                    resolved_id: department.id,
+                });
+                visible_department_ids.push(department.id);
+            }
+            None => unresolved.push(UnresolvedReference {
+                path,
+                identifier: code.clone(),
+                reason: "No department with this code".to_string(),
+            }),
+        }
+    }
+
+    // 4. 카테고리 확인
+    if let Some(category_code) = &bundle.template.category_code {
+        let categories = template_repo
+            .find_categories()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if !categories.iter().any(|c| &c.code == category_code) {
+            unresolved.push(UnresolvedReference {
+                path: "category_code".to_string(),
+                identifier: category_code.clone(),
+                reason: "No template category with this code".to_string(),
+            });
+        }
+    }
+
+    // 5. 이름 충돌 확인
+    let mut conflicts = Vec::new();
+    if let Some(existing) = template_repo
+        .find_active_by_name(&bundle.template.name)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    {
+        conflicts.push(ImportConflict {
+            path: "name".to_string(),
+            identifier: bundle.template.name.clone(),
+            existing_id: existing.id,
+        });
+    }
+
+    if params.dry_run || !unresolved.is_empty() || !conflicts.is_empty() {
+        let status = if !unresolved.is_empty() {
+            StatusCode::UNPROCESSABLE_ENTITY
+        } else if !conflicts.is_empty() {
+            StatusCode::CONFLICT
+        } else {
+            StatusCode::OK
+        };
+        return Ok((
+            status,
+            Json(ImportReport {
+                dry_run: params.dry_run,
+                resolved,
+                unresolved,
+                conflicts,
+                template: None,
+            }),
+        ));
+    }
+
+    // 6. 새 템플릿으로 저장
+    let template = template_repo
+        .create(CreateTemplateDto {
+            name: bundle.template.name,
+            description: bundle.template.description,
+            form_schema: bundle.template.form_schema,
+            workflow_snapshot: FlowProcess {
+                current_step: 1,
+                steps,
+                references,
+            },
+            category_code: bundle.template.category_code,
+            title_pattern: bundle.template.title_pattern,
+            visible_department_ids,
+        })
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImportReport {
+            dry_run: false,
+            resolved,
+            unresolved,
+            conflicts,
+            template: Some(template),
+        }),
+    ))
+}
+
+// 사용자를 찾지 못하면 unresolved에 기록하고 None을 반환합니다.
+async fn to_portable(
+    user_repo: &UserRepository,
+    user_id: Uuid,
+    path: String,
+    unresolved: &mut Vec<UnresolvedReference>,
+) -> Result<Option<PortableApprover>, (StatusCode, String)> {
+    let user = user_repo
+        .find_by_id(user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(match user {
+        Some(user) => Some(PortableApprover::Email { email: user.email }),
+        None => {
+            unresolved.push(UnresolvedReference {
+                path,
+                identifier: user_id.to_string(),
+                reason: "No user with this id".to_string(),
+            });
+            None
+        }
+    })
+}
+
+// 바깥 Result는 DB 에러, 안쪽 Result는 해석 실패 사유입니다.
+async fn resolve(
+    user_repo: &UserRepository,
+    department_repo: &DepartmentRepository,
+    approver: &PortableApprover,
+) -> Result<Result<Uuid, &'static str>, (StatusCode, String)> {
+    let db_error = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+
+    Ok(match approver {
+        PortableApprover::Email { email } => user_repo
+            .find_by_email(email)
+            .await
+            .map_err(db_error)?
+            .map(|u| u.id)
+            .ok_or("No user with this email"),
+        PortableApprover::DepartmentManager { department_code } => department_repo
+            .find_by_code(department_code)
+            .await
+            .map_err(db_error)?
+            .ok_or("No department with this code")
+            .and_then(|d| d.manager_id.ok_or("Department has no manager")),
+    })
+}
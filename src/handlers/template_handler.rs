@@ -1,12 +1,20 @@
 use crate::{
-    domain::template::CreateTemplateDto,
+    domain::{
+        pagination::{page_size, timestamp_after},
+        role::Permission,
+        template::{CreateTemplateDto, ListTemplatesQuery, PatchTemplateDto},
+        title_pattern::{TitleContext, TitlePattern},
+        user::AuthUser,
+    },
     repositories::{
-        approval_repository::ApprovalRepository, template_repository::TemplateRepository,
+        approval_repository::ApprovalRepository, department_repository::DepartmentRepository,
+        template_repository::TemplateRepository, user_repository::UserRepository,
     },
+    utils::timezone::org_timezone,
 };
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
 };
 use sqlx::PgPool;
@@ -15,68 +23,233 @@ use uuid::Uuid;
 pub async fn create_template(
     State(pool): State<PgPool>,
     Json(payload): Json<CreateTemplateDto>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    validate_title_pattern(payload.title_pattern.as_deref())?;
     let repo = TemplateRepository::new(pool);
 
     match repo.create(payload).await {
         Ok(template) => Ok(Json(serde_json::json!(template))),
+        Err(e) => Err(save_error("create", e)),
+    }
+}
+
+// GET /templates?include_archived=true&category=HR&limit=&cursor=
+// 기본적으로 보관(archive)된 템플릿은 제외하고, 사용자 부서에 공개된 템플릿만 보여줍니다.
+// template:manage 권한이 있으면 공개 범위와 관계없이 모든 템플릿을 봅니다.
+pub async fn list_templates(
+    State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
+    Query(params): Query<ListTemplatesQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let repo = TemplateRepository::new(pool);
+    let viewer_id = (!roles.has(Permission::TemplateManage)).then_some(user_id);
+
+    let after = timestamp_after(params.cursor.as_deref(), "created_at")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    match repo
+        .find_page(
+            params.include_archived,
+            params.category,
+            viewer_id,
+            after,
+            page_size(params.limit),
+        )
+        .await
+    {
+        Ok(page) => Ok(Json(serde_json::json!(page))),
         Err(e) => {
-            eprintln!("Failed to create template: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            eprintln!("Failed to list templates: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
         }
     }
 }
 
-pub async fn list_templates(
+// GET /templates/:id
+// 목록과 같은 공개 범위를 적용합니다. 공개되지 않은 템플릿은 존재 여부도 숨기기 위해 404로 응답합니다.
+pub async fn get_template(
+    Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
+    Extension(AuthUser {
+        id: user_id, roles, ..
+    }): Extension<AuthUser>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let repo = TemplateRepository::new(pool);
 
-    match repo.find_all().await {
-        Ok(templates) => Ok(Json(serde_json::json!(templates))),
+    let template = match repo.find_by_id(id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
         Err(e) => {
-            eprintln!("Failed to list templates: {:?}", e);
+            eprintln!("Failed to get template: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    if !roles.has(Permission::TemplateManage) {
+        match repo.is_visible_to(template.id, user_id).await {
+            Ok(true) => {}
+            Ok(false) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                eprintln!("Failed to check template visibility: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
+    Ok(Json(serde_json::json!(template)))
+}
+
+// GET /template-categories
+pub async fn list_categories(
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let repo = TemplateRepository::new(pool);
+
+    match repo.find_categories().await {
+        Ok(categories) => Ok(Json(serde_json::json!(categories))),
+        Err(e) => {
+            eprintln!("Failed to list template categories: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-pub async fn get_template(
+// PUT /templates/:id (전체 교체)
+pub async fn update_template(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateTemplateDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    validate_title_pattern(payload.title_pattern.as_deref())?;
+    let repo = TemplateRepository::new(pool);
+
+    match repo.update(id, payload).await {
+        Ok(Some(template)) => Ok(Json(serde_json::json!(template))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Template not found".to_string())),
+        Err(e) => Err(save_error("update", e)),
+    }
+}
+
+// PATCH /templates/:id (부분 수정)
+pub async fn patch_template(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Json(payload): Json<PatchTemplateDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    validate_title_pattern(payload.title_pattern.as_ref().and_then(Option::as_deref))?;
+    let repo = TemplateRepository::new(pool);
+
+    match repo.patch(id, payload).await {
+        Ok(Some(template)) => Ok(Json(serde_json::json!(template))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Template not found".to_string())),
+        Err(e) => Err(save_error("patch", e)),
+    }
+}
+
+// 잘못된 placeholder가 있는 제목 패턴은 저장 시점에 거부합니다.
+fn validate_title_pattern(pattern: Option<&str>) -> Result<(), (StatusCode, String)> {
+    if let Some(pattern) = pattern {
+        TitlePattern::parse(pattern).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid title_pattern: {}", e),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn save_error(action: &str, e: sqlx::Error) -> (StatusCode, String) {
+    match e {
+        // 존재하지 않는 category_code / department_id
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => (
+            StatusCode::BAD_REQUEST,
+            "Unknown category_code or department".to_string(),
+        ),
+        e => {
+            eprintln!("Failed to {} template: {:?}", action, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+    }
+}
+
+// POST /templates/:id/archive
+// 기존 결재 요청은 그대로 두고, 새 결재 요청 생성에서만 제외합니다.
+pub async fn archive_template(
     Path(id): Path<Uuid>,
     State(pool): State<PgPool>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let repo = TemplateRepository::new(pool);
 
-    match repo.find_by_id(id).await {
+    match repo.archive(id).await {
         Ok(Some(template)) => Ok(Json(serde_json::json!(template))),
         Ok(None) => Err(StatusCode::NOT_FOUND),
         Err(e) => {
-            eprintln!("Failed to get template: {:?}", e);
+            eprintln!("Failed to archive template: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
+// DELETE /templates/:id
+// 이 템플릿을 참조하는 결재 요청이 있으면 삭제를 거부합니다 (대신 archive 사용).
+pub async fn delete_template(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let repo = TemplateRepository::new(pool);
+
+    let usages = repo
+        .count_usages(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if usages > 0 {
+        return Err((
+            StatusCode::CONFLICT,
+            format!(
+                "Template is referenced by {} approval request(s); archive it instead",
+                usages
+            ),
+        ));
+    }
+
+    match repo.delete(id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "Template not found".to_string())),
+        // count 이후 동시에 결재 요청이 생성된 경우 FK(RESTRICT)가 막아줍니다.
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => Err((
+            StatusCode::CONFLICT,
+            "Template is referenced by approval requests; archive it instead".to_string(),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+}
+
 // 템플릿 기반으로 결재 요청 생성
 // POST /approvals/from-template/:template_id
-// Body: { "requester_id": "...", "form_data": { ... } }
+// Body: { "form_data": { ... } } - 요청자는 인증된 사용자입니다.
 // Title은 템플릿 이름 + Timestamp 등으로 자동 생성하거나 Body에서 받을 수도 있음.
 // FlowProcess는 템플릿에 정의된 스냅샷을 복사해옴.
 #[derive(serde::Deserialize)]
 pub struct CreateFromTemplateDto {
-    pub requester_id: Uuid,
     pub form_data: serde_json::Value,
-    // title은 선택적(Option), 없으면 "{Template Name} - {Date}" 형식
+    // title은 선택적(Option), 없으면 템플릿의 title_pattern 또는 "{Template Name} - {Date}" 형식
     pub title: Option<String>,
+    // true면 임시저장
+    #[serde(default)]
+    pub draft: bool,
 }
 
 pub async fn create_approval_from_template(
     Path(template_id): Path<Uuid>,
     State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
     Json(payload): Json<CreateFromTemplateDto>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     let template_repo = TemplateRepository::new(pool.clone());
-    let approval_repo = ApprovalRepository::new(pool);
+    let approval_repo = ApprovalRepository::new(pool.clone());
 
     // 1. 템플릿 조회
     let template = match template_repo.find_by_id(template_id).await {
@@ -90,14 +263,46 @@ pub async fn create_approval_from_template(
         }
     };
 
-    // 2. 제목 생성
-    let title = payload.title.unwrap_or_else(|| {
-        format!(
-            "{} - {}",
-            template.name,
-            chrono::Utc::now().format("%Y-%m-%d %H:%M")
-        )
-    });
+    // 사용자 부서(상위 부서 포함)에 공개된 템플릿인지 확인
+    let visible = template_repo
+        .is_visible_to(template.id, user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !visible {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Template is not available for your department".to_string(),
+        ));
+    }
+
+    if template.is_archived {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Template is archived and cannot be used for new requests".to_string(),
+        ));
+    }
+
+    // 2. 제목 생성 (직접 입력 > 템플릿 제목 패턴 > "{템플릿명} - {날짜}")
+    let title = match payload.title {
+        Some(title) => title,
+        None => {
+            let now = chrono::Utc::now().with_timezone(&org_timezone());
+            match template.title_pattern.as_deref() {
+                Some(pattern) => {
+                    render_title(
+                        &pool,
+                        pattern,
+                        &template.name,
+                        user_id,
+                        &payload.form_data,
+                        now,
+                    )
+                    .await?
+                }
+                None => format!("{} - {}", template.name, now.format("%Y-%m-%d %H:%M")),
+            }
+        }
+    };
 
     // 3. 결재 요청 생성 (템플릿의 워크플로우 복사)
     // 주의: 실제로는 form_data가 form_schema에 맞는지 검증(Validation)하는 로직이 필요함.
@@ -105,10 +310,23 @@ pub async fn create_approval_from_template(
     // Convert Json<FlowProcess> to FlowProcess
     let flow_process = template.workflow_snapshot.0;
 
-    match approval_repo
-        .create(title, payload.requester_id, payload.form_data, flow_process)
-        .await
-    {
+    let created = if payload.draft {
+        approval_repo
+            .create_draft(
+                title,
+                user_id,
+                payload.form_data,
+                flow_process,
+                Some(template.id),
+            )
+            .await
+    } else {
+        approval_repo
+            .create_from_template(template.id, title, user_id, payload.form_data, flow_process)
+            .await
+    };
+
+    match created {
         Ok(request) => Ok(Json(serde_json::json!(request))),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -116,3 +334,45 @@ pub async fn create_approval_from_template(
         )),
     }
 }
+
+// 요청자/부서 정보를 조회해 템플릿의 제목 패턴을 렌더링합니다.
+async fn render_title(
+    pool: &PgPool,
+    pattern: &str,
+    template_name: &str,
+    requester_id: Uuid,
+    form_data: &serde_json::Value,
+    now: chrono::DateTime<chrono_tz::Tz>,
+) -> Result<String, (StatusCode, String)> {
+    let pattern = TitlePattern::parse(pattern).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Template has an invalid title_pattern: {}", e),
+        )
+    })?;
+
+    let requester = UserRepository::new(pool.clone())
+        .find_by_id(requester_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::UNAUTHORIZED, "User not found".to_string()))?;
+
+    let department = match requester.department_id {
+        Some(department_id) => DepartmentRepository::new(pool.clone())
+            .find_by_id(department_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        None => None,
+    };
+
+    Ok(pattern.render(&TitleContext {
+        form_data,
+        requester_full_name: &requester.full_name,
+        requester_email: &requester.email,
+        requester_position: requester.position.as_deref(),
+        department_name: department.as_ref().map(|d| d.name.as_str()),
+        department_code: department.as_ref().map(|d| d.code.as_str()),
+        template_name,
+        now,
+    }))
+}
@@ -0,0 +1,230 @@
+use crate::{
+    domain::{
+        pagination::{page_size, timestamp_after},
+        user::AuthUser,
+        webhook::{CreateWebhookDto, DeliveryListQuery, PatchWebhookDto, WebhookEventType},
+    },
+    repositories::webhook_repository::WebhookRepository,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use rand::RngCore;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// [Webhook Admin]
+// 모든 라우트는 webhook:manage 권한이 필요합니다. (main.rs)
+
+// GET /webhooks
+pub async fn list_webhooks(
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let subscriptions = WebhookRepository::new(pool)
+        .find_all()
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!(subscriptions)))
+}
+
+// POST /webhooks - 응답에만 secret을 포함합니다. (이후 조회에서는 노출하지 않음)
+pub async fn create_webhook(
+    State(pool): State<PgPool>,
+    Extension(AuthUser { id: user_id, .. }): Extension<AuthUser>,
+    Json(payload): Json<CreateWebhookDto>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    validate_url(&payload.url)?;
+    let secret = match payload.secret {
+        Some(secret) => validate_secret(secret)?,
+        None => generate_secret(),
+    };
+
+    let repo = WebhookRepository::new(pool);
+    let subscription = match repo
+        .create(
+            payload.name,
+            payload.url,
+            secret,
+            event_type_codes(&payload.event_types),
+            payload.template_id,
+            user_id,
+        )
+        .await
+    {
+        Ok(subscription) => subscription,
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            return Err((StatusCode::BAD_REQUEST, "Unknown template".to_string()));
+        }
+        Err(e) => return Err(db_error(e)),
+    };
+
+    let mut body = serde_json::json!(subscription);
+    body["secret"] = serde_json::json!(subscription.secret);
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+// GET /webhooks/:id
+pub async fn get_webhook(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let subscription = WebhookRepository::new(pool)
+        .find_by_id(id)
+        .await
+        .map_err(db_error)?
+        .ok_or(not_found())?;
+    Ok(Json(serde_json::json!(subscription)))
+}
+
+// PATCH /webhooks/:id
+// Body 예: { "is_active": false } / { "event_types": ["approval.approved", "approval.rejected"] }
+pub async fn patch_webhook(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Json(payload): Json<PatchWebhookDto>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let repo = WebhookRepository::new(pool);
+    let mut subscription = repo
+        .find_by_id(id)
+        .await
+        .map_err(db_error)?
+        .ok_or(not_found())?;
+
+    if let Some(name) = payload.name {
+        subscription.name = name;
+    }
+    if let Some(url) = payload.url {
+        validate_url(&url)?;
+        subscription.url = url;
+    }
+    if let Some(secret) = payload.secret {
+        subscription.secret = validate_secret(secret)?;
+    }
+    if let Some(event_types) = payload.event_types {
+        subscription.event_types = event_type_codes(&event_types);
+    }
+    if let Some(template_id) = payload.template_id {
+        subscription.template_id = Some(template_id);
+    }
+    if let Some(is_active) = payload.is_active {
+        subscription.is_active = is_active;
+    }
+
+    match repo.update(subscription).await {
+        Ok(updated) => Ok(Json(serde_json::json!(updated))),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Err((StatusCode::BAD_REQUEST, "Unknown template".to_string()))
+        }
+        Err(e) => Err(db_error(e)),
+    }
+}
+
+// DELETE /webhooks/:id - 전송 이력도 함께 삭제됩니다.
+pub async fn delete_webhook(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let deleted = WebhookRepository::new(pool)
+        .delete(id)
+        .await
+        .map_err(db_error)?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(not_found())
+    }
+}
+
+// GET /webhooks/:id/deliveries?status=dead&cursor=...&limit=20 - 전송 이력 (최신순)
+pub async fn list_deliveries(
+    Path(id): Path<Uuid>,
+    State(pool): State<PgPool>,
+    Query(params): Query<DeliveryListQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let before = timestamp_after(params.cursor.as_deref(), "created_at")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let repo = WebhookRepository::new(pool);
+    repo.find_by_id(id)
+        .await
+        .map_err(db_error)?
+        .ok_or(not_found())?;
+
+    let page = repo
+        .find_deliveries_page(id, params.status, before, page_size(params.limit))
+        .await
+        .map_err(db_error)?;
+    Ok(Json(serde_json::json!(page)))
+}
+
+// GET /webhooks/deliveries/:delivery_id - payload, 마지막 응답 코드/오류 포함
+pub async fn get_delivery(
+    Path(delivery_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let delivery = WebhookRepository::new(pool)
+        .find_delivery(delivery_id)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Delivery not found".to_string()))?;
+    Ok(Json(serde_json::json!(delivery)))
+}
+
+// POST /webhooks/deliveries/:delivery_id/redeliver
+// dead/succeeded 상태여도 다시 전송 대기열에 넣습니다. (시도 횟수 초기화)
+pub async fn redeliver(
+    Path(delivery_id): Path<Uuid>,
+    State(pool): State<PgPool>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    let delivery = WebhookRepository::new(pool)
+        .redeliver(delivery_id)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Delivery not found".to_string()))?;
+    Ok((StatusCode::ACCEPTED, Json(serde_json::json!(delivery))))
+}
+
+fn validate_url(url: &str) -> Result<(), (StatusCode, String)> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => Ok(()),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            "url must be an absolute http(s) URL".to_string(),
+        )),
+    }
+}
+
+fn validate_secret(secret: String) -> Result<String, (StatusCode, String)> {
+    if secret.len() < 16 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "secret must be at least 16 characters".to_string(),
+        ));
+    }
+    Ok(secret)
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+// 빈 배열 = 모든 이벤트
+fn event_type_codes(event_types: &[WebhookEventType]) -> Vec<String> {
+    let mut codes: Vec<String> = event_types.iter().map(|t| t.as_str().to_string()).collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+fn not_found() -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, "Webhook not found".to_string())
+}
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    eprintln!("Webhook query failed: {:?}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
@@ -1,6 +1,8 @@
 pub mod domain;
 pub mod handlers;
 pub mod repositories;
+pub mod services;
+pub mod state;
 pub mod utils;
 
 use sqlx::postgres::PgPoolOptions;
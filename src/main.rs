@@ -1,17 +1,57 @@
 use axum::{
     Router,
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    middleware::from_fn_with_state,
+    routing::{delete, get, post, put},
 };
 use backend::{
+    domain::{attachment::AttachmentPolicy, role::Permission},
     establish_connection,
     handlers::approval_handler::{
-        add_comment, approve_request, create_approval, get_approval, get_logs, list_approvals,
-        reject_request,
+        add_comment, approve_request, create_approval, get_approval, get_logs, inbox_counts,
+        list_approvals, list_inbox, reject_request, search_approvals, set_due_date,
+        submit_approval, withdraw_approval,
     },
+    handlers::attachment_handler::{
+        delete_attachment, download_attachment, list_attachments, upload_attachments,
+    },
+    handlers::chat_handler::{
+        chat_command, chat_interaction, create_chat_channel, create_chat_link_code,
+        create_my_chat_webhook, delete_chat_account, delete_chat_channel, delete_my_chat_webhook,
+        list_chat_channels, list_my_chat_webhooks,
+    },
+    handlers::email_template_handler::{
+        get_email_template, list_email_templates, update_email_template,
+    },
+    handlers::export_handler::{export_approvals, export_pdf},
+    handlers::notification_handler::{
+        get_digest_settings, get_preferences, list_notifications, mark_all_read, mark_read,
+        unread_count, update_digest_settings, update_locale, update_preferences,
+    },
+    handlers::realtime_handler::stream_events,
+    handlers::stats_handler::{approver_stats, backlog_stats, step_stats, template_stats},
+    handlers::webhook_handler::{
+        create_webhook, delete_webhook, get_delivery, get_webhook, list_deliveries, list_webhooks,
+        patch_webhook, redeliver,
+    },
+    services::chat::{ChatConfig, ChatConsumer, ChatDispatcher},
+    services::digest::{DigestConfig, DigestScheduler},
+    services::email::{EmailConfig, EmailDispatcher, transport_from_env},
+    services::notification::{DeadlineConfig, DeadlineNotifier, NotificationConsumer},
+    services::outbox::{OutboxConfig, OutboxDispatcher},
+    services::password_reset::{EmailResetNotifier, PasswordResetConfig, PasswordResetService},
+    services::pdf::check_pdf_font,
+    services::realtime::{RealtimeConsumer, RealtimeHub, purge_expired_events},
+    services::storage::LocalStorage,
+    services::webhook::{WebhookConfig, WebhookConsumer, WebhookDispatcher},
+    state::AppState,
+    utils::jwt_keys::{JwtKeys, install_jwt_keys},
+    utils::middleware::require_permission,
 };
 use dotenvy::dotenv;
 use std::env;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::net::TcpListener;
 
 #[tokio::main]
@@ -19,12 +59,88 @@ async fn main() {
     // 1. 환경 변수 로드 (.env)
     dotenv().ok();
 
+    // JWT 서명/검증 키 (키가 없거나 잘못되면 시작하지 않음)
+    let jwt_keys =
+        JwtKeys::from_env().unwrap_or_else(|e| panic!("Invalid JWT key configuration: {}", e));
+    println!("🔑 JWT signing key: {}", jwt_keys.signing_kid());
+    install_jwt_keys(jwt_keys);
+
     // 2. 데이터베이스 연결 설정
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let pool = establish_connection(&database_url).await;
 
     println!("✅ Connection to Database successful!");
 
+    // PDF 내보내기 폰트 (없으면 한글 문서 PDF는 거부됨)
+    check_pdf_font();
+
+    // 첨부파일 저장소 / 업로드 제한
+    let attachment_policy = AttachmentPolicy::from_env();
+    // 한 요청에 최대 5개 파일 분량 + multipart 헤더 여유분
+    let attachment_body_limit = attachment_policy.max_bytes * 5 + 64 * 1024;
+    // 실시간 이벤트 hub: 모든 인스턴스가 LISTEN 하여 자기 SSE 연결에 전달
+    let realtime = RealtimeHub::new();
+    tokio::spawn(realtime.clone().listen(pool.clone()));
+    tokio::spawn(purge_expired_events(pool.clone()));
+
+    // 채팅 incoming webhook 설정 (버튼 토큰 서명 키 포함)
+    let chat_config = Arc::new(ChatConfig::from_env());
+
+    // 메일 전송 (SMTP_HOST가 없으면 로그로만 남김)
+    let email_transport = transport_from_env().expect("Invalid SMTP configuration");
+    // 비밀번호 재설정 토큰은 메일로 전달
+    let password_reset_config = PasswordResetConfig::from_env();
+    let password_reset = PasswordResetService::new(
+        Arc::new(EmailResetNotifier::new(
+            email_transport.clone(),
+            password_reset_config.base_url.clone(),
+        )),
+        password_reset_config,
+    );
+
+    let state = AppState {
+        pool: pool.clone(),
+        storage: Arc::new(LocalStorage::from_env()),
+        attachment_policy: Arc::new(attachment_policy),
+        realtime,
+        chat: chat_config.clone(),
+        password_reset: Arc::new(password_reset),
+    };
+
+    // 도메인 이벤트 outbox -> consumer 전달 worker
+    tokio::spawn(
+        OutboxDispatcher::new(
+            pool.clone(),
+            vec![
+                Arc::new(WebhookConsumer::new(pool.clone())),
+                Arc::new(RealtimeConsumer::new(pool.clone())),
+                Arc::new(NotificationConsumer::new(pool.clone())),
+                Arc::new(ChatConsumer::new(pool.clone(), chat_config.clone())),
+            ],
+            OutboxConfig::default(),
+        )
+        .run(),
+    );
+    // Outgoing webhook 전송 worker (webhook_deliveries 대기열)
+    tokio::spawn(WebhookDispatcher::new(pool.clone(), WebhookConfig::from_env()).run());
+    // 알림 메일 전송 worker (email_deliveries 대기열)
+    tokio::spawn(
+        EmailDispatcher::new(
+            pool.clone(),
+            email_transport.clone(),
+            EmailConfig::from_env(),
+        )
+        .run(),
+    );
+    // 일일 결재 요약 메일 (신청한 사용자, 사용자 타임존의 send_time)
+    tokio::spawn(
+        DigestScheduler::new(pool.clone(), email_transport, DigestConfig::from_env()).run(),
+    );
+    // 채팅 메시지 전송 worker (chat_deliveries 대기열)
+    tokio::spawn(ChatDispatcher::new(pool.clone(), chat_config).run());
+    // 결재 기한 임박 알림
+    tokio::spawn(DeadlineNotifier::new(pool.clone(), DeadlineConfig::from_env()).run());
+
     // 3. Router 설정
     let cors = tower_http::cors::CorsLayer::new()
         .allow_origin(tower_http::cors::Any)
@@ -42,28 +158,200 @@ async fn main() {
             "/org/users",
             get(backend::handlers::org_handler::list_users),
         )
+        .route(
+            "/org/users/{id}/roles",
+            get(backend::handlers::role_handler::get_user_roles)
+                .put(backend::handlers::role_handler::set_user_roles)
+                .route_layer(from_fn_with_state(
+                    Permission::RoleManage,
+                    require_permission,
+                )),
+        )
+        .route(
+            "/org/users/{id}/status",
+            put(backend::handlers::org_handler::set_user_status).route_layer(from_fn_with_state(
+                Permission::UserManage,
+                require_permission,
+            )),
+        )
+        .route(
+            "/org/users/{id}/unlock",
+            post(backend::handlers::org_handler::unlock_user).route_layer(from_fn_with_state(
+                Permission::UserManage,
+                require_permission,
+            )),
+        )
+        .route(
+            "/roles",
+            get(backend::handlers::role_handler::list_roles).route_layer(from_fn_with_state(
+                Permission::RoleManage,
+                require_permission,
+            )),
+        )
+        .route(
+            "/auth/logout",
+            post(backend::handlers::auth_handler::logout),
+        )
+        .route(
+            "/auth/password/change",
+            post(backend::handlers::auth_handler::change_password),
+        )
+        // 사용자 등록은 관리자만 (부서 지정 포함)
+        .route(
+            "/auth/register",
+            post(backend::handlers::auth_handler::register).route_layer(from_fn_with_state(
+                Permission::UserManage,
+                require_permission,
+            )),
+        )
         // Approval Routes
         .route("/approvals", post(create_approval).get(list_approvals))
+        .route("/approvals/search", get(search_approvals))
+        .route("/approvals/export", get(export_approvals))
         .route("/approvals/{id}", get(get_approval))
+        .route("/approvals/{id}/submit", post(submit_approval))
         .route("/approvals/{id}/approve", post(approve_request))
         .route("/approvals/{id}/reject", post(reject_request))
+        .route("/approvals/{id}/withdraw", post(withdraw_approval))
+        .route("/approvals/{id}/due-date", put(set_due_date))
         .route("/approvals/{id}/comments", post(add_comment))
         .route("/approvals/{id}/logs", get(get_logs))
-        // Template Routes
+        .route("/approvals/{id}/pdf", get(export_pdf))
+        .route(
+            "/approvals/{id}/attachments",
+            post(upload_attachments)
+                .layer(DefaultBodyLimit::max(attachment_body_limit))
+                .get(list_attachments),
+        )
+        .route(
+            "/approvals/{id}/attachments/{attachment_id}",
+            get(download_attachment).delete(delete_attachment),
+        )
+        // Notification Center
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/unread-count", get(unread_count))
+        .route("/notifications/read-all", post(mark_all_read))
+        .route(
+            "/notifications/preferences",
+            get(get_preferences).put(update_preferences),
+        )
+        .route("/notifications/locale", put(update_locale))
+        .route(
+            "/notifications/digest",
+            get(get_digest_settings).put(update_digest_settings),
+        )
+        .route("/notifications/{id}/read", post(mark_read))
+        // Realtime (SSE)
+        .route("/events/stream", get(stream_events))
+        // Inbox Routes (개인 문서함)
+        .route("/inbox/counts", get(inbox_counts))
+        // Stats Routes (부서장 / stats:read_all)
+        .route("/stats/templates", get(template_stats))
+        .route("/stats/steps", get(step_stats))
+        .route("/stats/approvers", get(approver_stats))
+        .route("/stats/backlog", get(backlog_stats))
+        .route("/inbox/{box}", get(list_inbox))
+        // Webhook Routes (webhook:manage)
+        .merge(
+            Router::new()
+                .route("/webhooks", get(list_webhooks).post(create_webhook))
+                .route(
+                    "/webhooks/{id}",
+                    get(get_webhook).patch(patch_webhook).delete(delete_webhook),
+                )
+                .route("/webhooks/{id}/deliveries", get(list_deliveries))
+                .route("/webhooks/deliveries/{delivery_id}", get(get_delivery))
+                .route(
+                    "/webhooks/deliveries/{delivery_id}/redeliver",
+                    post(redeliver),
+                )
+                .route_layer(from_fn_with_state(
+                    Permission::WebhookManage,
+                    require_permission,
+                )),
+        )
+        // Email Template Routes (email_template:manage)
+        .merge(
+            Router::new()
+                .route("/email-templates", get(list_email_templates))
+                .route(
+                    "/email-templates/{notification_type}/{locale}",
+                    get(get_email_template).put(update_email_template),
+                )
+                .route_layer(from_fn_with_state(
+                    Permission::EmailTemplateManage,
+                    require_permission,
+                )),
+        )
+        // Chat Integration (개인 webhook / 채팅 계정 연결)
+        .route(
+            "/chat/webhooks",
+            get(list_my_chat_webhooks).post(create_my_chat_webhook),
+        )
+        .route("/chat/webhooks/{id}", delete(delete_my_chat_webhook))
+        .route("/chat/account", delete(delete_chat_account))
+        .route("/chat/account/link", post(create_chat_link_code))
+        // Chat Channel Routes (chat:manage)
+        .merge(
+            Router::new()
+                .route(
+                    "/chat/channels",
+                    get(list_chat_channels).post(create_chat_channel),
+                )
+                .route("/chat/channels/{id}", delete(delete_chat_channel))
+                .route_layer(from_fn_with_state(
+                    Permission::ChatManage,
+                    require_permission,
+                )),
+        )
+        // Template Routes (조회는 모든 사용자, 변경은 template:manage 권한)
         .route(
             "/templates",
             post(backend::handlers::template_handler::create_template)
+                .route_layer(from_fn_with_state(
+                    Permission::TemplateManage,
+                    require_permission,
+                ))
                 .get(backend::handlers::template_handler::list_templates),
         )
         .route(
             "/templates/{id}",
-            get(backend::handlers::template_handler::get_template),
+            put(backend::handlers::template_handler::update_template)
+                .patch(backend::handlers::template_handler::patch_template)
+                .delete(backend::handlers::template_handler::delete_template)
+                .route_layer(from_fn_with_state(
+                    Permission::TemplateManage,
+                    require_permission,
+                ))
+                .get(backend::handlers::template_handler::get_template),
+        )
+        .route(
+            "/template-categories",
+            get(backend::handlers::template_handler::list_categories),
+        )
+        .route(
+            "/templates/import",
+            post(backend::handlers::template_bundle_handler::import_template).route_layer(
+                from_fn_with_state(Permission::TemplateManage, require_permission),
+            ),
+        )
+        .route(
+            "/templates/{id}/export",
+            get(backend::handlers::template_bundle_handler::export_template).route_layer(
+                from_fn_with_state(Permission::TemplateManage, require_permission),
+            ),
+        )
+        .route(
+            "/templates/{id}/archive",
+            post(backend::handlers::template_handler::archive_template).route_layer(
+                from_fn_with_state(Permission::TemplateManage, require_permission),
+            ),
         )
         .route(
             "/approvals/from-template/{template_id}",
             post(backend::handlers::template_handler::create_approval_from_template),
         )
-        .layer(axum::middleware::from_fn_with_state(
+        .layer(from_fn_with_state(
             pool.clone(),
             backend::utils::middleware::auth_middleware,
         ));
@@ -71,22 +359,44 @@ async fn main() {
     let app = Router::new()
         .route("/", get(root))
         // Auth Routes (Public)
+        .route("/auth/login", post(backend::handlers::auth_handler::login))
         .route(
-            "/auth/register",
-            post(backend::handlers::auth_handler::register),
+            "/auth/refresh",
+            post(backend::handlers::auth_handler::refresh),
         )
-        .route("/auth/login", post(backend::handlers::auth_handler::login))
+        .route(
+            "/auth/password/forgot",
+            post(backend::handlers::auth_handler::forgot_password),
+        )
+        .route(
+            "/auth/password/reset",
+            post(backend::handlers::auth_handler::reset_password),
+        )
+        // 토큰 검증용 공개키 (Public)
+        .route(
+            "/.well-known/jwks.json",
+            get(backend::handlers::auth_handler::jwks),
+        )
+        // Chat 버튼 콜백 (Public, 서명된 토큰으로 검증)
+        .route("/chat/interactions", post(chat_interaction))
+        .route("/chat/commands", post(chat_command))
         // Merge Protected Routes
         .merge(protected_routes)
         .layer(cors)
-        .with_state(pool);
+        .with_state(state);
 
     // 4. 서버 시작
     let addr = SocketAddr::from(([0, 0, 0, 0], 3001)); // Listen on all interfaces
     println!("🚀 Server listening on {}", addr);
     let listener = TcpListener::bind(addr).await.unwrap();
     println!("Routes initialized.");
-    axum::serve(listener, app).await.unwrap();
+    // 로그인 실패 집계에 접속 IP(ConnectInfo)를 사용합니다.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
 async fn root() -> &'static str {
@@ -1,8 +1,40 @@
-use crate::domain::approval::{ApprovalLog, ApprovalRequest, FlowProcess};
+use crate::domain::approval::{
+    ApprovalAction, ApprovalListQuery, ApprovalLog, ApprovalRequest, FlowProcess, InboxBox,
+    InboxCounts,
+};
+use crate::domain::digest::{PendingDigestItem, StatusChangeItem};
+use crate::domain::event::DomainEvent;
+use crate::domain::pagination::{Cursor, KeysetValue, Page, page_size, push_keyset};
+use crate::domain::role::{Permission, UserRoles};
+use crate::domain::search::{SearchHit, SearchQuery, SearchResult, SearchRow};
+use crate::repositories::outbox_repository::OutboxRepository;
+use async_stream::try_stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, TryStreamExt};
 use sqlx::types::Json;
-use sqlx::{PgPool, Result};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Result};
 use uuid::Uuid;
 
+// QueryBuilder(동적 쿼리)용 컬럼 목록 (alias `r`)
+const REQUEST_COLUMNS: &str = r#"
+    r.id, r.title, r.requester_id, r.status, r.form_data, r.flow_process,
+    r.template_id, r.due_at, r.created_at, r.updated_at
+"#;
+
+// 목록 조회 범위
+pub enum ApprovalScope<'a> {
+    // 열람 권한이 있는 모든 문서 (push_visibility_filter)
+    Visible {
+        viewer_id: Uuid,
+        roles: &'a UserRoles,
+    },
+    // 개인 문서함
+    Inbox {
+        user_id: Uuid,
+        inbox: InboxBox,
+    },
+}
+
 pub struct ApprovalRepository {
     pool: PgPool,
 }
@@ -20,11 +52,73 @@ impl ApprovalRepository {
         form_data: serde_json::Value,
         flow_process: FlowProcess,
     ) -> Result<ApprovalRequest> {
+        self.insert(
+            title,
+            requester_id,
+            form_data,
+            flow_process,
+            None,
+            "pending",
+        )
+        .await
+    }
+
+    pub async fn create_from_template(
+        &self,
+        template_id: Uuid,
+        title: String,
+        requester_id: Uuid,
+        form_data: serde_json::Value,
+        flow_process: FlowProcess,
+    ) -> Result<ApprovalRequest> {
+        self.insert(
+            title,
+            requester_id,
+            form_data,
+            flow_process,
+            Some(template_id),
+            "pending",
+        )
+        .await
+    }
+
+    // 임시저장: 결재선에 노출되지 않으며 submit 후 결재가 시작됩니다.
+    pub async fn create_draft(
+        &self,
+        title: String,
+        requester_id: Uuid,
+        form_data: serde_json::Value,
+        flow_process: FlowProcess,
+        template_id: Option<Uuid>,
+    ) -> Result<ApprovalRequest> {
+        self.insert(
+            title,
+            requester_id,
+            form_data,
+            flow_process,
+            template_id,
+            "draft",
+        )
+        .await
+    }
+
+    // 생성 + CREATED 이력 + RequestCreated 이벤트 (한 트랜잭션)
+    async fn insert(
+        &self,
+        title: String,
+        requester_id: Uuid,
+        form_data: serde_json::Value,
+        flow_process: FlowProcess,
+        template_id: Option<Uuid>,
+        status: &str,
+    ) -> Result<ApprovalRequest> {
+        let mut tx = self.pool.begin().await?;
+
         let request = sqlx::query_as!(
             ApprovalRequest,
             r#"
-            INSERT INTO pxm_approval_requests (title, requester_id, form_data, flow_process)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO pxm_approval_requests (title, requester_id, form_data, flow_process, template_id, status)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING
                 id,
                 title,
@@ -32,17 +126,32 @@ impl ApprovalRepository {
                 status,
                 form_data as "form_data: Json<serde_json::Value>",
                 flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
                 created_at,
                 updated_at
             "#,
             title,
             requester_id,
             Json(form_data) as Json<serde_json::Value>,
-            Json(flow_process) as Json<FlowProcess>
+            Json(flow_process) as Json<FlowProcess>,
+            template_id,
+            status
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        insert_log(&mut tx, request.id, requester_id, "CREATED", None).await?;
+        OutboxRepository::append(
+            &mut tx,
+            requester_id,
+            &DomainEvent::RequestCreated {
+                approval: request.clone(),
+            },
         )
-        .fetch_one(&self.pool)
         .await?;
 
+        tx.commit().await?;
         Ok(request)
     }
 
@@ -57,6 +166,8 @@ impl ApprovalRepository {
                 status,
                 form_data as "form_data: Json<serde_json::Value>",
                 flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
                 created_at,
                 updated_at
             FROM pxm_approval_requests
@@ -70,8 +181,33 @@ impl ApprovalRepository {
         Ok(request)
     }
 
+    // 상태/결재선만 저장합니다. (이력/이벤트 없음, 결재 처리는 record_action)
     pub async fn update(&self, request: ApprovalRequest) -> Result<ApprovalRequest> {
-        let updated_request = sqlx::query_as!(
+        let mut conn = self.pool.acquire().await?;
+        update_request(&mut conn, request).await
+    }
+
+    // 결재 처리(승인/반려) 결과 저장 + 이력 + 도메인 이벤트 (한 트랜잭션)
+    // request는 FlowProcess::handle_action을 적용한 상태여야 합니다.
+    // 그 사이 다른 요청이 같은 단계를 먼저 처리했다면 저장하지 않고 None을 반환합니다.
+    pub async fn record_action(
+        &self,
+        request: ApprovalRequest,
+        actor_id: Uuid,
+        action: ApprovalAction,
+        reason: Option<String>,
+    ) -> Result<Option<ApprovalRequest>> {
+        // handle_action 적용 전의 단계 (다음 단계로 넘어갔으면 current_step - 1)
+        let current_step = request.flow_process.0.current_step;
+        let acted_step = if request.status == "pending" {
+            current_step - 1
+        } else {
+            current_step
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query_as!(
             ApprovalRequest,
             r#"
             UPDATE pxm_approval_requests
@@ -80,6 +216,8 @@ impl ApprovalRepository {
                 flow_process = $2,
                 updated_at = NOW()
             WHERE id = $3
+              AND status = 'pending'
+              AND (flow_process->>'current_step')::int = $4
             RETURNING
                 id,
                 title,
@@ -87,40 +225,363 @@ impl ApprovalRepository {
                 status,
                 form_data as "form_data: Json<serde_json::Value>",
                 flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
                 created_at,
                 updated_at
             "#,
             request.status,
             request.flow_process as Json<FlowProcess>,
-            request.id
+            request.id,
+            acted_step
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
         .await?;
+        let Some(updated) = updated else {
+            return Ok(None);
+        };
+        let action_type = match action {
+            ApprovalAction::Approve => "APPROVED",
+            ApprovalAction::Reject => "REJECTED",
+        };
+        insert_log(&mut tx, updated.id, actor_id, action_type, reason.clone()).await?;
 
-        Ok(updated_request)
+        let approval = updated.clone();
+        let event = match updated.status.as_str() {
+            "approved" => DomainEvent::RequestCompleted { approval },
+            "rejected" => DomainEvent::RequestRejected { approval, reason },
+            _ => DomainEvent::StepApproved { approval },
+        };
+        OutboxRepository::append(&mut tx, actor_id, &event).await?;
+
+        tx.commit().await?;
+        Ok(Some(updated))
+    }
+
+    // 내보내기용: cursor/limit 없이 조건에 맞는 모든 행을 한 건씩 스트리밍합니다.
+    pub fn stream_all<'a>(
+        &'a self,
+        scope: ApprovalScope<'a>,
+        query: &'a ApprovalListQuery,
+    ) -> impl Stream<Item = Result<ApprovalRequest>> + 'a {
+        try_stream! {
+            let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+                "SELECT {REQUEST_COLUMNS} FROM pxm_approval_requests r WHERE "
+            ));
+            push_scope(&mut qb, &scope);
+            push_list_filters(&mut qb, query);
+            qb.push(format!(
+                " ORDER BY r.{column} {order}, r.id {order}",
+                column = query.sort.key(),
+                order = query.order.sql()
+            ));
+
+            let mut rows = qb.build_query_as::<ApprovalRequest>().fetch(&self.pool);
+            while let Some(row) = rows.try_next().await? {
+                yield row;
+            }
+        }
     }
 
-    pub async fn find_all(&self) -> Result<Vec<ApprovalRequest>> {
-        let requests = sqlx::query_as!(
+    // 전문 검색: 열람 가능한 문서 중 검색어와 일치하는 문서를 관련도 순으로 조회합니다.
+    pub async fn search(
+        &self,
+        viewer_id: Uuid,
+        roles: &UserRoles,
+        query: &SearchQuery,
+        limit: i64,
+    ) -> Result<SearchResult> {
+        let mut count_qb: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM pxm_approval_requests r WHERE ");
+        push_visibility_filter(&mut count_qb, viewer_id, roles);
+        push_search_filter(&mut count_qb, query);
+        let total_count: i64 = count_qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT {REQUEST_COLUMNS}, r.search_text, \
+             (ts_rank(r.search_vector, to_tsquery('simple', "
+        ));
+        qb.push_bind(query.tsquery())
+            .push(")) + word_similarity(")
+            .push_bind(query.text())
+            .push(", r.search_text)) AS rank FROM pxm_approval_requests r WHERE ");
+        push_visibility_filter(&mut qb, viewer_id, roles);
+        push_search_filter(&mut qb, query);
+        qb.push(" ORDER BY rank DESC, r.created_at DESC, r.id DESC LIMIT ")
+            .push_bind(limit);
+
+        let rows = qb
+            .build_query_as::<SearchRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let items = rows
+            .into_iter()
+            .map(|row| SearchHit {
+                highlight: query.highlight(&row.search_text),
+                request: row.request,
+                rank: row.rank,
+            })
+            .collect();
+
+        Ok(SearchResult { items, total_count })
+    }
+
+    // 임시저장 문서를 상신합니다. (draft가 아니면 None)
+    // 상신은 요청자 본인만 하므로 이력/이벤트의 actor는 requester_id입니다.
+    pub async fn submit(&self, id: Uuid) -> Result<Option<ApprovalRequest>> {
+        let mut tx = self.pool.begin().await?;
+
+        let request = sqlx::query_as!(
             ApprovalRequest,
             r#"
-            SELECT
+            UPDATE pxm_approval_requests
+            SET status = 'pending', updated_at = NOW()
+            WHERE id = $1 AND status = 'draft'
+            RETURNING
                 id,
                 title,
                 requester_id,
                 status,
                 form_data as "form_data: Json<serde_json::Value>",
                 flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
                 created_at,
                 updated_at
-            FROM pxm_approval_requests
-            ORDER BY created_at DESC
-            "#
+            "#,
+            id
         )
-        .fetch_all(&self.pool)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(request) = &request {
+            insert_log(&mut tx, id, request.requester_id, "SUBMITTED", None).await?;
+            OutboxRepository::append(
+                &mut tx,
+                request.requester_id,
+                &DomainEvent::RequestSubmitted {
+                    approval: request.clone(),
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(request)
+    }
+
+    // 진행 중인 문서를 요청자가 회수합니다. (pending이 아니면 None)
+    pub async fn withdraw(&self, id: Uuid) -> Result<Option<ApprovalRequest>> {
+        let mut tx = self.pool.begin().await?;
+
+        let request = sqlx::query_as!(
+            ApprovalRequest,
+            r#"
+            UPDATE pxm_approval_requests
+            SET status = 'withdrawn', updated_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING
+                id,
+                title,
+                requester_id,
+                status,
+                form_data as "form_data: Json<serde_json::Value>",
+                flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
+                created_at,
+                updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(request) = &request {
+            insert_log(&mut tx, id, request.requester_id, "WITHDRAWN", None).await?;
+            OutboxRepository::append(
+                &mut tx,
+                request.requester_id,
+                &DomainEvent::RequestWithdrawn {
+                    approval: request.clone(),
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(request)
+    }
+
+    // 결재 기한 지정/해제 (임시저장 또는 진행 중인 문서만, 아니면 None)
+    pub async fn set_due_at(
+        &self,
+        id: Uuid,
+        due_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<ApprovalRequest>> {
+        let request = sqlx::query_as!(
+            ApprovalRequest,
+            r#"
+            UPDATE pxm_approval_requests
+            SET due_at = $2, updated_at = NOW()
+            WHERE id = $1 AND status IN ('draft', 'pending')
+            RETURNING
+                id,
+                title,
+                requester_id,
+                status,
+                form_data as "form_data: Json<serde_json::Value>",
+                flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
+                created_at,
+                updated_at
+            "#,
+            id,
+            due_at
+        )
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(requests)
+        Ok(request)
+    }
+
+    // 결재 목록 (keyset 페이지네이션)
+    // scope(열람 가능 문서 / 문서함)에 공통 필터를 더하고, limit + 1건으로 다음 페이지 여부를 판단합니다.
+    pub async fn find_page(
+        &self,
+        scope: ApprovalScope<'_>,
+        query: &ApprovalListQuery,
+        keyset: Option<(KeysetValue, Uuid)>,
+    ) -> Result<Page<ApprovalRequest>> {
+        let limit = page_size(query.limit);
+        let column = format!("r.{}", query.sort.key());
+
+        // 1. 전체 건수 (cursor 제외)
+        let mut count_qb: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM pxm_approval_requests r WHERE ");
+        push_scope(&mut count_qb, &scope);
+        push_list_filters(&mut count_qb, query);
+        let total_count: i64 = count_qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        // 2. 페이지 조회
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT {REQUEST_COLUMNS} FROM pxm_approval_requests r WHERE "
+        ));
+        push_scope(&mut qb, &scope);
+        push_list_filters(&mut qb, query);
+        if let Some((value, id)) = keyset {
+            qb.push(" AND ");
+            push_keyset(&mut qb, &column, "r.id", query.order, value, id);
+        }
+        qb.push(format!(
+            " ORDER BY {column} {order}, r.id {order} LIMIT ",
+            order = query.order.sql()
+        ))
+        .push_bind(limit + 1);
+
+        let rows = qb
+            .build_query_as::<ApprovalRequest>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Page::from_rows(rows, limit, total_count, |r| {
+            query.sort.cursor_of(r)
+        }))
+    }
+
+    // 모든 문서함의 건수를 한 번에 계산합니다.
+    pub async fn count_inbox(&self, user_id: Uuid) -> Result<InboxCounts> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        for (idx, inbox) in InboxBox::ALL.into_iter().enumerate() {
+            if idx > 0 {
+                qb.push(", ");
+            }
+            qb.push("COUNT(*) FILTER (WHERE ");
+            push_inbox_filter(&mut qb, inbox, user_id);
+            qb.push(format!(") AS {}", inbox_column(inbox)));
+        }
+        // 관여한 문서만 스캔 (requester 인덱스 + flow_process GIN 인덱스)
+        qb.push(" FROM pxm_approval_requests r WHERE r.requester_id = ")
+            .push_bind(user_id)
+            .push(" OR ");
+        push_participant_filter(&mut qb, user_id);
+
+        let counts = qb
+            .build_query_as::<InboxCounts>()
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(counts)
+    }
+
+    // [Daily Digest] 결재 대기함 문서 (오래 기다린 순, 최대 limit건) + 전체 건수
+    // 대기 시작 시각 = 현재 단계가 된 시점 (상신/생성 또는 직전 단계 승인 이력)
+    pub async fn find_digest_pending(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<(Vec<PendingDigestItem>, i64)> {
+        let mut count_qb: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM pxm_approval_requests r WHERE ");
+        push_inbox_filter(&mut count_qb, InboxBox::Pending, user_id);
+        let total: i64 = count_qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT r.id, r.title, ru.full_name AS requester_name,
+                r.flow_process->'steps'->((r.flow_process->>'current_step')::int - 1)->>'name' AS step_name,
+                COALESCE(
+                    (SELECT MAX(l.created_at) FROM approval_logs l
+                     WHERE l.approval_id = r.id AND l.action_type IN ('CREATED', 'SUBMITTED', 'APPROVED')),
+                    r.created_at,
+                    NOW()
+                ) AS waiting_since
+            FROM pxm_approval_requests r
+            LEFT JOIN users ru ON ru.id = r.requester_id
+            WHERE "#,
+        );
+        push_inbox_filter(&mut qb, InboxBox::Pending, user_id);
+        qb.push(" ORDER BY waiting_since, r.id LIMIT ")
+            .push_bind(limit);
+        let items = qb
+            .build_query_as::<PendingDigestItem>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok((items, total))
+    }
+
+    // [Daily Digest] 내가 올린 문서 중 [from, to) 사이에 다른 사람이 승인/반려한 이력
+    pub async fn find_status_changes(
+        &self,
+        requester_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StatusChangeItem>> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT r.id, r.title, r.status, l.action_type, au.full_name AS actor_name,
+                l.created_at AS acted_at
+            FROM approval_logs l
+            JOIN pxm_approval_requests r ON r.id = l.approval_id
+            LEFT JOIN users au ON au.id = l.actor_id
+            WHERE "#,
+        );
+        push_inbox_filter(&mut qb, InboxBox::Requested, requester_id);
+        qb.push(" AND l.action_type IN ('APPROVED', 'REJECTED') AND l.actor_id <> r.requester_id")
+            .push(" AND l.created_at >= ")
+            .push_bind(from)
+            .push(" AND l.created_at < ")
+            .push_bind(to)
+            .push(" ORDER BY l.created_at, l.id");
+        let changes = qb
+            .build_query_as::<StatusChangeItem>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(changes)
     }
 
     pub async fn add_log(
@@ -130,22 +591,57 @@ impl ApprovalRepository {
         action_type: String,
         content: Option<String>,
     ) -> Result<ApprovalLog> {
-        let log = sqlx::query_as!(
-            ApprovalLog,
+        let mut conn = self.pool.acquire().await?;
+        insert_log(&mut conn, approval_id, actor_id, &action_type, content).await
+    }
+
+    // 의견 작성 + CommentAdded 이벤트 (한 트랜잭션, 문서가 없으면 None)
+    pub async fn add_comment(
+        &self,
+        approval_id: Uuid,
+        actor_id: Uuid,
+        content: String,
+    ) -> Result<Option<ApprovalLog>> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(approval) = sqlx::query_as!(
+            ApprovalRequest,
             r#"
-            INSERT INTO approval_logs (approval_id, actor_id, action_type, content)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
+            SELECT
+                id,
+                title,
+                requester_id,
+                status,
+                form_data as "form_data: Json<serde_json::Value>",
+                flow_process as "flow_process: Json<FlowProcess>",
+                template_id,
+                due_at,
+                created_at,
+                updated_at
+            FROM pxm_approval_requests
+            WHERE id = $1
             "#,
-            approval_id,
+            approval_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let comment = insert_log(&mut tx, approval_id, actor_id, "COMMENT", Some(content)).await?;
+        OutboxRepository::append(
+            &mut tx,
             actor_id,
-            action_type,
-            content
+            &DomainEvent::CommentAdded {
+                approval,
+                comment: comment.clone(),
+            },
         )
-        .fetch_one(&self.pool)
         .await?;
 
-        Ok(log)
+        tx.commit().await?;
+        Ok(Some(comment))
     }
 
     pub async fn get_logs(&self, approval_id: Uuid) -> Result<Vec<ApprovalLog>> {
@@ -163,4 +659,287 @@ impl ApprovalRepository {
 
         Ok(logs)
     }
+
+    // 결재 이력 (작성 순, keyset 페이지네이션)
+    pub async fn get_logs_page(
+        &self,
+        approval_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Page<ApprovalLog>> {
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM approval_logs WHERE approval_id = $1"#,
+            approval_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let (after_at, after_id) = after.unzip();
+        let rows = sqlx::query_as!(
+            ApprovalLog,
+            r#"
+            SELECT * FROM approval_logs
+            WHERE approval_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
+            "#,
+            approval_id,
+            after_at,
+            after_id,
+            limit + 1
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Page::from_rows(rows, limit, total_count, |log| {
+            Cursor::at("created_at", log.created_at, log.id)
+        }))
+    }
+}
+
+// 트랜잭션/단일 연결에서 공통으로 쓰는 쿼리
+async fn update_request(
+    conn: &mut PgConnection,
+    request: ApprovalRequest,
+) -> Result<ApprovalRequest> {
+    sqlx::query_as!(
+        ApprovalRequest,
+        r#"
+        UPDATE pxm_approval_requests
+        SET
+            status = $1,
+            flow_process = $2,
+            updated_at = NOW()
+        WHERE id = $3
+        RETURNING
+            id,
+            title,
+            requester_id,
+            status,
+            form_data as "form_data: Json<serde_json::Value>",
+            flow_process as "flow_process: Json<FlowProcess>",
+            template_id,
+            due_at,
+            created_at,
+            updated_at
+        "#,
+        request.status,
+        request.flow_process as Json<FlowProcess>,
+        request.id
+    )
+    .fetch_one(conn)
+    .await
+}
+
+async fn insert_log(
+    conn: &mut PgConnection,
+    approval_id: Uuid,
+    actor_id: Uuid,
+    action_type: &str,
+    content: Option<String>,
+) -> Result<ApprovalLog> {
+    sqlx::query_as!(
+        ApprovalLog,
+        r#"
+        INSERT INTO approval_logs (approval_id, actor_id, action_type, content)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        approval_id,
+        actor_id,
+        action_type,
+        content
+    )
+    .fetch_one(conn)
+    .await
+}
+
+fn push_scope(qb: &mut QueryBuilder<'_, Postgres>, scope: &ApprovalScope<'_>) {
+    match scope {
+        ApprovalScope::Visible { viewer_id, roles } => {
+            push_visibility_filter(qb, *viewer_id, roles)
+        }
+        ApprovalScope::Inbox { user_id, inbox } => push_inbox_filter(qb, *inbox, *user_id),
+    }
+}
+
+// 열람 권한 WHERE 조건 (pxm_approval_requests의 alias는 `r`)
+// services::access_policy의 can_view와 같은 규칙을 표현해야 합니다.
+// 호출 측에서 " AND " 등 앞 연결어를 붙인 뒤 호출합니다.
+fn push_visibility_filter(qb: &mut QueryBuilder<'_, Postgres>, viewer_id: Uuid, roles: &UserRoles) {
+    // 임시저장 문서는 요청자 본인만
+    qb.push("(r.status <> 'draft' OR r.requester_id = ")
+        .push_bind(viewer_id)
+        .push(") AND ");
+    if roles.has(Permission::ApprovalReadAll) {
+        qb.push("TRUE");
+        return;
+    }
+
+    // flow_process 전체에 대한 @> 조건이어야 GIN 인덱스(idx_pxm_requests_flow_process)를 탑니다.
+    qb.push("(r.requester_id = ")
+        .push_bind(viewer_id)
+        .push(" OR r.flow_process @> jsonb_build_object('steps', jsonb_build_array(jsonb_build_object('approver_id', ")
+        .push_bind(viewer_id.to_string())
+        .push("::text)))")
+        .push(" OR r.flow_process @> jsonb_build_object('references', jsonb_build_array(")
+        .push_bind(viewer_id.to_string())
+        .push("::text))")
+        // viewer가 관리하는 부서와 그 하위 부서에 속한 요청자
+        .push(
+            r#" OR EXISTS (
+                SELECT 1 FROM users ru
+                WHERE ru.id = r.requester_id
+                  AND ru.department_id IN (
+                    WITH RECURSIVE managed AS (
+                        SELECT d.id FROM departments d WHERE d.manager_id = "#,
+        )
+        .push_bind(viewer_id)
+        .push(
+            r#"
+                        UNION
+                        SELECT c.id FROM departments c JOIN managed m ON c.parent_id = m.id
+                    )
+                    SELECT id FROM managed
+                  )
+            ))"#,
+        );
+}
+
+// 목록 공통 필터 (scope 조건 뒤에 AND로 연결)
+fn push_list_filters(qb: &mut QueryBuilder<'_, Postgres>, query: &ApprovalListQuery) {
+    if let Some(status) = &query.status {
+        qb.push(" AND r.status = ").push_bind(status.clone());
+    }
+    if let Some(requester_id) = query.requester_id {
+        qb.push(" AND r.requester_id = ").push_bind(requester_id);
+    }
+    if let Some(approver_id) = query.approver_id {
+        qb.push(" AND ");
+        push_step_contains(qb, approver_id, None);
+    }
+    if let Some(template_id) = query.template_id {
+        qb.push(" AND r.template_id = ").push_bind(template_id);
+    }
+    if let Some(department_id) = query.department_id {
+        // 요청자가 해당 부서 또는 하위 부서 소속
+        qb.push(" AND ");
+        push_requester_in_subtree(qb, "d.id", department_id);
+    }
+    if let Some(created_from) = query.created_from {
+        qb.push(" AND r.created_at >= ").push_bind(created_from);
+    }
+    if let Some(created_to) = query.created_to {
+        qb.push(" AND r.created_at < ").push_bind(created_to);
+    }
+}
+
+// 요청자가 root 조건(예: "d.id" = 부서, "d.manager_id" = 부서장)에 맞는 부서 또는 그 하위 부서 소속
+pub(crate) fn push_requester_in_subtree(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    root_column: &str,
+    value: Uuid,
+) {
+    qb.push(format!(
+        r#"EXISTS (
+            SELECT 1 FROM users ru
+            WHERE ru.id = r.requester_id
+              AND ru.department_id IN (
+                WITH RECURSIVE subtree AS (
+                    SELECT d.id FROM departments d WHERE {root_column} = "#
+    ))
+    .push_bind(value)
+    .push(
+        r#"
+                    UNION
+                    SELECT c.id FROM departments c JOIN subtree s ON c.parent_id = s.id
+                )
+                SELECT id FROM subtree
+              )
+        )"#,
+    );
+}
+
+// 단어 매칭(tsvector) 또는 모든 검색어의 부분 문자열 매칭(pg_trgm)
+fn push_search_filter(qb: &mut QueryBuilder<'_, Postgres>, query: &SearchQuery) {
+    qb.push(" AND (r.search_vector @@ to_tsquery('simple', ")
+        .push_bind(query.tsquery())
+        .push(") OR (");
+    for (idx, pattern) in query.like_patterns().into_iter().enumerate() {
+        if idx > 0 {
+            qb.push(" AND ");
+        }
+        qb.push("r.search_text ILIKE ").push_bind(pattern);
+    }
+    qb.push("))");
+}
+
+fn inbox_column(inbox: InboxBox) -> &'static str {
+    match inbox {
+        InboxBox::Pending => "pending",
+        InboxBox::Requested => "requested",
+        InboxBox::Processed => "processed",
+        InboxBox::Completed => "completed",
+        InboxBox::Drafts => "drafts",
+    }
+}
+
+// 결재선 step 조건 (flow_process 전체에 대한 @> 이므로 GIN 인덱스 사용)
+fn push_step_contains(qb: &mut QueryBuilder<'_, Postgres>, user_id: Uuid, status: Option<&str>) {
+    qb.push("r.flow_process @> jsonb_build_object('steps', jsonb_build_array(jsonb_build_object('approver_id', ")
+        .push_bind(user_id.to_string())
+        .push("::text");
+    if let Some(status) = status {
+        qb.push(", 'status', ")
+            .push_bind(status.to_string())
+            .push("::text");
+    }
+    qb.push(")))");
+}
+
+// 결재자 또는 참조자로 관여한 문서
+fn push_participant_filter(qb: &mut QueryBuilder<'_, Postgres>, user_id: Uuid) {
+    qb.push("(");
+    push_step_contains(qb, user_id, None);
+    qb.push(" OR r.flow_process @> jsonb_build_object('references', jsonb_build_array(")
+        .push_bind(user_id.to_string())
+        .push("::text)))");
+}
+
+fn push_inbox_filter(qb: &mut QueryBuilder<'_, Postgres>, inbox: InboxBox, user_id: Uuid) {
+    match inbox {
+        InboxBox::Pending => {
+            // GIN 인덱스로 후보를 좁힌 뒤, 현재 단계의 결재자가 본인인지 확인
+            qb.push("(r.status = 'pending' AND ");
+            push_step_contains(qb, user_id, Some("pending"));
+            qb.push(" AND r.flow_process->'steps'->((r.flow_process->>'current_step')::int - 1)->>'approver_id' = ")
+                .push_bind(user_id.to_string())
+                .push(")");
+        }
+        InboxBox::Requested => {
+            qb.push("(r.requester_id = ")
+                .push_bind(user_id)
+                .push(" AND r.status <> 'draft')");
+        }
+        InboxBox::Processed => {
+            qb.push("(");
+            push_step_contains(qb, user_id, Some("approved"));
+            qb.push(" OR ");
+            push_step_contains(qb, user_id, Some("rejected"));
+            qb.push(")");
+        }
+        InboxBox::Completed => {
+            qb.push("(r.status IN ('approved', 'rejected') AND (r.requester_id = ")
+                .push_bind(user_id)
+                .push(" OR ");
+            push_participant_filter(qb, user_id);
+            qb.push("))");
+        }
+        InboxBox::Drafts => {
+            qb.push("(r.requester_id = ")
+                .push_bind(user_id)
+                .push(" AND r.status = 'draft')");
+        }
+    }
 }
@@ -0,0 +1,103 @@
+use crate::domain::attachment::Attachment;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct AttachmentRepository {
+    pool: PgPool,
+}
+
+pub struct NewAttachment {
+    pub id: Uuid,
+    pub approval_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub storage_key: String,
+    pub uploaded_by: Uuid,
+}
+
+impl AttachmentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 결재 요청이 임시저장(draft) 상태일 때만 저장합니다. (상신 후면 None)
+    pub async fn create(&self, new: NewAttachment) -> Result<Option<Attachment>> {
+        let attachment = sqlx::query_as!(
+            Attachment,
+            r#"
+            INSERT INTO approval_attachments
+                (id, approval_id, file_name, content_type, size_bytes, sha256, storage_key, uploaded_by)
+            SELECT $1, r.id, $3, $4, $5, $6, $7, $8
+            FROM pxm_approval_requests r
+            WHERE r.id = $2 AND r.status = 'draft'
+            RETURNING *
+            "#,
+            new.id,
+            new.approval_id,
+            new.file_name,
+            new.content_type,
+            new.size_bytes,
+            new.sha256,
+            new.storage_key,
+            new.uploaded_by
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(attachment)
+    }
+
+    pub async fn find_by_approval(&self, approval_id: Uuid) -> Result<Vec<Attachment>> {
+        let attachments = sqlx::query_as!(
+            Attachment,
+            r#"
+            SELECT * FROM approval_attachments
+            WHERE approval_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            approval_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attachments)
+    }
+
+    pub async fn find_by_id(&self, approval_id: Uuid, id: Uuid) -> Result<Option<Attachment>> {
+        let attachment = sqlx::query_as!(
+            Attachment,
+            r#"
+            SELECT * FROM approval_attachments
+            WHERE approval_id = $1 AND id = $2
+            "#,
+            approval_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(attachment)
+    }
+
+    // 임시저장 상태에서만 삭제합니다. (삭제된 행 반환, 없거나 잠겨 있으면 None)
+    pub async fn delete(&self, approval_id: Uuid, id: Uuid) -> Result<Option<Attachment>> {
+        let attachment = sqlx::query_as!(
+            Attachment,
+            r#"
+            DELETE FROM approval_attachments a
+            USING pxm_approval_requests r
+            WHERE a.approval_id = $1 AND a.id = $2
+              AND r.id = a.approval_id AND r.status = 'draft'
+            RETURNING a.*
+            "#,
+            approval_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(attachment)
+    }
+}
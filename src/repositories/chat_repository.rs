@@ -0,0 +1,287 @@
+use crate::domain::chat::ChatWebhook;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+// 저장할 메시지 1건 (webhook 하나에 한 번 전송)
+#[derive(Debug, Clone)]
+pub struct NewChatDelivery {
+    pub webhook_id: Uuid,
+    pub event_id: Uuid,
+    pub recipient_id: Uuid,
+    pub notification_type: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct DueChatDelivery {
+    pub id: Uuid,
+    pub attempts: i32,
+    pub url: String,
+    pub payload: Json<serde_json::Value>,
+}
+
+pub struct ChatRepository {
+    pool: PgPool,
+}
+
+impl ChatRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // owner_id = None이면 채널 webhook
+    pub async fn create_webhook(
+        &self,
+        owner_id: Option<Uuid>,
+        name: String,
+        url: String,
+        locale: String,
+        notification_types: Vec<String>,
+        created_by: Uuid,
+    ) -> Result<ChatWebhook> {
+        let webhook = sqlx::query_as!(
+            ChatWebhook,
+            r#"
+            INSERT INTO chat_webhooks (owner_id, name, url, locale, notification_types, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, owner_id, name, url, locale, notification_types, is_active, created_by, created_at
+            "#,
+            owner_id,
+            name,
+            url,
+            locale,
+            &notification_types,
+            created_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    // owner_id = None이면 채널 webhook 목록
+    pub async fn find_webhooks(&self, owner_id: Option<Uuid>) -> Result<Vec<ChatWebhook>> {
+        let webhooks = sqlx::query_as!(
+            ChatWebhook,
+            r#"
+            SELECT id, owner_id, name, url, locale, notification_types, is_active, created_by, created_at
+            FROM chat_webhooks
+            WHERE owner_id IS NOT DISTINCT FROM $1
+            ORDER BY created_at, id
+            "#,
+            owner_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    // 본인(또는 채널) webhook만 삭제됩니다.
+    pub async fn delete_webhook(&self, id: Uuid, owner_id: Option<Uuid>) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM chat_webhooks WHERE id = $1 AND owner_id IS NOT DISTINCT FROM $2",
+            id,
+            owner_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 알림 받을 사용자들의 개인 webhook + 모든 채널 webhook (활성만)
+    pub async fn find_targets(&self, user_ids: &[Uuid]) -> Result<Vec<ChatWebhook>> {
+        let webhooks = sqlx::query_as!(
+            ChatWebhook,
+            r#"
+            SELECT id, owner_id, name, url, locale, notification_types, is_active, created_by, created_at
+            FROM chat_webhooks
+            WHERE is_active AND (owner_id IS NULL OR owner_id = ANY($1))
+            "#,
+            user_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    // 같은 이벤트를 다시 받아도 메시지는 한 번만 저장됩니다.
+    pub async fn enqueue(&self, deliveries: &[NewChatDelivery]) -> Result<u64> {
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+        let webhook_ids: Vec<Uuid> = deliveries.iter().map(|d| d.webhook_id).collect();
+        let event_ids: Vec<Uuid> = deliveries.iter().map(|d| d.event_id).collect();
+        let recipient_ids: Vec<Uuid> = deliveries.iter().map(|d| d.recipient_id).collect();
+        let types: Vec<String> = deliveries
+            .iter()
+            .map(|d| d.notification_type.clone())
+            .collect();
+        let payloads: Vec<serde_json::Value> =
+            deliveries.iter().map(|d| d.payload.clone()).collect();
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO chat_deliveries (webhook_id, event_id, recipient_id, notification_type, payload)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::text[], $5::jsonb[])
+            ON CONFLICT DO NOTHING
+            "#,
+            &webhook_ids,
+            &event_ids,
+            &recipient_ids,
+            &types,
+            &payloads
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // 전송할 메시지를 가져가며 lease_secs 동안 다른 worker가 가져가지 못하게 합니다.
+    pub async fn claim_due(&self, limit: i64, lease_secs: f64) -> Result<Vec<DueChatDelivery>> {
+        let deliveries = sqlx::query_as!(
+            DueChatDelivery,
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM chat_deliveries d
+                JOIN chat_webhooks w ON w.id = d.webhook_id
+                WHERE d.status = 'pending' AND d.next_attempt_at <= NOW() AND w.is_active
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE chat_deliveries d
+            SET attempts = d.attempts + 1,
+                next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, chat_webhooks w
+            WHERE d.id = due.id AND w.id = d.webhook_id
+            RETURNING d.id, d.attempts, w.url, d.payload as "payload: Json<serde_json::Value>"
+            "#,
+            limit,
+            lease_secs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn mark_sent(&self, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE chat_deliveries SET status = 'sent', sent_at = NOW(), last_error = NULL WHERE id = $1",
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE chat_deliveries
+            SET status = $2, next_attempt_at = $3, last_error = $4
+            WHERE id = $1
+            "#,
+            id,
+            status,
+            next_attempt_at,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 다른 사용자가 이미 연결한 채팅 계정이면 unique 위반
+    // 채팅 서버가 검증한 ID만 넣어야 합니다. (link code 확인 후)
+    pub async fn set_account(&self, user_id: Uuid, chat_user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO chat_accounts (user_id, chat_user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET chat_user_id = EXCLUDED.chat_user_id, updated_at = NOW()
+            "#,
+            user_id,
+            chat_user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_account(&self, user_id: Uuid) -> Result<Option<String>> {
+        let chat_user_id = sqlx::query_scalar!(
+            "SELECT chat_user_id FROM chat_accounts WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(chat_user_id)
+    }
+
+    pub async fn delete_account(&self, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!("DELETE FROM chat_accounts WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 사용자당 하나: 새 코드를 발급하면 이전 코드는 사라집니다.
+    pub async fn create_link_code(
+        &self,
+        user_id: Uuid,
+        code_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO chat_link_codes (user_id, code_hash, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+            SET code_hash = EXCLUDED.code_hash, expires_at = EXCLUDED.expires_at, created_at = NOW()
+            "#,
+            user_id,
+            code_hash,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 유효한 코드를 한 번만 사용 (삭제하면서 사용자 ID 반환)
+    pub async fn consume_link_code(&self, code_hash: &str) -> Result<Option<Uuid>> {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            DELETE FROM chat_link_codes
+            WHERE code_hash = $1 AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            code_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user_id)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::department::Department;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct DepartmentRepository {
+    pool: PgPool,
+}
+
+impl DepartmentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Department>> {
+        let department = sqlx::query_as!(
+            Department,
+            r#"
+            SELECT * FROM departments WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(department)
+    }
+
+    pub async fn find_by_code(&self, code: &str) -> Result<Option<Department>> {
+        let department = sqlx::query_as!(
+            Department,
+            r#"
+            SELECT * FROM departments WHERE code = $1
+            "#,
+            code
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(department)
+    }
+
+    // 한 개 이상의 부서에서 부서장인지
+    pub async fn is_manager(&self, user_id: Uuid) -> Result<bool> {
+        let manages = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM departments WHERE manager_id = $1) as "exists!""#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(manages)
+    }
+}
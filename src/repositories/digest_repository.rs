@@ -0,0 +1,121 @@
+use crate::domain::digest::DigestSettings;
+use chrono::{NaiveDate, NaiveTime};
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+// 이번 회차에 요약 메일을 보낼 사용자
+#[derive(Debug, Clone)]
+pub struct DueDigest {
+    pub user_id: Uuid,
+    pub timezone: String,
+    // 사용자 타임존 기준 오늘 날짜 (= 새 last_sent_on)
+    pub sent_on: NaiveDate,
+    // 전송 실패 시 되돌릴 값
+    pub previous_sent_on: Option<NaiveDate>,
+}
+
+pub struct DigestRepository {
+    pool: PgPool,
+}
+
+impl DigestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_settings(&self, user_id: Uuid) -> Result<Option<DigestSettings>> {
+        let settings = sqlx::query_as!(
+            DigestSettings,
+            r#"
+            SELECT user_id, enabled, send_time, timezone, last_sent_on
+            FROM digest_settings
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    pub async fn upsert_settings(
+        &self,
+        user_id: Uuid,
+        enabled: bool,
+        send_time: NaiveTime,
+        timezone: Option<&str>,
+    ) -> Result<DigestSettings> {
+        let settings = sqlx::query_as!(
+            DigestSettings,
+            r#"
+            INSERT INTO digest_settings (user_id, enabled, send_time, timezone)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE
+            SET enabled = EXCLUDED.enabled,
+                send_time = EXCLUDED.send_time,
+                timezone = EXCLUDED.timezone,
+                updated_at = NOW()
+            RETURNING user_id, enabled, send_time, timezone, last_sent_on
+            "#,
+            user_id,
+            enabled,
+            send_time,
+            timezone
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    // 사용자 타임존으로 send_time이 지났고 오늘 아직 보내지 않은 사용자를 가져가며
+    // last_sent_on을 오늘로 표시합니다. (여러 인스턴스가 동시에 돌아도 하루 한 번)
+    // timezone이 없는 사용자는 default_timezone(조직 타임존)을 따릅니다.
+    pub async fn claim_due(&self, default_timezone: &str, limit: i64) -> Result<Vec<DueDigest>> {
+        let due = sqlx::query_as!(
+            DueDigest,
+            r#"
+            WITH due AS (
+                SELECT user_id, COALESCE(timezone, $1) AS tz, last_sent_on
+                FROM digest_settings
+                WHERE enabled
+                  AND (NOW() AT TIME ZONE COALESCE(timezone, $1))::time >= send_time
+                  AND (last_sent_on IS NULL
+                       OR last_sent_on < (NOW() AT TIME ZONE COALESCE(timezone, $1))::date)
+                ORDER BY user_id
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE digest_settings s
+            SET last_sent_on = (NOW() AT TIME ZONE due.tz)::date
+            FROM due
+            WHERE s.user_id = due.user_id
+            RETURNING
+                s.user_id,
+                due.tz as "timezone!",
+                s.last_sent_on as "sent_on!",
+                due.last_sent_on as previous_sent_on
+            "#,
+            default_timezone,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(due)
+    }
+
+    // 전송 실패 -> 다음 회차에 다시 시도하도록 되돌림
+    pub async fn release(&self, user_id: Uuid, previous_sent_on: Option<NaiveDate>) -> Result<()> {
+        sqlx::query!(
+            "UPDATE digest_settings SET last_sent_on = $2 WHERE user_id = $1",
+            user_id,
+            previous_sent_on
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
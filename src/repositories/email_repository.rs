@@ -0,0 +1,201 @@
+use crate::domain::approval::FlowProcess;
+use crate::domain::email::{EmailTemplate, UpdateEmailTemplateDto};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+// 전송할 메일 1건 (렌더링에 필요한 값 포함)
+#[derive(Debug, Clone)]
+pub struct DueEmail {
+    pub id: Uuid,
+    pub attempts: i32,
+    pub notification_type: String,
+    pub approval_id: Option<Uuid>,
+    pub title: String,
+    pub data: Json<serde_json::Value>,
+    pub recipient_email: String,
+    pub recipient_name: String,
+    pub locale: String,
+    pub requester_name: Option<String>,
+    pub flow_process: Option<Json<FlowProcess>>,
+}
+
+pub struct EmailRepository {
+    pool: PgPool,
+}
+
+impl EmailRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_templates(&self) -> Result<Vec<EmailTemplate>> {
+        let templates = sqlx::query_as!(
+            EmailTemplate,
+            r#"
+            SELECT notification_type, locale, subject, body_text, body_html, updated_by, updated_at
+            FROM email_templates
+            ORDER BY notification_type, locale
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    pub async fn find_template(
+        &self,
+        notification_type: &str,
+        locale: &str,
+    ) -> Result<Option<EmailTemplate>> {
+        let template = sqlx::query_as!(
+            EmailTemplate,
+            r#"
+            SELECT notification_type, locale, subject, body_text, body_html, updated_by, updated_at
+            FROM email_templates
+            WHERE notification_type = $1 AND locale = $2
+            "#,
+            notification_type,
+            locale
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    // 유형/언어 조합이 처음이면 새로 만듭니다.
+    pub async fn upsert_template(
+        &self,
+        notification_type: &str,
+        locale: &str,
+        dto: UpdateEmailTemplateDto,
+        updated_by: Uuid,
+    ) -> Result<EmailTemplate> {
+        let template = sqlx::query_as!(
+            EmailTemplate,
+            r#"
+            INSERT INTO email_templates
+                (notification_type, locale, subject, body_text, body_html, updated_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (notification_type, locale) DO UPDATE
+            SET subject = EXCLUDED.subject,
+                body_text = EXCLUDED.body_text,
+                body_html = EXCLUDED.body_html,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            RETURNING notification_type, locale, subject, body_text, body_html, updated_by, updated_at
+            "#,
+            notification_type,
+            locale,
+            dto.subject,
+            dto.body_text,
+            dto.body_html,
+            updated_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    pub async fn set_user_locale(&self, user_id: Uuid, locale: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET locale = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            locale
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 보낼 차례인 메일을 가져오면서 attempts를 올리고 lease 동안 다른 worker가 가져가지 않게 합니다.
+    pub async fn claim_due(&self, limit: i64, lease_secs: f64) -> Result<Vec<DueEmail>> {
+        let emails = sqlx::query_as!(
+            DueEmail,
+            r#"
+            WITH due AS (
+                SELECT id FROM email_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            ), claimed AS (
+                UPDATE email_deliveries d
+                SET attempts = d.attempts + 1,
+                    next_attempt_at = NOW() + make_interval(secs => $2)
+                FROM due
+                WHERE d.id = due.id
+                RETURNING d.id, d.attempts, d.notification_id
+            )
+            SELECT
+                c.id as "id!",
+                c.attempts as "attempts!",
+                n.notification_type,
+                n.approval_id,
+                n.title,
+                n.data as "data: Json<serde_json::Value>",
+                u.email as recipient_email,
+                u.full_name as recipient_name,
+                u.locale,
+                ru.full_name as "requester_name?",
+                r.flow_process as "flow_process?: Json<FlowProcess>"
+            FROM claimed c
+            JOIN notifications n ON n.id = c.notification_id
+            JOIN users u ON u.id = n.user_id
+            LEFT JOIN pxm_approval_requests r ON r.id = n.approval_id
+            LEFT JOIN users ru ON ru.id = r.requester_id
+            "#,
+            limit,
+            lease_secs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(emails)
+    }
+
+    pub async fn mark_sent(&self, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE email_deliveries
+            SET status = 'sent', sent_at = NOW(), last_error = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // status: "pending"(next_attempt_at에 재시도) | "dead"
+    pub async fn mark_failed(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE email_deliveries
+            SET status = $2, next_attempt_at = $3, last_error = $4
+            WHERE id = $1
+            "#,
+            id,
+            status,
+            next_attempt_at,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
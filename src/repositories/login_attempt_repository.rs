@@ -0,0 +1,137 @@
+use crate::domain::login::ThrottleState;
+use sqlx::{PgPool, Result};
+use std::time::Duration;
+use uuid::Uuid;
+
+// 로그인 이력 한 건 (login_attempts)
+pub struct NewLoginAttempt<'a> {
+    pub user_id: Option<Uuid>,
+    pub email: &'a str,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub success: bool,
+    pub failure_reason: Option<&'a str>,
+}
+
+pub struct LoginAttemptRepository {
+    pool: PgPool,
+}
+
+impl LoginAttemptRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, attempt: &NewLoginAttempt<'_>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO login_attempts (user_id, email, ip_address, user_agent, success, failure_reason)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            attempt.user_id,
+            attempt.email,
+            attempt.ip_address,
+            attempt.user_agent,
+            attempt.success,
+            attempt.failure_reason
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 비밀번호를 확인하기 전에 시도 1회를 먼저 셉니다. (동시에 여러 번 시도해도 한도를 넘지 못하도록)
+    // 잠겨 있으면 세지 않고 None, 아니면 이번 시도를 포함한 실패 횟수를 돌려줍니다.
+    // max_failures번째 시도에서 lockout 동안 잠급니다. (성공하면 clear / release로 되돌림)
+    // 잠금이 풀린 뒤나 window가 지난 뒤에는 1부터 다시 셉니다.
+    // ($3: window, $4: max_failures, $5: lockout)
+    pub async fn reserve(
+        &self,
+        scope: &str,
+        key: &str,
+        window: Duration,
+        max_failures: i32,
+        lockout: Duration,
+    ) -> Result<Option<ThrottleState>> {
+        sqlx::query!(
+            r#"
+            INSERT INTO login_throttles (scope, key, failures, last_failure_at)
+            VALUES ($1, $2, 0, NOW())
+            ON CONFLICT (scope, key) DO NOTHING
+            "#,
+            scope,
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 행 잠금 후 WHERE를 다시 확인하므로 잠금 여부 확인과 증가가 한 번에 일어납니다.
+        let row = sqlx::query!(
+            r#"
+            UPDATE login_throttles
+            SET failures = CASE
+                    WHEN locked_until IS NULL AND last_failure_at > NOW() - make_interval(secs => $3)
+                    THEN failures + 1
+                    ELSE 1
+                END,
+                locked_until = CASE
+                    WHEN (CASE
+                            WHEN locked_until IS NULL AND last_failure_at > NOW() - make_interval(secs => $3)
+                            THEN failures + 1
+                            ELSE 1
+                          END) >= $4
+                    THEN NOW() + make_interval(secs => $5)
+                END,
+                last_failure_at = NOW()
+            WHERE scope = $1 AND key = $2 AND (locked_until IS NULL OR locked_until <= NOW())
+            RETURNING failures, locked_until
+            "#,
+            scope,
+            key,
+            window.as_secs_f64(),
+            max_failures,
+            lockout.as_secs_f64()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| ThrottleState {
+            failures: row.failures,
+            locked_until: row.locked_until,
+        }))
+    }
+
+    // reserve로 센 시도 1회를 되돌립니다. (실패가 아니었던 시도)
+    // 그 시도로 걸린 잠금은 횟수가 한도 아래로 내려가면 풀립니다.
+    pub async fn release(&self, scope: &str, key: &str, max_failures: i32) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE login_throttles
+            SET failures = GREATEST(failures - 1, 0),
+                locked_until = CASE WHEN failures - 1 < $3 THEN NULL ELSE locked_until END
+            WHERE scope = $1 AND key = $2
+            "#,
+            scope,
+            key,
+            max_failures
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 로그인 성공, 관리자 잠금 해제
+    pub async fn clear(&self, scope: &str, key: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM login_throttles WHERE scope = $1 AND key = $2",
+            scope,
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
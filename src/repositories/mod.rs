@@ -1,3 +1,17 @@
 pub mod approval_repository;
+pub mod attachment_repository;
+pub mod chat_repository;
+pub mod department_repository;
+pub mod digest_repository;
+pub mod email_repository;
+pub mod login_attempt_repository;
+pub mod notification_repository;
+pub mod outbox_repository;
+pub mod password_reset_repository;
+pub mod realtime_repository;
+pub mod role_repository;
+pub mod session_repository;
+pub mod stats_repository;
 pub mod template_repository;
 pub mod user_repository;
+pub mod webhook_repository;
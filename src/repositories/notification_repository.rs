@@ -0,0 +1,243 @@
+use crate::domain::notification::{NewNotification, Notification, NotificationType};
+use crate::domain::pagination::{Cursor, Page};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+// 기한이 다가온 진행 중 문서와 현재 결재자
+#[derive(Debug, Clone)]
+pub struct DueSoon {
+    pub approval_id: Uuid,
+    pub title: String,
+    pub due_at: DateTime<Utc>,
+    pub current_step: i32,
+    pub approver_id: Uuid,
+}
+
+pub struct NotificationRepository {
+    pool: PgPool,
+}
+
+impl NotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 알림 저장 (저장된 건수 반환)
+    // - 수신자가 해당 유형을 꺼 두었으면 건너뜁니다.
+    // - (user_id, notification_type, dedupe_key)가 이미 있으면 건너뜁니다. (재처리 시 중복 방지)
+    // - 등록된 사용자에게는 같은 트랜잭션에서 메일 발송(email_deliveries)을 예약합니다.
+    pub async fn insert_many(&self, notifications: &[NewNotification]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+        for n in notifications {
+            let id = sqlx::query_scalar!(
+                r#"
+                INSERT INTO notifications
+                    (user_id, notification_type, approval_id, actor_id, title, data, dedupe_key)
+                SELECT $1, $2::text, $3, $4, $5, $6, $7
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM notification_preferences p
+                    WHERE p.user_id = $1 AND p.notification_type = $2::text AND NOT p.enabled
+                )
+                ON CONFLICT (user_id, notification_type, dedupe_key) DO NOTHING
+                RETURNING id
+                "#,
+                n.user_id,
+                n.notification_type.as_str(),
+                n.approval_id,
+                n.actor_id,
+                n.title,
+                Json(&n.data) as _,
+                n.dedupe_key
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let Some(id) = id else {
+                continue;
+            };
+            inserted += 1;
+            sqlx::query!(
+                r#"
+                INSERT INTO email_deliveries (notification_id)
+                SELECT $1 WHERE EXISTS (SELECT 1 FROM users WHERE id = $2)
+                "#,
+                id,
+                n.user_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    // 최신순 목록 (keyset 페이지네이션)
+    pub async fn find_page(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+        before: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Page<Notification>> {
+        let total_count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM notifications
+            WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+            "#,
+            user_id,
+            unread_only
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let (before_at, before_id) = before.unzip();
+        let rows = sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT
+                id, user_id, notification_type, approval_id, actor_id, title,
+                data as "data: Json<serde_json::Value>", read_at, created_at
+            FROM notifications
+            WHERE user_id = $1
+              AND (NOT $2 OR read_at IS NULL)
+              AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
+            "#,
+            user_id,
+            unread_only,
+            before_at,
+            before_id,
+            limit + 1
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Page::from_rows(rows, limit, total_count, |n| {
+            Cursor::at("created_at", n.created_at, n.id)
+        }))
+    }
+
+    pub async fn count_unread(&self, user_id: Uuid) -> Result<i64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM notifications
+            WHERE user_id = $1 AND read_at IS NULL
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    // 읽음 처리 (본인 알림이 아니거나 없으면 None, 이미 읽었으면 처음 읽은 시각 유지)
+    pub async fn mark_read(&self, user_id: Uuid, id: Uuid) -> Result<Option<Notification>> {
+        let notification = sqlx::query_as!(
+            Notification,
+            r#"
+            UPDATE notifications
+            SET read_at = COALESCE(read_at, NOW())
+            WHERE id = $1 AND user_id = $2
+            RETURNING
+                id, user_id, notification_type, approval_id, actor_id, title,
+                data as "data: Json<serde_json::Value>", read_at, created_at
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(notification)
+    }
+
+    // 모두 읽음 처리 (처리한 건수 반환)
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64> {
+        let result = sqlx::query!(
+            "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // 사용자가 꺼 둔 유형 (설정 행이 없으면 켜져 있는 것으로 봅니다)
+    pub async fn find_disabled_types(&self, user_id: Uuid) -> Result<Vec<String>> {
+        let types = sqlx::query_scalar!(
+            r#"
+            SELECT notification_type FROM notification_preferences
+            WHERE user_id = $1 AND NOT enabled
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(types)
+    }
+
+    pub async fn set_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: &[(NotificationType, bool)],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (notification_type, enabled) in preferences {
+            sqlx::query!(
+                r#"
+                INSERT INTO notification_preferences (user_id, notification_type, enabled)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (user_id, notification_type)
+                DO UPDATE SET enabled = EXCLUDED.enabled, updated_at = NOW()
+                "#,
+                user_id,
+                notification_type.as_str(),
+                enabled
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // 기한이 due_before 이전인 진행 중 문서 (이미 지난 문서 포함)
+    pub async fn find_due_soon(&self, due_before: DateTime<Utc>) -> Result<Vec<DueSoon>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                title,
+                due_at as "due_at!",
+                (flow_process->>'current_step')::int as "current_step!",
+                (flow_process->'steps'->((flow_process->>'current_step')::int - 1)->>'approver_id')::uuid
+                    as "approver_id?"
+            FROM pxm_approval_requests
+            WHERE status = 'pending' AND due_at IS NOT NULL AND due_at <= $1
+            "#,
+            due_before
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(DueSoon {
+                    approval_id: row.id,
+                    title: row.title,
+                    due_at: row.due_at,
+                    current_step: row.current_step,
+                    approver_id: row.approver_id?,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::domain::event::{DomainEvent, OutboxEvent};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool, Result};
+use uuid::Uuid;
+
+// outbox_events 접근
+// append/lock_due/mark_*는 호출자의 트랜잭션(&mut PgConnection) 안에서 실행됩니다.
+pub struct OutboxRepository {
+    pool: PgPool,
+}
+
+impl OutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 상태 변경과 같은 트랜잭션에서 이벤트를 기록합니다.
+    pub async fn append(
+        conn: &mut PgConnection,
+        actor_id: Uuid,
+        event: &DomainEvent,
+    ) -> Result<Uuid> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO outbox_events (approval_id, actor_id, event_type, payload)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+            event.approval().id,
+            actor_id,
+            event.event_type(),
+            Json(event) as _
+        )
+        .fetch_one(conn)
+        .await?;
+
+        Ok(id)
+    }
+
+    // 처리할 차례가 된 이벤트를 잠급니다. (다른 dispatcher가 잠근 행은 건너뜀)
+    // 트랜잭션이 끝날 때까지 잠금이 유지되므로, 처리 도중 프로세스가 죽으면 다시 처리됩니다.
+    pub async fn lock_due(conn: &mut PgConnection, limit: i64) -> Result<Vec<OutboxEvent>> {
+        let events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            SELECT
+                id, approval_id, actor_id, event_type,
+                payload as "payload: Json<DomainEvent>",
+                status, attempts, last_error, created_at
+            FROM outbox_events
+            WHERE status = 'pending' AND next_attempt_at <= NOW()
+            ORDER BY created_at ASC, id ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            limit
+        )
+        .fetch_all(conn)
+        .await?;
+
+        Ok(events)
+    }
+
+    pub async fn mark_processed(conn: &mut PgConnection, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET status = 'processed', attempts = attempts + 1, last_error = NULL, processed_at = NOW()
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    // 실패 기록: 재시도(pending + next_attempt_at) 또는 포기(dead)
+    pub async fn mark_failed(
+        conn: &mut PgConnection,
+        id: Uuid,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET status = $2, attempts = attempts + 1, next_attempt_at = $3, last_error = $4
+            WHERE id = $1
+            "#,
+            id,
+            status,
+            next_attempt_at,
+            error
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    // 문서별 이벤트 (발생 순)
+    pub async fn find_by_approval(&self, approval_id: Uuid) -> Result<Vec<OutboxEvent>> {
+        let events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            SELECT
+                id, approval_id, actor_id, event_type,
+                payload as "payload: Json<DomainEvent>",
+                status, attempts, last_error, created_at
+            FROM outbox_events
+            WHERE approval_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            approval_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+}
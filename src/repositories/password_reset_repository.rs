@@ -0,0 +1,91 @@
+use crate::domain::session::REVOKE_PASSWORD_RESET;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct PasswordResetRepository {
+    pool: PgPool,
+}
+
+impl PasswordResetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 새 재설정 토큰 (token_hash = hex(SHA-256)). 아직 쓰지 않은 이전 토큰은 무효화합니다.
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!(
+            "UPDATE password_reset_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    // 유효한 토큰이면 사용 처리 + 비밀번호 변경 + 모든 세션(refresh token 포함) 폐기를 한 트랜잭션으로 처리합니다.
+    // 없거나 만료되었거나 이미 사용된 토큰이면 None
+    pub async fn reset_password(
+        &self,
+        token_hash: &str,
+        password_hash: &str,
+    ) -> Result<Option<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
+        // 조건부 UPDATE로 같은 토큰을 동시에 두 번 써도 한쪽만 성공합니다.
+        let user_id = sqlx::query_scalar!(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            token_hash
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            password_hash
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            UPDATE auth_sessions SET revoked_at = NOW(), revoke_reason = $2
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id,
+            REVOKE_PASSWORD_RESET
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Some(user_id))
+    }
+}
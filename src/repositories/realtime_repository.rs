@@ -0,0 +1,110 @@
+use crate::domain::realtime::UserEvent;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+// user_events INSERT를 다른 서버 인스턴스에 알리는 채널
+pub const USER_EVENTS_CHANNEL: &str = "pxm_user_events";
+
+pub struct RealtimeRepository {
+    pool: PgPool,
+}
+
+impl RealtimeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 수신자별로 이벤트를 저장하고 NOTIFY 합니다. (NOTIFY는 commit 시점에 전달)
+    // 같은 outbox 이벤트를 다시 처리하면 이미 저장된 행은 건너뜁니다.
+    pub async fn publish(
+        &self,
+        source_event_id: Uuid,
+        recipients: &[(Uuid, &str)],
+        data: &serde_json::Value,
+    ) -> Result<Vec<UserEvent>> {
+        let (user_ids, event_types): (Vec<Uuid>, Vec<String>) = recipients
+            .iter()
+            .map(|(user_id, event_type)| (*user_id, event_type.to_string()))
+            .unzip();
+
+        let mut tx = self.pool.begin().await?;
+        let events = sqlx::query_as!(
+            UserEvent,
+            r#"
+            INSERT INTO user_events (user_id, source_event_id, event_type, data)
+            SELECT r.user_id, $1, r.event_type, $4
+            FROM UNNEST($2::uuid[], $3::text[]) AS r(user_id, event_type)
+            ON CONFLICT (source_event_id, user_id, event_type) DO NOTHING
+            RETURNING id, user_id, event_type, data as "data: Json<serde_json::Value>", created_at
+            "#,
+            source_event_id,
+            &user_ids,
+            &event_types,
+            Json(data) as _
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for event in &events {
+            let payload =
+                serde_json::to_string(event).map_err(|e| sqlx::Error::Encode(e.into()))?;
+            sqlx::query("SELECT pg_notify($1, $2)")
+                .bind(USER_EVENTS_CHANNEL)
+                .bind(payload)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(events)
+    }
+
+    // 재연결 시 놓친 이벤트 (Last-Event-ID 이후, id 순)
+    pub async fn find_after(
+        &self,
+        user_id: Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<UserEvent>> {
+        let events = sqlx::query_as!(
+            UserEvent,
+            r#"
+            SELECT id, user_id, event_type, data as "data: Json<serde_json::Value>", created_at
+            FROM user_events
+            WHERE user_id = $1 AND id > $2
+            ORDER BY id ASC
+            LIMIT $3
+            "#,
+            user_id,
+            after_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    // Last-Event-ID 없이 연결한 클라이언트의 시작 지점 (이 이후 이벤트만 보냄)
+    pub async fn latest_id(&self, user_id: Uuid) -> Result<i64> {
+        let latest = sqlx::query_scalar!(
+            r#"SELECT COALESCE(MAX(id), 0) as "id!" FROM user_events WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(latest)
+    }
+
+    // 보관 기간이 지난 이벤트 삭제 (삭제 건수 반환)
+    pub async fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query!("DELETE FROM user_events WHERE created_at < $1", cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
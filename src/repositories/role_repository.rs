@@ -0,0 +1,89 @@
+use crate::domain::role::{Role, UserRoles};
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct RoleRepository {
+    pool: PgPool,
+}
+
+impl RoleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<Role>> {
+        let roles = sqlx::query_as!(
+            Role,
+            r#"
+            SELECT
+                r.code,
+                r.name,
+                r.description,
+                COALESCE(
+                    array_agg(rp.permission_code ORDER BY rp.permission_code)
+                        FILTER (WHERE rp.permission_code IS NOT NULL),
+                    '{}'
+                ) as "permissions!"
+            FROM roles r
+            LEFT JOIN role_permissions rp ON rp.role_code = r.code
+            GROUP BY r.code
+            ORDER BY r.code
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(roles)
+    }
+
+    // 사용자의 역할과, 역할을 통해 부여된 권한 전체
+    pub async fn find_for_user(&self, user_id: Uuid) -> Result<UserRoles> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT ur.role_code, rp.permission_code as "permission_code?"
+            FROM user_roles ur
+            LEFT JOIN role_permissions rp ON rp.role_code = ur.role_code
+            WHERE ur.user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut user_roles = UserRoles::default();
+        for row in rows {
+            if !user_roles.roles.contains(&row.role_code) {
+                user_roles.roles.push(row.role_code);
+            }
+            if let Some(permission) = row.permission_code {
+                user_roles.permissions.insert(permission);
+            }
+        }
+
+        Ok(user_roles)
+    }
+
+    // 사용자의 역할을 주어진 목록으로 교체합니다.
+    pub async fn set_user_roles(&self, user_id: Uuid, roles: &[String]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM user_roles WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_id, role_code)
+            SELECT $1, UNNEST($2::varchar[])
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            roles
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
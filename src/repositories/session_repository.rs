@@ -0,0 +1,192 @@
+use crate::domain::session::{REVOKE_REFRESH_REUSED, RefreshOutcome};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct SessionRepository {
+    pool: PgPool,
+}
+
+impl SessionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 세션 + 첫 refresh token (token_hash = hex(SHA-256))
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        user_agent: Option<String>,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Uuid> {
+        let mut tx = self.pool.begin().await?;
+        let session_id = sqlx::query_scalar!(
+            "INSERT INTO auth_sessions (user_id, user_agent) VALUES ($1, $2) RETURNING id",
+            user_id,
+            user_agent
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_token(&mut tx, session_id, token_hash, expires_at).await?;
+        tx.commit().await?;
+
+        Ok(session_id)
+    }
+
+    // refresh token 교체: 사용하지 않은 유효한 토큰이면 사용 처리하고 새 토큰을 같은 세션에 저장합니다.
+    // 이미 사용된 토큰이 다시 오면 탈취된 것으로 보고 세션을 폐기합니다.
+    pub async fn rotate(
+        &self,
+        token_hash: &str,
+        new_token_hash: &str,
+        new_expires_at: DateTime<Utc>,
+    ) -> Result<RefreshOutcome> {
+        let mut tx = self.pool.begin().await?;
+
+        // 조건부 UPDATE로 동시에 같은 토큰을 두 번 써도 한쪽만 성공합니다.
+        let rotated = sqlx::query!(
+            r#"
+            UPDATE refresh_tokens t
+            SET used_at = NOW()
+            FROM auth_sessions s
+            WHERE t.token_hash = $1
+              AND t.used_at IS NULL
+              AND t.expires_at > NOW()
+              AND s.id = t.session_id
+              AND s.revoked_at IS NULL
+            RETURNING t.session_id, s.user_id
+            "#,
+            token_hash
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(row) = rotated {
+            insert_token(&mut tx, row.session_id, new_token_hash, new_expires_at).await?;
+            sqlx::query!(
+                "UPDATE auth_sessions SET last_used_at = NOW() WHERE id = $1",
+                row.session_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(RefreshOutcome::Rotated {
+                session_id: row.session_id,
+                user_id: row.user_id,
+            });
+        }
+
+        let used = sqlx::query!(
+            "SELECT session_id, used_at FROM refresh_tokens WHERE token_hash = $1",
+            token_hash
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let outcome = match used {
+            Some(row) if row.used_at.is_some() => {
+                sqlx::query!(
+                    r#"
+                    UPDATE auth_sessions SET revoked_at = NOW(), revoke_reason = $2
+                    WHERE id = $1 AND revoked_at IS NULL
+                    "#,
+                    row.session_id,
+                    REVOKE_REFRESH_REUSED
+                )
+                .execute(&mut *tx)
+                .await?;
+                RefreshOutcome::Reused {
+                    session_id: row.session_id,
+                }
+            }
+            _ => RefreshOutcome::Invalid,
+        };
+        tx.commit().await?;
+
+        Ok(outcome)
+    }
+
+    // auth_middleware: 폐기되지 않은 본인 세션인지 확인
+    pub async fn is_active(&self, session_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let active = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM auth_sessions
+                WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            ) as "active!"
+            "#,
+            session_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(active)
+    }
+
+    pub async fn revoke(&self, session_id: Uuid, reason: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE auth_sessions SET revoked_at = NOW(), revoke_reason = $2
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+            session_id,
+            reason
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 사용자의 모든 세션 폐기 (모든 기기 로그아웃, 비밀번호 재설정 등)
+    pub async fn revoke_all(&self, user_id: Uuid, reason: &str) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE auth_sessions SET revoked_at = NOW(), revoke_reason = $2
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id,
+            reason
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // 현재 세션만 남기고 폐기 (비밀번호 변경)
+    pub async fn revoke_others(&self, user_id: Uuid, keep: Uuid, reason: &str) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE auth_sessions SET revoked_at = NOW(), revoke_reason = $3
+            WHERE user_id = $1 AND id <> $2 AND revoked_at IS NULL
+            "#,
+            user_id,
+            keep,
+            reason
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+async fn insert_token(
+    conn: &mut sqlx::PgConnection,
+    session_id: Uuid,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO refresh_tokens (session_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+        session_id,
+        token_hash,
+        expires_at
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
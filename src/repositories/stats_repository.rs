@@ -0,0 +1,218 @@
+use crate::domain::stats::{
+    ApproverStats, BacklogStats, StatsQuery, StatsScope, StepStats, TemplateStats,
+};
+use crate::repositories::approval_repository::push_requester_in_subtree;
+use sqlx::{PgPool, Postgres, QueryBuilder, Result};
+
+// 소요 시간(hour) 집계 컬럼: hours 컬럼에 대한 평균/중앙값/90분위
+const DURATION_AGGREGATES: &str = r#"
+    AVG(x.hours) AS avg_hours,
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY x.hours) AS p50_hours,
+    percentile_cont(0.9) WITHIN GROUP (ORDER BY x.hours) AS p90_hours
+"#;
+
+// 처리된 결재 단계 (단계별/결재자별 집계용)
+const PROCESSED_STEPS: &str = r#"
+    SELECT s.*, EXTRACT(EPOCH FROM (s.processed_at - s.started_at))::float8 / 3600 AS hours
+    FROM steps s
+    WHERE s.step_status IN ('approved', 'rejected') AND s.processed_at IS NOT NULL
+"#;
+
+pub struct StatsRepository {
+    pool: PgPool,
+}
+
+impl StatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // 템플릿별: 상신 ~ 완료 소요 시간, 반려율
+    pub async fn by_template(
+        &self,
+        scope: StatsScope,
+        query: &StatsQuery,
+    ) -> Result<Vec<TemplateStats>> {
+        let mut qb = QueryBuilder::new("");
+        push_ctes(&mut qb, scope, query, true);
+        qb.push(format!(
+            r#"
+            , per_request AS (
+                SELECT b.id, b.template_id, b.status,
+                    CASE WHEN b.status IN ('approved', 'rejected') THEN
+                        EXTRACT(EPOCH FROM (
+                            (SELECT MAX(s.processed_at) FROM steps s WHERE s.id = b.id) - b.submitted_at
+                        ))::float8 / 3600
+                    END AS hours
+                FROM scoped b
+            )
+            SELECT
+                x.template_id,
+                t.name AS template_name,
+                COUNT(*) AS submitted,
+                COUNT(*) FILTER (WHERE x.status IN ('approved', 'rejected')) AS completed,
+                COUNT(*) FILTER (WHERE x.status = 'rejected') AS rejected,
+                (COUNT(*) FILTER (WHERE x.status = 'rejected'))::float8
+                    / NULLIF(COUNT(*) FILTER (WHERE x.status IN ('approved', 'rejected')), 0) AS rejection_rate,
+                {DURATION_AGGREGATES}
+            FROM per_request x
+            LEFT JOIN templates t ON t.id = x.template_id
+            GROUP BY x.template_id, t.name
+            ORDER BY submitted DESC, t.name
+            "#
+        ));
+
+        qb.build_query_as::<TemplateStats>()
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    // 템플릿의 결재 단계별: 단계 소요 시간, 반려율
+    pub async fn by_step(&self, scope: StatsScope, query: &StatsQuery) -> Result<Vec<StepStats>> {
+        let mut qb = QueryBuilder::new("");
+        push_ctes(&mut qb, scope, query, true);
+        qb.push(format!(
+            r#"
+            SELECT
+                x.template_id,
+                t.name AS template_name,
+                x.seq,
+                x.step_name,
+                COUNT(*) AS processed,
+                COUNT(*) FILTER (WHERE x.step_status = 'rejected') AS rejected,
+                (COUNT(*) FILTER (WHERE x.step_status = 'rejected'))::float8 / COUNT(*) AS rejection_rate,
+                {DURATION_AGGREGATES}
+            FROM ({PROCESSED_STEPS}) x
+            LEFT JOIN templates t ON t.id = x.template_id
+            GROUP BY x.template_id, t.name, x.seq, x.step_name
+            ORDER BY t.name NULLS LAST, x.template_id, x.seq, x.step_name
+            "#
+        ));
+
+        qb.build_query_as::<StepStats>().fetch_all(&self.pool).await
+    }
+
+    // 결재자별: 처리 건수, 소요 시간, 반려율
+    pub async fn by_approver(
+        &self,
+        scope: StatsScope,
+        query: &StatsQuery,
+    ) -> Result<Vec<ApproverStats>> {
+        let mut qb = QueryBuilder::new("");
+        push_ctes(&mut qb, scope, query, true);
+        qb.push(format!(
+            r#"
+            SELECT
+                x.approver_id,
+                u.full_name AS approver_name,
+                COUNT(*) AS processed,
+                COUNT(*) FILTER (WHERE x.step_status = 'rejected') AS rejected,
+                (COUNT(*) FILTER (WHERE x.step_status = 'rejected'))::float8 / COUNT(*) AS rejection_rate,
+                {DURATION_AGGREGATES}
+            FROM ({PROCESSED_STEPS}) x
+            LEFT JOIN users u ON u.id = x.approver_id
+            GROUP BY x.approver_id, u.full_name
+            ORDER BY avg_hours DESC NULLS LAST, processed DESC
+            "#
+        ));
+
+        qb.build_query_as::<ApproverStats>()
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    // 결재자별 현재 대기 건수 (기간 필터는 적용하지 않음)
+    pub async fn backlog(
+        &self,
+        scope: StatsScope,
+        query: &StatsQuery,
+    ) -> Result<Vec<BacklogStats>> {
+        let mut qb = QueryBuilder::new("");
+        push_ctes(&mut qb, scope, query, false);
+        qb.push(
+            r#"
+            SELECT
+                x.approver_id,
+                u.full_name AS approver_name,
+                COUNT(*) AS pending,
+                MIN(x.started_at) AS oldest_waiting_since,
+                AVG(EXTRACT(EPOCH FROM (NOW() - x.started_at))::float8 / 3600) AS avg_waiting_hours
+            FROM steps x
+            LEFT JOIN users u ON u.id = x.approver_id
+            WHERE x.request_status = 'pending' AND x.is_current AND x.step_status = 'pending'
+            GROUP BY x.approver_id, u.full_name
+            ORDER BY pending DESC, oldest_waiting_since
+            "#,
+        );
+
+        qb.build_query_as::<BacklogStats>()
+            .fetch_all(&self.pool)
+            .await
+    }
+}
+
+// 공통 CTE
+// - base: 범위/부서 조건에 맞는 상신된 문서 + 상신 시각
+// - scoped: 기간 조건 적용 (with_dates)
+// - steps: flow_process.steps를 행으로 펼치고 단계 시작 시각(이전 단계 처리 시각)을 계산
+fn push_ctes(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    scope: StatsScope,
+    query: &StatsQuery,
+    with_dates: bool,
+) {
+    qb.push(
+        r#"
+        WITH base AS (
+            SELECT r.id, r.template_id, r.status, r.flow_process,
+                COALESCE(
+                    (SELECT MAX(l.created_at) FROM approval_logs l
+                     WHERE l.approval_id = r.id AND l.action_type = 'SUBMITTED'),
+                    r.created_at
+                ) AS submitted_at
+            FROM pxm_approval_requests r
+            WHERE r.status <> 'draft'
+        "#,
+    );
+    if let StatsScope::ManagedBy(manager_id) = scope {
+        qb.push(" AND ");
+        push_requester_in_subtree(qb, "d.manager_id", manager_id);
+    }
+    if let Some(department_id) = query.department_id {
+        qb.push(" AND ");
+        push_requester_in_subtree(qb, "d.id", department_id);
+    }
+
+    qb.push("), scoped AS (SELECT * FROM base WHERE TRUE");
+    if with_dates {
+        if let Some(from) = query.from {
+            qb.push(" AND submitted_at >= ").push_bind(from);
+        }
+        if let Some(to) = query.to {
+            qb.push(" AND submitted_at < ").push_bind(to);
+        }
+    }
+
+    qb.push(
+        r#"
+        ), steps AS (
+            SELECT
+                b.id,
+                b.template_id,
+                b.status AS request_status,
+                s.ord AS seq,
+                s.step->>'name' AS step_name,
+                (s.step->>'approver_id')::uuid AS approver_id,
+                s.step->>'status' AS step_status,
+                (s.step->>'timestamp')::timestamptz AS processed_at,
+                COALESCE(
+                    LAG((s.step->>'timestamp')::timestamptz) OVER (PARTITION BY b.id ORDER BY s.ord),
+                    b.submitted_at
+                ) AS started_at,
+                (b.flow_process->>'current_step')::bigint = s.ord AS is_current
+            FROM scoped b
+            CROSS JOIN LATERAL jsonb_array_elements(b.flow_process->'steps') WITH ORDINALITY AS s(step, ord)
+        )
+        "#,
+    );
+}
@@ -1,7 +1,56 @@
-use crate::domain::template::{CreateTemplateDto, Template};
-use sqlx::PgPool;
+use crate::domain::pagination::{Cursor, Page};
+use crate::domain::template::{CreateTemplateDto, PatchTemplateDto, Template, TemplateCategory};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+// visible_department_ids는 template_visibility 테이블에서 집계합니다.
+const TEMPLATE_COLUMNS: &str = r#"
+    t.id, t.name, t.description, t.form_schema, t.workflow_snapshot, t.category_code, t.title_pattern,
+    COALESCE(
+        (SELECT array_agg(v.department_id) FROM template_visibility v WHERE v.template_id = t.id),
+        '{}'
+    ) AS visible_department_ids,
+    t.is_archived, t.archived_at, t.created_at, t.updated_at
+"#;
+
+// 사용자의 소속 부서와 모든 상위 부서 (부모 부서 공개 = 하위 부서에도 공개)
+// $1 = user_id
+const USER_DEPARTMENT_CHAIN: &str = r#"
+    WITH RECURSIVE chain AS (
+        SELECT d.id, d.parent_id
+        FROM departments d
+        JOIN users u ON u.department_id = d.id
+        WHERE u.id = $1
+        UNION
+        SELECT p.id, p.parent_id
+        FROM departments p
+        JOIN chain c ON p.id = c.parent_id
+    )
+"#;
+
+// 공개 범위가 없거나, 공개 부서 중 하나가 사용자의 부서 체인에 포함되면 노출
+const VISIBLE_TO_CHAIN: &str = r#"
+    (
+        NOT EXISTS (SELECT 1 FROM template_visibility v WHERE v.template_id = t.id)
+        OR EXISTS (
+            SELECT 1 FROM template_visibility v
+            WHERE v.template_id = t.id AND v.department_id IN (SELECT id FROM chain)
+        )
+    )
+"#;
+
+// 목록 조건 ($1 = viewer_id, $2 = include_archived, $3 = category)
+fn list_filter() -> String {
+    format!(
+        r#"
+        ($2 OR NOT t.is_archived)
+        AND ($3::varchar IS NULL OR t.category_code = $3)
+        AND ($1::uuid IS NULL OR {VISIBLE_TO_CHAIN})
+        "#
+    )
+}
+
 pub struct TemplateRepository {
     pool: PgPool,
 }
@@ -12,50 +61,310 @@ impl TemplateRepository {
     }
 
     pub async fn create(&self, dto: CreateTemplateDto) -> Result<Template, sqlx::Error> {
-        let template = sqlx::query_as::<_, Template>(
+        let id = Uuid::new_v4();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
             r#"
-            INSERT INTO templates (id, name, description, form_schema, workflow_snapshot, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
-            RETURNING id, name, description, form_schema, workflow_snapshot, created_at, updated_at
-            "#
+            INSERT INTO templates (id, name, description, form_schema, workflow_snapshot, category_code, title_pattern, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW(), NOW())
+            "#,
         )
-        .bind(Uuid::new_v4())
+        .bind(id)
         .bind(dto.name)
         .bind(dto.description)
         .bind(sqlx::types::Json(dto.form_schema))
         .bind(sqlx::types::Json(dto.workflow_snapshot))
-        .fetch_one(&self.pool)
+        .bind(dto.category_code)
+        .bind(dto.title_pattern)
+        .execute(&mut *tx)
         .await?;
 
+        Self::replace_visibility(&mut tx, id, &dto.visible_department_ids).await?;
+        tx.commit().await?;
+
+        self.find_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    // viewer_id가 주어지면 해당 사용자의 부서에 공개된 템플릿만 조회합니다.
+    pub async fn find_all(
+        &self,
+        include_archived: bool,
+        category: Option<String>,
+        viewer_id: Option<Uuid>,
+    ) -> Result<Vec<Template>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            {USER_DEPARTMENT_CHAIN}
+            SELECT {TEMPLATE_COLUMNS}
+            FROM templates t
+            WHERE {}
+            ORDER BY t.created_at DESC, t.id DESC
+            "#,
+            list_filter()
+        );
+        let templates = sqlx::query_as::<_, Template>(&sql)
+            .bind(viewer_id)
+            .bind(include_archived)
+            .bind(category)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(templates)
+    }
+
+    // find_all과 같은 조건의 keyset 페이지 (최신순)
+    pub async fn find_page(
+        &self,
+        include_archived: bool,
+        category: Option<String>,
+        viewer_id: Option<Uuid>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Page<Template>, sqlx::Error> {
+        let count_sql = format!(
+            r#"
+            {USER_DEPARTMENT_CHAIN}
+            SELECT COUNT(*) FROM templates t WHERE {}
+            "#,
+            list_filter()
+        );
+        let total_count: i64 = sqlx::query_scalar(&count_sql)
+            .bind(viewer_id)
+            .bind(include_archived)
+            .bind(category.clone())
+            .fetch_one(&self.pool)
+            .await?;
+
+        let (after_at, after_id) = after.unzip();
+        let sql = format!(
+            r#"
+            {USER_DEPARTMENT_CHAIN}
+            SELECT {TEMPLATE_COLUMNS}
+            FROM templates t
+            WHERE {}
+              AND ($4::timestamptz IS NULL OR (t.created_at, t.id) < ($4, $5))
+            ORDER BY t.created_at DESC, t.id DESC
+            LIMIT $6
+            "#,
+            list_filter()
+        );
+        let rows = sqlx::query_as::<_, Template>(&sql)
+            .bind(viewer_id)
+            .bind(include_archived)
+            .bind(category)
+            .bind(after_at)
+            .bind(after_id)
+            .bind(limit + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Page::from_rows(rows, limit, total_count, |t| {
+            Cursor::at("created_at", t.created_at, t.id)
+        }))
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Template>, sqlx::Error> {
+        let sql = format!("SELECT {TEMPLATE_COLUMNS} FROM templates t WHERE t.id = $1");
+        let template = sqlx::query_as::<_, Template>(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
         Ok(template)
     }
 
-    pub async fn find_all(&self) -> Result<Vec<Template>, sqlx::Error> {
-        let templates = sqlx::query_as::<_, Template>(
+    // 같은 이름의 보관되지 않은 템플릿 (번들 import 충돌 확인용)
+    pub async fn find_active_by_name(&self, name: &str) -> Result<Option<Template>, sqlx::Error> {
+        let sql = format!(
+            "SELECT {TEMPLATE_COLUMNS} FROM templates t
+             WHERE t.name = $1 AND NOT t.is_archived
+             ORDER BY t.created_at LIMIT 1"
+        );
+        let template = sqlx::query_as::<_, Template>(&sql)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(template)
+    }
+
+    // 사용자의 부서(상위 부서 포함)에 이 템플릿이 공개되어 있는지 확인합니다.
+    pub async fn is_visible_to(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let sql = format!(
+            r#"
+            {USER_DEPARTMENT_CHAIN}
+            SELECT EXISTS (
+                SELECT 1 FROM templates t WHERE t.id = $2 AND {VISIBLE_TO_CHAIN}
+            )
+            "#
+        );
+        let visible: bool = sqlx::query_scalar(&sql)
+            .bind(user_id)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(visible)
+    }
+
+    // PUT: 전체 필드를 교체합니다.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        dto: CreateTemplateDto,
+    ) -> Result<Option<Template>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
-            SELECT id, name, description, form_schema, workflow_snapshot, created_at, updated_at
-            FROM templates
-            ORDER BY created_at DESC
+            UPDATE templates
+            SET name = $2, description = $3, form_schema = $4, workflow_snapshot = $5,
+                category_code = $6, title_pattern = $7, updated_at = NOW()
+            WHERE id = $1
             "#,
         )
-        .fetch_all(&self.pool)
+        .bind(id)
+        .bind(dto.name)
+        .bind(dto.description)
+        .bind(sqlx::types::Json(dto.form_schema))
+        .bind(sqlx::types::Json(dto.workflow_snapshot))
+        .bind(dto.category_code)
+        .bind(dto.title_pattern)
+        .execute(&mut *tx)
         .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
 
-        Ok(templates)
+        Self::replace_visibility(&mut tx, id, &dto.visible_department_ids).await?;
+        tx.commit().await?;
+
+        self.find_by_id(id).await
     }
 
-    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Template>, sqlx::Error> {
-        let template = sqlx::query_as::<_, Template>(
+    // PATCH: 전달되지 않은(None) 필드는 기존 값을 유지합니다.
+    // description, category_code, title_pattern은 Some(None)이면 NULL로 해제합니다.
+    pub async fn patch(
+        &self,
+        id: Uuid,
+        dto: PatchTemplateDto,
+    ) -> Result<Option<Template>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
-            SELECT id, name, description, form_schema, workflow_snapshot, created_at, updated_at
-            FROM templates
+            UPDATE templates
+            SET
+                name = COALESCE($2, name),
+                description = CASE WHEN $3 THEN $4 ELSE description END,
+                form_schema = COALESCE($5, form_schema),
+                workflow_snapshot = COALESCE($6, workflow_snapshot),
+                category_code = CASE WHEN $7 THEN $8 ELSE category_code END,
+                title_pattern = CASE WHEN $9 THEN $10 ELSE title_pattern END,
+                updated_at = NOW()
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(dto.name)
+        .bind(dto.description.is_some())
+        .bind(dto.description.flatten())
+        .bind(dto.form_schema.map(sqlx::types::Json))
+        .bind(dto.workflow_snapshot.map(sqlx::types::Json))
+        .bind(dto.category_code.is_some())
+        .bind(dto.category_code.flatten())
+        .bind(dto.title_pattern.is_some())
+        .bind(dto.title_pattern.flatten())
+        .execute(&mut *tx)
         .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
 
-        Ok(template)
+        if let Some(department_ids) = dto.visible_department_ids {
+            Self::replace_visibility(&mut tx, id, &department_ids).await?;
+        }
+        tx.commit().await?;
+
+        self.find_by_id(id).await
+    }
+
+    pub async fn archive(&self, id: Uuid) -> Result<Option<Template>, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE templates
+            SET is_archived = TRUE, archived_at = COALESCE(archived_at, NOW()), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        self.find_by_id(id).await
+    }
+
+    // 이 템플릿으로 생성된 결재 요청 수 (삭제 가능 여부 판단용)
+    pub async fn count_usages(&self, id: Uuid) -> Result<i64, sqlx::Error> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM pxm_approval_requests WHERE template_id = $1")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(count)
+    }
+
+    // 삭제된 행이 있으면 true
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM templates WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn find_categories(&self) -> Result<Vec<TemplateCategory>, sqlx::Error> {
+        let categories = sqlx::query_as::<_, TemplateCategory>(
+            r#"
+            SELECT code, name, sort_order
+            FROM template_categories
+            ORDER BY sort_order ASC, name ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    async fn replace_visibility(
+        tx: &mut Transaction<'_, Postgres>,
+        template_id: Uuid,
+        department_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM template_visibility WHERE template_id = $1")
+            .bind(template_id)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO template_visibility (template_id, department_id)
+            SELECT $1, UNNEST($2::uuid[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(template_id)
+        .bind(department_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
     }
 }
@@ -65,4 +65,51 @@ impl UserRepository {
 
         Ok(user)
     }
+
+    // ACTIVE | INACTIVE | SUSPENDED
+    pub async fn set_status(&self, id: Uuid, status: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET status = $2, updated_at = NOW() WHERE id = $1",
+            id,
+            status
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 로그인 성공 시각 기록
+    pub async fn record_login(&self, id: Uuid) -> Result<Option<User>> {
+        let user = sqlx::query_as!(
+            User,
+            "UPDATE users SET last_login_at = NOW() WHERE id = $1 RETURNING *",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    pub async fn update_password(&self, id: Uuid, password_hash: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = NOW() WHERE id = $1",
+            id,
+            password_hash
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 이메일(대소문자 무시)로 사용자 ID 조회 (의견 @언급 등)
+    pub async fn find_ids_by_emails(&self, emails: &[String]) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar!("SELECT id FROM users WHERE LOWER(email) = ANY($1)", emails)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
 }
@@ -0,0 +1,302 @@
+use crate::domain::pagination::{Cursor, Page};
+use crate::domain::webhook::{
+    DeliveryStatus, DueDelivery, WebhookDelivery, WebhookEvent, WebhookSubscription,
+};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        name: String,
+        url: String,
+        secret: String,
+        event_types: Vec<String>,
+        template_id: Option<Uuid>,
+        created_by: Uuid,
+    ) -> Result<WebhookSubscription> {
+        let subscription = sqlx::query_as!(
+            WebhookSubscription,
+            r#"
+            INSERT INTO webhook_subscriptions (name, url, secret, event_types, template_id, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+            name,
+            url,
+            secret,
+            &event_types,
+            template_id,
+            created_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(subscription)
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<WebhookSubscription>> {
+        let subscriptions = sqlx::query_as!(
+            WebhookSubscription,
+            "SELECT * FROM webhook_subscriptions ORDER BY created_at ASC, id ASC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookSubscription>> {
+        let subscription = sqlx::query_as!(
+            WebhookSubscription,
+            "SELECT * FROM webhook_subscriptions WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(subscription)
+    }
+
+    pub async fn update(&self, subscription: WebhookSubscription) -> Result<WebhookSubscription> {
+        let updated = sqlx::query_as!(
+            WebhookSubscription,
+            r#"
+            UPDATE webhook_subscriptions
+            SET name = $1, url = $2, secret = $3, event_types = $4, template_id = $5,
+                is_active = $6, updated_at = NOW()
+            WHERE id = $7
+            RETURNING *
+            "#,
+            subscription.name,
+            subscription.url,
+            subscription.secret,
+            &subscription.event_types,
+            subscription.template_id,
+            subscription.is_active,
+            subscription.id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    // 구독과 전송 이력(delivery)을 함께 삭제합니다.
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query!("DELETE FROM webhook_subscriptions WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 이벤트에 해당하는 활성 구독마다 delivery를 하나씩 만듭니다. (만든 건수 반환)
+    // 같은 이벤트를 다시 넣어도 구독별 delivery는 하나만 생깁니다.
+    // - event_types가 비어 있으면 모든 이벤트
+    // - template_id가 있으면 해당 템플릿으로 만든 문서의 이벤트만
+    pub async fn enqueue(&self, event: &WebhookEvent) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            SELECT s.id, $1, $2::text, $3
+            FROM webhook_subscriptions s
+            WHERE s.is_active
+              AND (cardinality(s.event_types) = 0 OR $2::text = ANY(s.event_types))
+              AND (s.template_id IS NULL OR s.template_id = $4)
+            ON CONFLICT (subscription_id, event_id) DO NOTHING
+            "#,
+            event.id,
+            event.event_type.as_str(),
+            Json(&event.payload) as _,
+            event.template_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // 전송 이력 (최신순 keyset 페이지네이션)
+    pub async fn find_deliveries_page(
+        &self,
+        subscription_id: Uuid,
+        status: Option<DeliveryStatus>,
+        before: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Page<WebhookDelivery>> {
+        let status = status.map(|s| s.as_str());
+        let total_count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM webhook_deliveries
+            WHERE subscription_id = $1 AND ($2::text IS NULL OR status = $2)
+            "#,
+            subscription_id,
+            status
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let (before_at, before_id) = before.unzip();
+        let rows = sqlx::query_as!(
+            WebhookDelivery,
+            r#"
+            SELECT
+                id, subscription_id, event_id, event_type,
+                payload as "payload: Json<serde_json::Value>",
+                status, attempts, next_attempt_at, last_attempt_at,
+                response_status, last_error, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE subscription_id = $1
+              AND ($2::text IS NULL OR status = $2)
+              AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
+            "#,
+            subscription_id,
+            status,
+            before_at,
+            before_id,
+            limit + 1
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Page::from_rows(rows, limit, total_count, |delivery| {
+            Cursor::at("created_at", delivery.created_at, delivery.id)
+        }))
+    }
+
+    pub async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as!(
+            WebhookDelivery,
+            r#"
+            SELECT
+                id, subscription_id, event_id, event_type,
+                payload as "payload: Json<serde_json::Value>",
+                status, attempts, next_attempt_at, last_attempt_at,
+                response_status, last_error, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    // 전송할 차례가 된 delivery를 가져가면서 시도 횟수를 올립니다.
+    // next_attempt_at을 lease_secs 뒤로 미뤄 두므로, 결과 기록 전에 worker가 죽더라도 나중에 다시 전송됩니다.
+    // (여러 worker가 동시에 실행돼도 SKIP LOCKED로 같은 행을 가져가지 않습니다.)
+    pub async fn claim_due(&self, limit: i64, lease_secs: f64) -> Result<Vec<DueDelivery>> {
+        let deliveries = sqlx::query_as!(
+            DueDelivery,
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM webhook_deliveries d
+                JOIN webhook_subscriptions s ON s.id = d.subscription_id
+                WHERE d.status = 'pending' AND d.next_attempt_at <= NOW() AND s.is_active
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET attempts = d.attempts + 1,
+                last_attempt_at = NOW(),
+                next_attempt_at = NOW() + make_interval(secs => $2),
+                updated_at = NOW()
+            FROM due, webhook_subscriptions s
+            WHERE d.id = due.id AND s.id = d.subscription_id
+            RETURNING
+                d.id, d.event_type,
+                d.payload as "payload: Json<serde_json::Value>",
+                d.attempts, s.url, s.secret
+            "#,
+            limit,
+            lease_secs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn mark_succeeded(&self, id: Uuid, response_status: i32) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'succeeded', response_status = $2, last_error = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            response_status
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 실패 기록: 재시도(pending + next_attempt_at) 또는 포기(dead)
+    pub async fn mark_failed(
+        &self,
+        id: Uuid,
+        status: DeliveryStatus,
+        next_attempt_at: DateTime<Utc>,
+        response_status: Option<i32>,
+        error: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, next_attempt_at = $3, response_status = $4, last_error = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            status.as_str(),
+            next_attempt_at,
+            response_status,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // 수동 재전송: 상태와 무관하게 시도 횟수를 초기화하고 바로 전송 대기열에 넣습니다.
+    pub async fn redeliver(&self, id: Uuid) -> Result<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as!(
+            WebhookDelivery,
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending', attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            RETURNING
+                id, subscription_id, event_id, event_type,
+                payload as "payload: Json<serde_json::Value>",
+                status, attempts, next_attempt_at, last_attempt_at,
+                response_status, last_error, created_at, updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::domain::{
+    approval::ApprovalRequest,
+    role::{Permission, UserRoles},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// [Access Policy]
+// 결재 문서 열람 권한을 한 곳에서 판단합니다. 모든 조회 경로(단건/목록/로그/검색 등)는 이 모듈을 사용해야 합니다.
+//
+// 임시저장(draft) 문서는 요청자 본인만 열람합니다. (approval:read_all 포함 다른 누구도 불가)
+//
+// 열람 가능한 사용자:
+// 1. 요청자
+// 2. 결재선(flow_process.steps)의 결재자
+// 3. 참조자(flow_process.references)
+// 4. 요청자 소속 부서 및 상위 부서들의 부서장
+// 5. approval:read_all 권한 보유자 (AUDITOR, ADMIN)
+//
+// 목록 SQL 조건은 repositories::approval_repository의 push_visibility_filter가 같은 규칙을 표현합니다.
+pub struct ApprovalAccessPolicy {
+    pool: PgPool,
+}
+
+impl ApprovalAccessPolicy {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn can_view(
+        &self,
+        viewer_id: Uuid,
+        roles: &UserRoles,
+        request: &ApprovalRequest,
+    ) -> sqlx::Result<bool> {
+        if request.status == "draft" {
+            return Ok(request.requester_id == viewer_id);
+        }
+        if roles.has(Permission::ApprovalReadAll) {
+            return Ok(true);
+        }
+
+        let flow_process = &request.flow_process.0;
+        if request.requester_id == viewer_id
+            || flow_process
+                .steps
+                .iter()
+                .any(|s| s.approver_id == viewer_id)
+            || flow_process.references.contains(&viewer_id)
+        {
+            return Ok(true);
+        }
+
+        self.manages_department_of(viewer_id, request.requester_id)
+            .await
+    }
+
+    // viewer가 requester 소속 부서(또는 그 상위 부서)의 부서장인지
+    pub async fn manages_department_of(
+        &self,
+        viewer_id: Uuid,
+        requester_id: Uuid,
+    ) -> sqlx::Result<bool> {
+        let manages = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT d.id, d.parent_id, d.manager_id
+                FROM departments d
+                JOIN users u ON u.department_id = d.id
+                WHERE u.id = $1
+                UNION
+                SELECT p.id, p.parent_id, p.manager_id
+                FROM departments p
+                JOIN chain c ON p.id = c.parent_id
+            )
+            SELECT EXISTS (SELECT 1 FROM chain WHERE manager_id = $2) as "manages!"
+            "#,
+            requester_id,
+            viewer_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(manages)
+    }
+}
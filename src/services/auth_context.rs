@@ -0,0 +1,85 @@
+use crate::domain::role::UserRoles;
+use crate::repositories::role_repository::RoleRepository;
+use crate::repositories::user_repository::UserRepository;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// [Auth Context Cache]
+// auth_middleware가 매 요청 사용자 상태/부서/역할을 DB에서 읽지 않도록 짧게(AUTH_CACHE_TTL_SECS, 기본 30초) 캐시합니다.
+// 역할 변경 등은 invalidate로 바로 반영하고, 그 밖의 변경도 TTL이 지나면 반영됩니다.
+// 세션 폐기(로그아웃)는 즉시 반영되어야 하므로 캐시하지 않습니다.
+
+#[derive(Debug, Clone)]
+pub struct CachedUser {
+    pub status: String,
+    pub department_id: Option<Uuid>,
+    pub roles: UserRoles,
+}
+
+pub struct AuthContextCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<Uuid, (Instant, CachedUser)>>,
+}
+
+// 이 크기를 넘으면 만료된 항목을 정리합니다.
+const MAX_ENTRIES: usize = 10_000;
+
+pub fn auth_cache() -> &'static AuthContextCache {
+    static CACHE: OnceLock<AuthContextCache> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let ttl = std::env::var("AUTH_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(30);
+        AuthContextCache::new(Duration::from_secs(ttl))
+    })
+}
+
+impl AuthContextCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::default(),
+        }
+    }
+
+    // 캐시에 없거나 만료되었으면 DB에서 읽습니다. (삭제된 사용자는 None)
+    pub async fn load(&self, pool: &PgPool, user_id: Uuid) -> sqlx::Result<Option<CachedUser>> {
+        if let Some((fetched_at, user)) = self.entries.read().unwrap().get(&user_id)
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(Some(user.clone()));
+        }
+
+        let Some(user) = UserRepository::new(pool.clone())
+            .find_by_id(user_id)
+            .await?
+        else {
+            self.invalidate(user_id);
+            return Ok(None);
+        };
+        let roles = RoleRepository::new(pool.clone())
+            .find_for_user(user_id)
+            .await?;
+        let cached = CachedUser {
+            status: user.status,
+            department_id: user.department_id,
+            roles,
+        };
+
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < self.ttl);
+        }
+        entries.insert(user_id, (Instant::now(), cached.clone()));
+
+        Ok(Some(cached))
+    }
+
+    pub fn invalidate(&self, user_id: Uuid) {
+        self.entries.write().unwrap().remove(&user_id);
+    }
+}
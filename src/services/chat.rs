@@ -0,0 +1,325 @@
+use crate::domain::chat::{ChatAction, ChatButtons, ChatMessage, InteractionToken};
+use crate::domain::event::{DomainEvent, OutboxEvent};
+use crate::domain::notification::NotificationType;
+use crate::repositories::chat_repository::{ChatRepository, NewChatDelivery};
+use crate::repositories::user_repository::UserRepository;
+use crate::services::notification::RecipientResolver;
+use crate::services::outbox::{EventConsumer, RetryPolicy, claim_lease, poll_batches};
+use async_trait::async_trait;
+use chrono::Utc;
+use rand::RngCore;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+// [Chat Delivery]
+// 재시도 backoff와 polling 루프는 services::outbox의 RetryPolicy/poll_batches를 씁니다.
+// - ChatConsumer: outbox 이벤트 -> 받을 사용자(알림 센터와 같은 기준)의 개인 webhook과
+//   채널 webhook마다 메시지를 chat_deliveries에 저장 (버튼 토큰도 이때 서명)
+// - ChatDispatcher: 저장된 메시지를 incoming webhook URL로 POST, 실패하면 backoff 후 재시도
+
+// CHAT_INTERACTION_SECRET, CHAT_CALLBACK_URL, CHAT_TOKEN_TTL_HOURS, CHAT_MAX_ATTEMPTS,
+// CHAT_SLACK_SIGNING_SECRET, CHAT_MATTERMOST_TOKEN, ...
+#[derive(Debug, Clone)]
+pub struct ChatConfig {
+    pub batch_size: i64,
+    pub poll_interval: Duration,
+    pub max_attempts: i32,
+    pub timeout: Duration,
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+    // 메시지 제목 링크의 기준 URL (프론트엔드 주소)
+    pub app_url: String,
+    // 버튼이 호출할 이 서버의 POST /chat/interactions 주소 (채팅 서버에서 접근 가능해야 함)
+    pub callback_url: String,
+    // 버튼 토큰 서명 키
+    pub secret: String,
+    pub token_ttl: Duration,
+    // 채팅 서버가 보낸 요청인지 확인하는 값 (둘 다 없으면 버튼/명령 콜백은 모두 거부)
+    // - Slack: app의 Signing Secret
+    // - Mattermost: /pxm slash command의 token (버튼 context에도 넣어 보냄)
+    pub slack_signing_secret: Option<String>,
+    pub mattermost_token: Option<String>,
+    // 채팅 계정 연결 코드 유효 시간
+    pub link_code_ttl: Duration,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(5),
+            max_attempts: 6,
+            timeout: Duration::from_secs(10),
+            backoff_base: Duration::from_secs(30),
+            backoff_cap: Duration::from_secs(60 * 60),
+            app_url: "http://localhost:3000".to_string(),
+            callback_url: "http://localhost:3001/chat/interactions".to_string(),
+            secret: generate_secret(),
+            token_ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            slack_signing_secret: None,
+            mattermost_token: None,
+            link_code_ttl: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl ChatConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        let env_secret = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let secret = std::env::var("CHAT_INTERACTION_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty())
+            .unwrap_or_else(|| {
+                eprintln!(
+                    "CHAT_INTERACTION_SECRET is not set; chat buttons stop working after a restart"
+                );
+                default.secret.clone()
+            });
+        let slack_signing_secret = env_secret("CHAT_SLACK_SIGNING_SECRET");
+        let mattermost_token = env_secret("CHAT_MATTERMOST_TOKEN");
+        if slack_signing_secret.is_none() && mattermost_token.is_none() {
+            eprintln!(
+                "CHAT_SLACK_SIGNING_SECRET / CHAT_MATTERMOST_TOKEN are not set; chat buttons and commands are disabled"
+            );
+        }
+        Self {
+            max_attempts: env_u64("CHAT_MAX_ATTEMPTS")
+                .map(|v| v.max(1) as i32)
+                .unwrap_or(default.max_attempts),
+            poll_interval: env_u64("CHAT_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.poll_interval),
+            token_ttl: env_u64("CHAT_TOKEN_TTL_HOURS")
+                .map(|hours| Duration::from_secs(hours * 60 * 60))
+                .unwrap_or(default.token_ttl),
+            app_url: std::env::var("APP_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(default.app_url.clone()),
+            callback_url: std::env::var("CHAT_CALLBACK_URL")
+                .unwrap_or(default.callback_url.clone()),
+            secret,
+            slack_signing_secret,
+            mattermost_token,
+            ..default
+        }
+    }
+
+    pub fn retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            backoff_base: self.backoff_base,
+            backoff_cap: self.backoff_cap,
+        }
+    }
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub struct ChatConsumer {
+    repo: ChatRepository,
+    users: UserRepository,
+    recipients: RecipientResolver,
+    config: Arc<ChatConfig>,
+}
+
+impl ChatConsumer {
+    pub fn new(pool: PgPool, config: Arc<ChatConfig>) -> Self {
+        Self {
+            repo: ChatRepository::new(pool.clone()),
+            users: UserRepository::new(pool.clone()),
+            recipients: RecipientResolver::new(pool),
+            config,
+        }
+    }
+
+    async fn user_name(&self, user_id: Uuid) -> Result<String, String> {
+        Ok(self
+            .users
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|user| user.full_name)
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl EventConsumer for ChatConsumer {
+    fn name(&self) -> &'static str {
+        "chat"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        let recipients = self.recipients.resolve(event).await?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        let user_ids: Vec<_> = recipients.iter().map(|(user_id, _)| *user_id).collect();
+        let webhooks = self
+            .repo
+            .find_targets(&user_ids)
+            .await
+            .map_err(|e| e.to_string())?;
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let domain_event = &event.payload.0;
+        let approval = domain_event.approval();
+        let flow = &approval.flow_process;
+        let message = ChatMessage {
+            title: approval.title.clone(),
+            requester: self.user_name(approval.requester_id).await?,
+            actor: self.user_name(event.actor_id).await?,
+            step_name: flow
+                .steps
+                .get((flow.current_step - 1).max(0) as usize)
+                .map(|step| step.name.clone())
+                .unwrap_or_default(),
+            reason: match domain_event {
+                DomainEvent::RequestRejected { reason, .. } => reason.clone(),
+                _ => None,
+            },
+            link: format!("{}/approvals/{}", self.config.app_url, approval.id),
+        };
+
+        let expires_at = (Utc::now()
+            + chrono::Duration::from_std(self.config.token_ttl).unwrap_or(chrono::Duration::MAX))
+        .timestamp();
+        let mut deliveries = Vec::new();
+        for (user_id, notification_type) in recipients {
+            // 지금 이 사용자가 결재할 차례일 때만 버튼을 붙입니다.
+            let actionable = notification_type == NotificationType::Assigned
+                && approval.status == "pending"
+                && approval.current_approver() == Some(user_id);
+            for webhook in &webhooks {
+                if webhook.owner_id.is_some_and(|owner| owner != user_id)
+                    || !webhook.accepts(notification_type)
+                {
+                    continue;
+                }
+                let buttons = actionable.then(|| {
+                    let token = |action| {
+                        InteractionToken {
+                            approval_id: approval.id,
+                            step: flow.current_step,
+                            approver_id: user_id,
+                            action,
+                            channel: webhook.owner_id.is_none(),
+                            expires_at,
+                        }
+                        .sign(&self.config.secret)
+                    };
+                    ChatButtons {
+                        callback_url: self.config.callback_url.clone(),
+                        approve_token: token(ChatAction::Approve),
+                        reject_token: token(ChatAction::Reject),
+                        verify_token: self.config.mattermost_token.clone(),
+                    }
+                });
+                deliveries.push(NewChatDelivery {
+                    webhook_id: webhook.id,
+                    event_id: event.id,
+                    recipient_id: user_id,
+                    notification_type: notification_type.as_str().to_string(),
+                    payload: message.payload(notification_type, &webhook.locale, buttons.as_ref()),
+                });
+            }
+        }
+
+        self.repo
+            .enqueue(&deliveries)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+pub struct ChatDispatcher {
+    repo: ChatRepository,
+    client: reqwest::Client,
+    config: Arc<ChatConfig>,
+}
+
+impl ChatDispatcher {
+    pub fn new(pool: PgPool, config: Arc<ChatConfig>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .user_agent("pxm-chat/1.0")
+            .build()
+            .expect("Failed to build chat HTTP client");
+        Self {
+            repo: ChatRepository::new(pool),
+            client,
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돕니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        poll_batches(
+            "Chat",
+            self.config.batch_size,
+            self.config.poll_interval,
+            || self.deliver_due(),
+        )
+        .await
+    }
+
+    // 보낼 차례인 메시지를 한 묶음 전송하고 처리한 건수를 반환합니다.
+    pub async fn deliver_due(&self) -> Result<usize, sqlx::Error> {
+        // 한 묶음을 동시에 보내므로 전송 timeout 한 번만큼 lease를 잡아 둡니다.
+        let lease = claim_lease(self.config.timeout);
+        let deliveries = self.repo.claim_due(self.config.batch_size, lease).await?;
+        let count = deliveries.len();
+
+        let results =
+            futures::future::join_all(deliveries.into_iter().map(|delivery| async move {
+                let result = self
+                    .client
+                    .post(&delivery.url)
+                    .json(&delivery.payload.0)
+                    .send()
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|response| match response.status() {
+                        status if status.is_success() => Ok(()),
+                        status => Err(format!("HTTP {}", status)),
+                    });
+                (result, delivery)
+            }))
+            .await;
+
+        for (result, delivery) in results {
+            match result {
+                Ok(()) => self.repo.mark_sent(delivery.id).await?,
+                Err(error) => {
+                    let (status, next_attempt_at) =
+                        match self.config.retry().next_attempt_at(delivery.attempts) {
+                            Some(next_attempt_at) => ("pending", next_attempt_at),
+                            None => {
+                                eprintln!(
+                                    "Chat delivery {} gave up after {} attempts: {}",
+                                    delivery.id, delivery.attempts, error
+                                );
+                                ("dead", Utc::now())
+                            }
+                        };
+                    self.repo
+                        .mark_failed(delivery.id, status, next_attempt_at, error)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::domain::digest::Digest;
+use crate::domain::email::{DEFAULT_LOCALE, DIGEST_TEMPLATE};
+use crate::repositories::approval_repository::ApprovalRepository;
+use crate::repositories::digest_repository::{DigestRepository, DueDigest};
+use crate::repositories::email_repository::EmailRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::services::email::{EmailMessage, EmailTransport};
+use crate::utils::timezone::org_timezone;
+use chrono::{Days, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+// [Daily Digest]
+// 요약 메일을 신청한 사용자에게 하루 한 번, 사용자 타임존의 send_time이 지나면 보냅니다.
+// - 결재 대기함 문서(대기 시간 포함)와 어제 승인/반려된 내 문서를 한 통에 담습니다.
+// - 보낼 내용이 없는 날은 건너뜁니다. (그날은 보낸 것으로 처리)
+// - 메일 문구는 email_templates의 "digest" 템플릿(사용자 언어)을 사용합니다.
+// - 전송에 실패하면 last_sent_on을 되돌려 다음 회차에 다시 시도합니다.
+
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    pub batch_size: i64,
+    pub poll_interval: Duration,
+    // 대기 문서 목록에 담는 최대 건수 (전체 건수는 따로 표시)
+    pub max_items: i64,
+    // 메일 본문 링크의 기준 URL (프론트엔드 주소)
+    pub base_url: String,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(60),
+            max_items: 20,
+            base_url: "http://localhost:3000".to_string(),
+        }
+    }
+}
+
+impl DigestConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            poll_interval: env_u64("DIGEST_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.poll_interval),
+            max_items: env_u64("DIGEST_MAX_ITEMS")
+                .map(|v| v.max(1) as i64)
+                .unwrap_or(default.max_items),
+            base_url: std::env::var("APP_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(default.base_url.clone()),
+            ..default
+        }
+    }
+}
+
+pub struct DigestScheduler {
+    pool: PgPool,
+    repo: DigestRepository,
+    transport: Arc<dyn EmailTransport>,
+    config: DigestConfig,
+}
+
+impl DigestScheduler {
+    pub fn new(pool: PgPool, transport: Arc<dyn EmailTransport>, config: DigestConfig) -> Self {
+        Self {
+            repo: DigestRepository::new(pool.clone()),
+            pool,
+            transport,
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돕니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        loop {
+            match self.send_due().await {
+                Ok(count) if count as i64 >= self.config.batch_size => continue,
+                Ok(_) => {}
+                Err(e) => eprintln!("Digest scheduler error: {:?}", e),
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    // 보낼 차례인 사용자 한 묶음을 처리하고 처리한 사용자 수를 반환합니다.
+    pub async fn send_due(&self) -> Result<usize, sqlx::Error> {
+        let due = self
+            .repo
+            .claim_due(org_timezone().name(), self.config.batch_size)
+            .await?;
+
+        for digest in &due {
+            if let Err(error) = self.send(digest).await {
+                eprintln!("Digest for user {} failed: {}", digest.user_id, error);
+                self.repo
+                    .release(digest.user_id, digest.previous_sent_on)
+                    .await?;
+            }
+        }
+
+        Ok(due.len())
+    }
+
+    async fn send(&self, due: &DueDigest) -> Result<(), String> {
+        let db_error = |e: sqlx::Error| e.to_string();
+        let Some(user) = UserRepository::new(self.pool.clone())
+            .find_by_id(due.user_id)
+            .await
+            .map_err(db_error)?
+        else {
+            return Ok(());
+        };
+        if user.status != "ACTIVE" {
+            return Ok(());
+        }
+
+        let tz: Tz = due.timezone.parse().unwrap_or_else(|_| org_timezone());
+        let (from, to) = local_day_range(tz, due.sent_on - Days::new(1));
+        let approvals = ApprovalRepository::new(self.pool.clone());
+        let (pending, pending_total) = approvals
+            .find_digest_pending(user.id, self.config.max_items)
+            .await
+            .map_err(db_error)?;
+        let changes = approvals
+            .find_status_changes(user.id, from, to)
+            .await
+            .map_err(db_error)?;
+
+        let digest = Digest {
+            pending,
+            pending_total,
+            changes,
+        };
+        if digest.is_empty() {
+            return Ok(());
+        }
+
+        // 사용자 언어 템플릿이 없으면 기본 언어
+        let templates = EmailRepository::new(self.pool.clone());
+        let mut template = None;
+        for locale in [user.locale.as_str(), DEFAULT_LOCALE] {
+            template = templates
+                .find_template(DIGEST_TEMPLATE, locale)
+                .await
+                .map_err(db_error)?;
+            if template.is_some() {
+                break;
+            }
+        }
+        let template = template.ok_or("No email template for 'digest'")?;
+        let rendered = digest
+            .render(
+                &template,
+                &user.locale,
+                &user.full_name,
+                due.sent_on,
+                &self.config.base_url,
+                Utc::now(),
+            )
+            .map_err(|e| e.to_string())?;
+        self.transport
+            .send(&EmailMessage {
+                to_email: user.email,
+                to_name: user.full_name,
+                subject: rendered.subject,
+                text: rendered.text,
+                html: rendered.html,
+            })
+            .await
+    }
+}
+
+// 타임존 tz의 date 하루를 UTC 구간 [from, to)로 변환합니다. (DST 전환일 포함)
+pub fn local_day_range(tz: Tz, date: NaiveDate) -> (chrono::DateTime<Utc>, chrono::DateTime<Utc>) {
+    let start_of = |date: NaiveDate| {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        tz.from_local_datetime(&midnight)
+            .earliest()
+            // 자정이 존재하지 않는 날(DST 전환)은 UTC 기준으로 근사
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|| midnight.and_utc())
+    };
+    (start_of(date), start_of(date + Days::new(1)))
+}
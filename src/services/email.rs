@@ -0,0 +1,363 @@
+use crate::domain::email::{DEFAULT_LOCALE, EmailContext, EmailTemplate, RenderedEmail};
+use crate::repositories::email_repository::{DueEmail, EmailRepository};
+use crate::services::outbox::{RetryPolicy, claim_lease, poll_batches};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+// [Email Notifier]
+// 알림(notifications)마다 예약된 email_deliveries를 렌더링해 보냅니다.
+// - 실패하면 backoff 후 재시도, max_attempts를 넘기면 dead로 남깁니다. (결재 처리에는 영향 없음)
+// - SMTP_HOST가 없으면 LogTransport로 내용을 로그에만 남깁니다. (개발 환경)
+
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to_email: String,
+    pub to_name: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+#[async_trait]
+pub trait EmailTransport: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpTls {
+    // 평문 (로컬 SMTP sink 등)
+    None,
+    // 평문 연결 후 STARTTLS 필수 (기본, 587)
+    StartTls,
+    // 처음부터 TLS (465)
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // 보내는 사람 (예: "PXM <no-reply@pxm.com>")
+    pub from: String,
+    pub timeout: Duration,
+}
+
+impl SmtpConfig {
+    // SMTP_HOST가 없으면 None
+    // SMTP_PORT, SMTP_TLS(none|starttls|tls), SMTP_USERNAME, SMTP_PASSWORD, SMTP_FROM, SMTP_TIMEOUT_SECS
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Ok(host) = std::env::var("SMTP_HOST") else {
+            return Ok(None);
+        };
+        let tls = match std::env::var("SMTP_TLS")
+            .unwrap_or_else(|_| "starttls".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "none" => SmtpTls::None,
+            "starttls" => SmtpTls::StartTls,
+            "tls" => SmtpTls::Tls,
+            other => return Err(format!("Invalid SMTP_TLS '{}'", other)),
+        };
+        let port = match std::env::var("SMTP_PORT") {
+            Ok(port) => port
+                .parse()
+                .map_err(|_| format!("Invalid SMTP_PORT '{}'", port))?,
+            Err(_) => match tls {
+                SmtpTls::None => 25,
+                SmtpTls::StartTls => 587,
+                SmtpTls::Tls => 465,
+            },
+        };
+        Ok(Some(Self {
+            host,
+            port,
+            tls,
+            username: std::env::var("SMTP_USERNAME").ok(),
+            password: std::env::var("SMTP_PASSWORD").ok(),
+            from: std::env::var("SMTP_FROM")
+                .unwrap_or_else(|_| "PXM <no-reply@pxm.local>".to_string()),
+            timeout: smtp_timeout_from_env(),
+        }))
+    }
+}
+
+// SMTP_TIMEOUT_SECS (기본 30초)
+fn smtp_timeout_from_env() -> Duration {
+    std::env::var("SMTP_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(30))
+}
+
+pub struct SmtpTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpTransport {
+    pub fn new(config: &SmtpConfig) -> Result<Self, String> {
+        let builder = match config.tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| e.to_string())?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| e.to_string())?,
+        };
+        let mut builder = builder.port(config.port).timeout(Some(config.timeout));
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| format!("Invalid SMTP_FROM: {}", e))?;
+
+        Ok(Self {
+            mailer: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailTransport for SmtpTransport {
+    async fn send(&self, message: &EmailMessage) -> Result<(), String> {
+        let to = Mailbox::new(
+            Some(message.to_name.clone()),
+            message
+                .to_email
+                .parse()
+                .map_err(|e| format!("Invalid recipient: {}", e))?,
+        );
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&message.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                message.text.clone(),
+                message.html.clone(),
+            ))
+            .map_err(|e| e.to_string())?;
+
+        self.mailer
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// SMTP 설정이 없을 때: 보내는 대신 로그로 남깁니다.
+pub struct LogTransport;
+
+#[async_trait]
+impl EmailTransport for LogTransport {
+    async fn send(&self, message: &EmailMessage) -> Result<(), String> {
+        eprintln!(
+            "📧 [email] to={} subject={:?}\n{}",
+            message.to_email, message.subject, message.text
+        );
+        Ok(())
+    }
+}
+
+// SMTP_HOST가 있으면 SMTP, 없으면 로그
+pub fn transport_from_env() -> Result<Arc<dyn EmailTransport>, String> {
+    match SmtpConfig::from_env()? {
+        Some(config) => Ok(Arc::new(SmtpTransport::new(&config)?)),
+        None => {
+            eprintln!("SMTP_HOST not set: email notifications are written to the log only");
+            Ok(Arc::new(LogTransport))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub batch_size: i64,
+    pub poll_interval: Duration,
+    pub max_attempts: i32,
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+    // 메일 한 통의 전송 timeout (SMTP_TIMEOUT_SECS, claim lease 계산용)
+    pub send_timeout: Duration,
+    // 메일 본문 링크의 기준 URL (프론트엔드 주소)
+    pub base_url: String,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 20,
+            poll_interval: Duration::from_secs(5),
+            max_attempts: 6,
+            backoff_base: Duration::from_secs(60),
+            backoff_cap: Duration::from_secs(60 * 60),
+            send_timeout: Duration::from_secs(30),
+            base_url: "http://localhost:3000".to_string(),
+        }
+    }
+}
+
+impl EmailConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            max_attempts: env_u64("EMAIL_MAX_ATTEMPTS")
+                .map(|v| v.max(1) as i32)
+                .unwrap_or(default.max_attempts),
+            poll_interval: env_u64("EMAIL_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.poll_interval),
+            send_timeout: smtp_timeout_from_env(),
+            base_url: std::env::var("APP_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(default.base_url.clone()),
+            ..default
+        }
+    }
+
+    pub fn retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            backoff_base: self.backoff_base,
+            backoff_cap: self.backoff_cap,
+        }
+    }
+}
+
+pub struct EmailDispatcher {
+    repo: EmailRepository,
+    transport: Arc<dyn EmailTransport>,
+    config: EmailConfig,
+}
+
+impl EmailDispatcher {
+    pub fn new(pool: PgPool, transport: Arc<dyn EmailTransport>, config: EmailConfig) -> Self {
+        Self {
+            repo: EmailRepository::new(pool),
+            transport,
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돕니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        poll_batches(
+            "Email",
+            self.config.batch_size,
+            self.config.poll_interval,
+            || self.deliver_due(),
+        )
+        .await
+    }
+
+    // 보낼 차례인 메일 한 묶음을 처리하고 처리한 건수를 반환합니다.
+    pub async fn deliver_due(&self) -> Result<usize, sqlx::Error> {
+        // 한 통씩 차례로 보내므로 묶음 전체가 끝날 때까지 lease를 잡아 둡니다.
+        let batch_size = u32::try_from(self.config.batch_size).unwrap_or(u32::MAX);
+        let lease = claim_lease(self.config.send_timeout.saturating_mul(batch_size));
+        let emails = self.repo.claim_due(self.config.batch_size, lease).await?;
+        if emails.is_empty() {
+            return Ok(0);
+        }
+        // 관리자가 편집한 최신 템플릿을 사용합니다.
+        let templates = self.repo.find_templates().await?;
+
+        for email in &emails {
+            let result = match self.render(email, &templates) {
+                Ok(message) => self.transport.send(&message).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => self.repo.mark_sent(email.id).await?,
+                Err(error) => {
+                    let (status, next_attempt_at) =
+                        match self.config.retry().next_attempt_at(email.attempts) {
+                            Some(next_attempt_at) => ("pending", next_attempt_at),
+                            None => {
+                                eprintln!(
+                                    "Email delivery {} gave up after {} attempts: {}",
+                                    email.id, email.attempts, error
+                                );
+                                ("dead", Utc::now())
+                            }
+                        };
+                    self.repo
+                        .mark_failed(email.id, status, next_attempt_at, error)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(emails.len())
+    }
+
+    fn render(
+        &self,
+        email: &DueEmail,
+        templates: &[EmailTemplate],
+    ) -> Result<EmailMessage, String> {
+        // 사용자 언어 템플릿이 없으면 기본 언어
+        let template = [email.locale.as_str(), DEFAULT_LOCALE]
+            .iter()
+            .find_map(|locale| {
+                templates
+                    .iter()
+                    .find(|t| t.notification_type == email.notification_type && t.locale == *locale)
+            })
+            .ok_or_else(|| format!("No email template for '{}'", email.notification_type))?;
+
+        let step_name = email
+            .data
+            .0
+            .get("current_step")
+            .and_then(|step| step.as_i64())
+            .zip(email.flow_process.as_ref())
+            .and_then(|(step, flow)| flow.0.steps.get((step - 1).max(0) as usize))
+            .map(|step| step.name.clone())
+            .unwrap_or_default();
+        let link = email
+            .approval_id
+            .map(|id| format!("{}/approvals/{}", self.config.base_url, id))
+            .unwrap_or_else(|| self.config.base_url.clone());
+
+        let RenderedEmail {
+            subject,
+            text,
+            html,
+        } = template
+            .render(&EmailContext {
+                title: email.title.clone(),
+                requester: email.requester_name.clone().unwrap_or_default(),
+                step_name,
+                link,
+                recipient: email.recipient_name.clone(),
+                ..EmailContext::default()
+            })
+            .map_err(|e| e.to_string())?;
+
+        Ok(EmailMessage {
+            to_email: email.recipient_email.clone(),
+            to_name: email.recipient_name.clone(),
+            subject,
+            text,
+            html,
+        })
+    }
+}
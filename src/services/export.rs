@@ -0,0 +1,192 @@
+use crate::domain::approval::ApprovalRequest;
+use crate::domain::form_schema::{FormField, display_value, fields_of};
+use crate::repositories::user_repository::UserRepository;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// [Approval Export]
+// 결재 목록을 CSV/XLSX 한 행씩으로 변환합니다.
+// 고정 컬럼 + 선택한 form_data 필드(JSON 경로, 예: "trip.city")를 form_schema 라벨로 펼칩니다.
+
+const FIXED_COLUMNS: &[&str] = &[
+    "document_number",
+    "title",
+    "status",
+    "requester",
+    "approvers",
+    "created_at",
+    "completed_at",
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+// GET /approvals/export?format=xlsx&fields=amount,trip.city (+ 목록 필터)
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub format: ExportFormat,
+    // 쉼표로 구분한 form_data 경로. 없으면 template_id 필터의 양식 필드 전체.
+    pub fields: Option<String>,
+}
+
+pub struct ExportColumns {
+    tz: Tz,
+    fields: Vec<FormField>,
+}
+
+impl ExportColumns {
+    // form_schema: template_id로 필터링한 경우 해당 템플릿의 스키마 (라벨/기본 필드용)
+    pub fn new(tz: Tz, form_schema: Option<&Value>, selected: Option<&str>) -> Self {
+        let schema_fields = form_schema.map(fields_of).unwrap_or_default();
+        let fields = match selected {
+            Some(raw) => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| {
+                    schema_fields
+                        .iter()
+                        .find(|f| f.key() == key)
+                        .cloned()
+                        .unwrap_or_else(|| FormField {
+                            path: key.split('.').map(str::to_string).collect(),
+                            label: key.to_string(),
+                        })
+                })
+                .collect(),
+            None => schema_fields,
+        };
+        Self { tz, fields }
+    }
+
+    pub fn header(&self) -> Vec<String> {
+        FIXED_COLUMNS
+            .iter()
+            .map(|c| c.to_string())
+            .chain(self.fields.iter().map(|f| f.label.clone()))
+            .collect()
+    }
+
+    pub fn row(&self, request: &ApprovalRequest, names: &HashMap<Uuid, String>) -> Vec<String> {
+        let name_of = |id: &Uuid| names.get(id).cloned().unwrap_or_else(|| id.to_string());
+        let approvers = request
+            .flow_process
+            .steps
+            .iter()
+            .map(|step| {
+                format!(
+                    "{}: {} ({})",
+                    step.name,
+                    name_of(&step.approver_id),
+                    step.status
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" > ");
+
+        let mut row = vec![
+            request.document_number(self.tz),
+            request.title.clone(),
+            request.status.clone(),
+            name_of(&request.requester_id),
+            approvers,
+            self.format_time(request.created_at),
+            completed_at(request)
+                .map(|t| self.format_time(t))
+                .unwrap_or_default(),
+        ];
+        row.extend(self.fields.iter().map(|field| {
+            field
+                .value_in(&request.form_data.0)
+                .map(display_value)
+                .unwrap_or_default()
+        }));
+        row
+    }
+
+    fn format_time(&self, time: DateTime<Utc>) -> String {
+        time.with_timezone(&self.tz)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+}
+
+// 완료(승인/반려) 시각: 마지막으로 처리된 결재 단계의 시각
+pub fn completed_at(request: &ApprovalRequest) -> Option<DateTime<Utc>> {
+    if !matches!(request.status.as_str(), "approved" | "rejected") {
+        return None;
+    }
+    request
+        .flow_process
+        .steps
+        .iter()
+        .filter_map(|step| step.timestamp)
+        .max()
+}
+
+// CSV 셀 값: 수식으로 해석될 수 있는 값(=, +, -, @)은 앞에 '를 붙입니다.
+// 음수(-1200, -3.5)는 숫자로 남도록 그대로 둡니다.
+pub fn csv_cell(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@']) && !is_negative_number(value) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn is_negative_number(value: &str) -> bool {
+    let Some(digits) = value.strip_prefix('-') else {
+        return false;
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+    !integer.is_empty()
+        && !fraction.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
+
+// 요청자/결재자 이름 조회 (행마다 반복 조회하지 않도록 캐시)
+pub struct UserNames {
+    repo: UserRepository,
+    names: HashMap<Uuid, String>,
+}
+
+impl UserNames {
+    pub fn new(repo: UserRepository) -> Self {
+        Self {
+            repo,
+            names: HashMap::new(),
+        }
+    }
+
+    pub async fn load_for(
+        &mut self,
+        request: &ApprovalRequest,
+    ) -> Result<&HashMap<Uuid, String>, sqlx::Error> {
+        let ids = std::iter::once(request.requester_id)
+            .chain(request.flow_process.steps.iter().map(|s| s.approver_id))
+            .collect::<Vec<_>>();
+        for id in ids {
+            if !self.names.contains_key(&id) {
+                let name = self
+                    .repo
+                    .find_by_id(id)
+                    .await?
+                    .map(|u| u.full_name)
+                    .unwrap_or_else(|| id.to_string());
+                self.names.insert(id, name);
+            }
+        }
+        Ok(&self.names)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::domain::login::{LoginThrottleConfig, SCOPE_EMAIL, SCOPE_IP};
+use crate::repositories::login_attempt_repository::{LoginAttemptRepository, NewLoginAttempt};
+use sqlx::PgPool;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// [Login Guard]
+// login 핸들러가 비밀번호를 확인하기 전에 reserve로 시도를 먼저 세고 (계정/IP),
+// 결과에 따라 record(실패) / record_success / release(실패가 아닌 거부)를 부릅니다.
+// 설정은 LOGIN_* 환경 변수 (domain::login::LoginThrottleConfig)
+
+pub fn login_throttle() -> &'static LoginThrottleConfig {
+    static CONFIG: OnceLock<LoginThrottleConfig> = OnceLock::new();
+    CONFIG.get_or_init(LoginThrottleConfig::from_env)
+}
+
+// 이번 시도를 센 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoginCheck {
+    pub ip_blocked: bool,
+    pub account_locked: bool,
+    // 비밀번호 확인 전에 기다릴 시간 (계정/IP의 이전 실패 중 많은 쪽 기준)
+    pub delay: Duration,
+}
+
+pub struct LoginGuard {
+    repo: LoginAttemptRepository,
+    config: &'static LoginThrottleConfig,
+}
+
+impl LoginGuard {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: LoginAttemptRepository::new(pool),
+            config: login_throttle(),
+        }
+    }
+
+    // account: domain::login::account_key(email)
+    // IP가 차단되어 있으면 계정은 세지 않습니다.
+    pub async fn reserve(&self, account: &str, ip: Option<&str>) -> sqlx::Result<LoginCheck> {
+        let window = self.config.failure_window;
+        let ip_failures = match ip {
+            Some(ip) => {
+                let reserved = self
+                    .repo
+                    .reserve(
+                        SCOPE_IP,
+                        ip,
+                        window,
+                        self.config.ip_max_failures,
+                        self.config.lockout,
+                    )
+                    .await?;
+                let Some(state) = reserved else {
+                    return Ok(LoginCheck {
+                        ip_blocked: true,
+                        account_locked: false,
+                        delay: Duration::ZERO,
+                    });
+                };
+                state.failures
+            }
+            None => 0,
+        };
+        let account_state = self
+            .repo
+            .reserve(
+                SCOPE_EMAIL,
+                account,
+                window,
+                self.config.max_failures,
+                self.config.lockout,
+            )
+            .await?;
+
+        Ok(LoginCheck {
+            ip_blocked: false,
+            account_locked: account_state.is_none(),
+            // 이번 시도를 포함한 횟수이므로 1을 빼서 이전 실패 횟수로 계산
+            delay: self.config.delay(
+                account_state
+                    .map_or(0, |state| state.failures)
+                    .max(ip_failures)
+                    - 1,
+            ),
+        })
+    }
+
+    // 로그인 성공: 계정 실패 횟수는 초기화, IP는 이번 시도만 되돌립니다.
+    // (IP 횟수는 다른 계정을 시도하는 중일 수 있으므로 유지)
+    pub async fn record_success(&self, attempt: &NewLoginAttempt<'_>) -> sqlx::Result<()> {
+        self.repo.clear(SCOPE_EMAIL, attempt.email).await?;
+        if let Some(ip) = attempt.ip_address {
+            self.repo
+                .release(SCOPE_IP, ip, self.config.ip_max_failures)
+                .await?;
+        }
+        self.repo.record(attempt).await
+    }
+
+    // 비밀번호는 맞았지만 다른 이유로 거부 (비활성 계정 등): 센 시도를 되돌리고 이력만 남깁니다.
+    pub async fn release(&self, attempt: &NewLoginAttempt<'_>) -> sqlx::Result<()> {
+        self.repo
+            .release(SCOPE_EMAIL, attempt.email, self.config.max_failures)
+            .await?;
+        if let Some(ip) = attempt.ip_address {
+            self.repo
+                .release(SCOPE_IP, ip, self.config.ip_max_failures)
+                .await?;
+        }
+        self.repo.record(attempt).await
+    }
+
+    // 이력만 남깁니다. (실패는 reserve에서 이미 셌음, IP 차단)
+    pub async fn record(&self, attempt: &NewLoginAttempt<'_>) -> sqlx::Result<()> {
+        self.repo.record(attempt).await
+    }
+
+    // 관리자 잠금 해제
+    pub async fn unlock(&self, account: &str) -> sqlx::Result<bool> {
+        self.repo.clear(SCOPE_EMAIL, account).await
+    }
+}
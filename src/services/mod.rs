@@ -0,0 +1,14 @@
+pub mod access_policy;
+pub mod auth_context;
+pub mod chat;
+pub mod digest;
+pub mod email;
+pub mod export;
+pub mod login_guard;
+pub mod notification;
+pub mod outbox;
+pub mod password_reset;
+pub mod pdf;
+pub mod realtime;
+pub mod storage;
+pub mod webhook;
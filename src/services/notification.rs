@@ -0,0 +1,235 @@
+use crate::domain::approval::ApprovalRequest;
+use crate::domain::event::{DomainEvent, OutboxEvent};
+use crate::domain::notification::{
+    NewNotification, NotificationType, mentioned_emails, notifications_for,
+};
+use crate::repositories::notification_repository::NotificationRepository;
+use crate::repositories::role_repository::RoleRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::services::access_policy::ApprovalAccessPolicy;
+use crate::services::outbox::EventConsumer;
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+// outbox 이벤트 -> 알림 (배정/승인/반려/언급)
+// dedupe_key = outbox 이벤트 id 이므로 같은 이벤트를 다시 받아도 알림은 한 번만 생깁니다.
+pub struct NotificationConsumer {
+    notifications: NotificationRepository,
+    recipients: RecipientResolver,
+}
+
+impl NotificationConsumer {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            notifications: NotificationRepository::new(pool.clone()),
+            recipients: RecipientResolver::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl EventConsumer for NotificationConsumer {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        let domain_event = &event.payload.0;
+        let recipients = self.recipients.resolve(event).await?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+
+        let approval = domain_event.approval();
+        let mut data = serde_json::json!({
+            "source": domain_event.event_type(),
+            "status": approval.status,
+            "current_step": approval.flow_process.current_step,
+        });
+        match domain_event {
+            DomainEvent::RequestRejected {
+                reason: Some(reason),
+                ..
+            } => data["reason"] = serde_json::json!(reason),
+            DomainEvent::CommentAdded { comment, .. } => {
+                data["comment_id"] = serde_json::json!(comment.id)
+            }
+            _ => {}
+        }
+
+        let notifications: Vec<NewNotification> = recipients
+            .into_iter()
+            .map(|(user_id, notification_type)| NewNotification {
+                user_id,
+                notification_type,
+                approval_id: Some(approval.id),
+                actor_id: Some(event.actor_id),
+                title: approval.title.clone(),
+                data: data.clone(),
+                dedupe_key: event.id.to_string(),
+            })
+            .collect();
+        self.notifications
+            .insert_many(&notifications)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// 이벤트로 알림을 받을 사용자와 알림 종류 (의견의 @이메일 언급 포함)
+// 채팅 알림(services::chat::ChatConsumer)도 같은 기준을 사용합니다.
+pub struct RecipientResolver {
+    users: UserRepository,
+    roles: RoleRepository,
+    policy: ApprovalAccessPolicy,
+}
+
+impl RecipientResolver {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            users: UserRepository::new(pool.clone()),
+            roles: RoleRepository::new(pool.clone()),
+            policy: ApprovalAccessPolicy::new(pool),
+        }
+    }
+
+    pub async fn resolve(
+        &self,
+        event: &OutboxEvent,
+    ) -> Result<Vec<(Uuid, NotificationType)>, String> {
+        let domain_event = &event.payload.0;
+        let mentioned = match domain_event {
+            DomainEvent::CommentAdded { approval, comment } => {
+                let emails = mentioned_emails(comment.content.as_deref().unwrap_or_default());
+                if emails.is_empty() {
+                    Vec::new()
+                } else {
+                    let ids = self
+                        .users
+                        .find_ids_by_emails(&emails)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    self.viewers_of(approval, ids).await?
+                }
+            }
+            _ => Vec::new(),
+        };
+        Ok(notifications_for(domain_event, event.actor_id, &mentioned))
+    }
+
+    // 언급은 문서를 열람할 수 있는 사용자에게만 알립니다. (제목/의견이 알림으로 새지 않도록)
+    async fn viewers_of(
+        &self,
+        approval: &ApprovalRequest,
+        user_ids: Vec<Uuid>,
+    ) -> Result<Vec<Uuid>, String> {
+        let mut viewers = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            let roles = self
+                .roles
+                .find_for_user(user_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            if self
+                .policy
+                .can_view(user_id, &roles, approval)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                viewers.push(user_id);
+            }
+        }
+        Ok(viewers)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadlineConfig {
+    // 기한까지 이 시간 이내로 남으면 알림
+    pub window: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for DeadlineConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(24 * 60 * 60),
+            poll_interval: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl DeadlineConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            window: env_u64("NOTIFY_DEADLINE_WINDOW_HOURS")
+                .map(|hours| Duration::from_secs(hours * 60 * 60))
+                .unwrap_or(default.window),
+            poll_interval: env_u64("NOTIFY_DEADLINE_POLL_SECS")
+                .map(|secs| Duration::from_secs(secs.max(1)))
+                .unwrap_or(default.poll_interval),
+        }
+    }
+}
+
+// 기한 임박 알림: 진행 중 문서의 현재 결재자에게 보냅니다.
+// 단계나 기한이 바뀌면 (dedupe_key가 달라져) 다시 알립니다.
+pub struct DeadlineNotifier {
+    repo: NotificationRepository,
+    config: DeadlineConfig,
+}
+
+impl DeadlineNotifier {
+    pub fn new(pool: PgPool, config: DeadlineConfig) -> Self {
+        Self {
+            repo: NotificationRepository::new(pool),
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돕니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        loop {
+            if let Err(e) = self.notify_due_soon().await {
+                eprintln!("Deadline notifier error: {:?}", e);
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    // 새로 만든 알림 건수를 반환합니다.
+    pub async fn notify_due_soon(&self) -> Result<u64, sqlx::Error> {
+        let window =
+            chrono::Duration::from_std(self.config.window).unwrap_or(chrono::Duration::MAX);
+        let due = self.repo.find_due_soon(Utc::now() + window).await?;
+        if due.is_empty() {
+            return Ok(0);
+        }
+
+        let notifications: Vec<NewNotification> = due
+            .into_iter()
+            .map(|d| {
+                let due_at = d.due_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+                NewNotification {
+                    user_id: d.approver_id,
+                    notification_type: NotificationType::DeadlineApproaching,
+                    approval_id: Some(d.approval_id),
+                    actor_id: None,
+                    title: d.title,
+                    data: serde_json::json!({
+                        "due_at": d.due_at,
+                        "current_step": d.current_step,
+                    }),
+                    dedupe_key: format!("{}:{}:{}", d.approval_id, d.current_step, due_at),
+                }
+            })
+            .collect();
+        self.repo.insert_many(&notifications).await
+    }
+}
@@ -0,0 +1,177 @@
+use crate::domain::event::OutboxEvent;
+use crate::repositories::outbox_repository::OutboxRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+// [Outbox Dispatcher]
+// outbox_events의 pending 이벤트를 발생 순서대로 가져와 등록된 consumer 모두에게 전달합니다.
+// - 한 묶음을 FOR UPDATE SKIP LOCKED로 잠근 트랜잭션 안에서 처리하므로 여러 서버가 동시에 돌아도 됩니다.
+// - consumer 하나라도 실패하면 이벤트 전체를 backoff 후 다시 전달합니다. (at-least-once)
+//   따라서 consumer는 event.id 기준으로 멱등하게 처리해야 합니다.
+
+#[async_trait]
+pub trait EventConsumer: Send + Sync {
+    // 오류 메시지/로그용 이름
+    fn name(&self) -> &'static str;
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxConfig {
+    pub batch_size: i64,
+    pub poll_interval: Duration,
+    pub max_attempts: i32,
+    // 첫 재시도 간격 (이후 2배씩, backoff_cap까지)
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            max_attempts: 10,
+            backoff_base: Duration::from_secs(5),
+            backoff_cap: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+// 실패한 전달의 재시도 정책 (outbox 이벤트, webhook/email/chat 전달이 함께 사용)
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: i32,
+    // 첫 재시도 간격 (이후 2배씩, backoff_cap까지)
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+}
+
+impl RetryPolicy {
+    // attempts번째 시도가 실패한 뒤 다음 시도까지의 대기 시간: base * 2^(attempts-1), 최대 cap
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+        self.backoff_base
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.backoff_cap)
+    }
+
+    // attempts번째 시도가 실패한 뒤 다음 시도 시각 (max_attempts에 도달했으면 None -> dead)
+    pub fn next_attempt_at(&self, attempts: i32) -> Option<DateTime<Utc>> {
+        (attempts < self.max_attempts).then(|| {
+            Utc::now()
+                + chrono::Duration::from_std(self.backoff(attempts))
+                    .unwrap_or(chrono::Duration::MAX)
+        })
+    }
+}
+
+// 전달 worker 공통 루프 (main에서 tokio::spawn)
+// 한 묶음을 꽉 채웠다면 남은 건이 있을 수 있으므로 바로 다시, 아니면 poll_interval 후 처리합니다.
+pub async fn poll_batches<F, Fut>(
+    name: &str,
+    batch_size: i64,
+    poll_interval: Duration,
+    mut drain: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<usize, sqlx::Error>>,
+{
+    loop {
+        match drain().await {
+            Ok(handled) if handled as i64 >= batch_size => continue,
+            Ok(_) => {}
+            Err(e) => eprintln!("{} dispatcher error: {:?}", name, e),
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+// claim한 행을 다른 worker가 다시 가져가지 않도록 잡아 두는 시간(초)
+// 한 묶음을 보내는 데 걸릴 수 있는 최대 시간 + 여유 (DB 갱신 등)
+const CLAIM_LEASE_MARGIN: Duration = Duration::from_secs(30);
+
+pub fn claim_lease(max_send_time: Duration) -> f64 {
+    max_send_time
+        .saturating_add(CLAIM_LEASE_MARGIN)
+        .as_secs_f64()
+}
+
+impl OutboxConfig {
+    pub fn retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            backoff_base: self.backoff_base,
+            backoff_cap: self.backoff_cap,
+        }
+    }
+}
+
+pub struct OutboxDispatcher {
+    pool: PgPool,
+    consumers: Vec<Arc<dyn EventConsumer>>,
+    config: OutboxConfig,
+}
+
+impl OutboxDispatcher {
+    pub fn new(pool: PgPool, consumers: Vec<Arc<dyn EventConsumer>>, config: OutboxConfig) -> Self {
+        Self {
+            pool,
+            consumers,
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돌며 outbox를 비웁니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        poll_batches(
+            "Outbox",
+            self.config.batch_size,
+            self.config.poll_interval,
+            || self.drain(),
+        )
+        .await
+    }
+
+    // pending 이벤트 한 묶음을 consumer에게 전달하고 처리한 건수를 반환합니다.
+    pub async fn drain(&self) -> Result<usize, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let events = OutboxRepository::lock_due(&mut tx, self.config.batch_size).await?;
+
+        for event in &events {
+            let mut errors = Vec::new();
+            for consumer in &self.consumers {
+                if let Err(e) = consumer.handle(event).await {
+                    errors.push(format!("{}: {}", consumer.name(), e));
+                }
+            }
+
+            if errors.is_empty() {
+                OutboxRepository::mark_processed(&mut tx, event.id).await?;
+                continue;
+            }
+
+            let attempts = event.attempts + 1;
+            let error = errors.join("; ");
+            let (status, next_attempt_at) = match self.config.retry().next_attempt_at(attempts) {
+                Some(next_attempt_at) => ("pending", next_attempt_at),
+                None => {
+                    eprintln!(
+                        "Outbox event {} ({}) gave up after {} attempts: {}",
+                        event.id, event.event_type, attempts, error
+                    );
+                    ("dead", Utc::now())
+                }
+            };
+            OutboxRepository::mark_failed(&mut tx, event.id, status, next_attempt_at, error)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(events.len())
+    }
+}
@@ -0,0 +1,135 @@
+use crate::domain::email::escape_html;
+use crate::domain::user::User;
+use crate::services::email::{EmailMessage, EmailTransport};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+// [Password Reset]
+// POST /auth/password/forgot 가 만든 일회용 재설정 토큰을 사용자에게 전달합니다.
+// 기본 구현은 알림 메일과 같은 EmailTransport로 보내는 EmailResetNotifier이며,
+// SMS 등 다른 채널은 이 trait을 구현해 AppState에 주입하면 됩니다.
+
+#[async_trait]
+pub trait PasswordResetNotifier: Send + Sync {
+    // token: 재설정 토큰 원문 (DB에는 hash만 저장됨)
+    async fn send_reset(&self, user: &User, token: &str, ttl: Duration) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetConfig {
+    // 재설정 토큰 유효 시간
+    pub token_ttl: Duration,
+    // 메일 본문 링크의 기준 URL (프론트엔드 주소)
+    pub base_url: String,
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::from_secs(30 * 60),
+            base_url: "http://localhost:3000".to_string(),
+        }
+    }
+}
+
+impl PasswordResetConfig {
+    // PASSWORD_RESET_TTL_MINUTES (기본 30분), APP_BASE_URL
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            token_ttl: std::env::var("PASSWORD_RESET_TTL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|minutes| Duration::from_secs(minutes.max(1) * 60))
+                .unwrap_or(default.token_ttl),
+            base_url: std::env::var("APP_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(default.base_url),
+        }
+    }
+}
+
+// 핸들러가 State로 받는 재설정 토큰 전달 서비스
+pub struct PasswordResetService {
+    notifier: Arc<dyn PasswordResetNotifier>,
+    config: PasswordResetConfig,
+}
+
+impl PasswordResetService {
+    pub fn new(notifier: Arc<dyn PasswordResetNotifier>, config: PasswordResetConfig) -> Self {
+        Self { notifier, config }
+    }
+
+    pub fn token_ttl(&self) -> Duration {
+        self.config.token_ttl
+    }
+
+    // 응답을 기다리게 하지 않도록(메일 서버 지연, 가입 여부 추측 방지) 백그라운드로 보냅니다.
+    pub fn notify(&self, user: User, token: String) {
+        let notifier = self.notifier.clone();
+        let ttl = self.config.token_ttl;
+        tokio::spawn(async move {
+            if let Err(e) = notifier.send_reset(&user, &token, ttl).await {
+                eprintln!("Failed to send password reset to {}: {}", user.id, e);
+            }
+        });
+    }
+}
+
+// {base_url}/reset-password?token=... 링크를 메일로 보냅니다. (사용자 locale)
+pub struct EmailResetNotifier {
+    transport: Arc<dyn EmailTransport>,
+    base_url: String,
+}
+
+impl EmailResetNotifier {
+    pub fn new(transport: Arc<dyn EmailTransport>, base_url: String) -> Self {
+        Self {
+            transport,
+            base_url,
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetNotifier for EmailResetNotifier {
+    async fn send_reset(&self, user: &User, token: &str, ttl: Duration) -> Result<(), String> {
+        let link = format!("{}/reset-password?token={}", self.base_url, token);
+        let minutes = ttl.as_secs() / 60;
+        let (subject, text) = if user.locale == "en" {
+            (
+                "[PXM] Reset your password".to_string(),
+                format!(
+                    "Hi {},\n\nOpen the link below to set a new password. It expires in {} minutes and can be used once.\n\n{}\n\nIf you did not request this, you can ignore this email.",
+                    user.full_name, minutes, link
+                ),
+            )
+        } else {
+            (
+                "[PXM] 비밀번호 재설정 안내".to_string(),
+                format!(
+                    "{}님,\n\n아래 링크에서 새 비밀번호를 설정하세요. 링크는 {}분 동안 한 번만 사용할 수 있습니다.\n\n{}\n\n요청하지 않으셨다면 이 메일을 무시하셔도 됩니다.",
+                    user.full_name, minutes, link
+                ),
+            )
+        };
+        let html = format!(
+            "<p>{}</p>",
+            escape_html(&text).replace("\n\n", "</p><p>").replace(
+                &escape_html(&link),
+                &format!("<a href=\"{0}\">{0}</a>", escape_html(&link))
+            )
+        );
+
+        self.transport
+            .send(&EmailMessage {
+                to_email: user.email.clone(),
+                to_name: user.full_name.clone(),
+                subject,
+                text,
+                html,
+            })
+            .await
+    }
+}
@@ -0,0 +1,422 @@
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+    Point,
+};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+// [Approval PDF]
+// 결재 문서를 외부 서비스 없이 프로세스 안에서 PDF로 그립니다.
+// 한글 출력에는 한글 글리프가 있는 TTF/OTF 폰트가 필요합니다. (PDF_FONT_PATH, 예: NanumGothic.ttf)
+// 폰트가 없으면 PDF 기본 폰트(Helvetica)와 영문 라벨을 사용합니다. 이때 기본 폰트로 표현할 수 없는
+// 글자(한글 등)가 있는 문서는 '?'로 바꿔 그리지 않고 PdfError::MissingFont로 거부합니다.
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const STAMP_WIDTH: f32 = 26.0;
+const PT_TO_MM: f32 = 0.3528;
+
+#[derive(Debug, Error)]
+pub enum PdfError {
+    #[error("Failed to render PDF: {0}")]
+    Render(String),
+    #[error("PDF_FONT_PATH must point to a font with the '{0}' glyph to export this document")]
+    MissingFont(char),
+}
+
+impl From<printpdf::Error> for PdfError {
+    fn from(e: printpdf::Error) -> Self {
+        PdfError::Render(e.to_string())
+    }
+}
+
+// PDF에 그릴 내용 (문자열은 모두 표시용으로 가공된 값)
+#[derive(Debug, Clone)]
+pub struct ApprovalDocument {
+    pub title: String,
+    pub document_number: String,
+    pub status: String,
+    pub template_name: Option<String>,
+    pub requester_name: String,
+    pub department_name: Option<String>,
+    pub created_at: String,
+    pub fields: Vec<(String, String)>, // (라벨, 값)
+    pub stamps: Vec<StampCell>,
+    pub comments: Vec<CommentEntry>,
+}
+
+// 결재란 한 칸
+#[derive(Debug, Clone)]
+pub struct StampCell {
+    pub step_name: String,
+    pub approver_name: String,
+    pub status: String, // pending, approved, rejected
+    pub processed_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommentEntry {
+    pub actor_name: String,
+    pub action_type: String,
+    pub content: Option<String>,
+    pub created_at: String,
+}
+
+struct Labels {
+    document_number: &'static str,
+    status: &'static str,
+    template: &'static str,
+    requester: &'static str,
+    department: &'static str,
+    created_at: &'static str,
+    form: &'static str,
+    history: &'static str,
+    approved: &'static str,
+    rejected: &'static str,
+    pending: &'static str,
+}
+
+const KO_LABELS: Labels = Labels {
+    document_number: "문서번호",
+    status: "상태",
+    template: "양식",
+    requester: "기안자",
+    department: "부서",
+    created_at: "기안일",
+    form: "내용",
+    history: "결재 이력",
+    approved: "승인",
+    rejected: "반려",
+    pending: "대기",
+};
+
+const EN_LABELS: Labels = Labels {
+    document_number: "Document No.",
+    status: "Status",
+    template: "Form",
+    requester: "Requester",
+    department: "Department",
+    created_at: "Created",
+    form: "Details",
+    history: "History",
+    approved: "Approved",
+    rejected: "Rejected",
+    pending: "Pending",
+};
+
+// PDF_FONT_PATH 폰트 파일 (한 번만 읽음)
+fn external_font() -> Option<&'static [u8]> {
+    static FONT: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+    FONT.get_or_init(|| {
+        let path = std::env::var("PDF_FONT_PATH").ok()?;
+        match std::fs::read(&path) {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                eprintln!("Failed to read PDF_FONT_PATH '{}': {:?}", path, e);
+                None
+            }
+        }
+    })
+    .as_deref()
+}
+
+// 시작할 때 폰트 설정을 확인합니다. (main에서 호출, 문제가 있으면 경고만 남김)
+pub fn check_pdf_font() {
+    match std::env::var("PDF_FONT_PATH") {
+        Ok(_) if external_font().is_none() => {
+            eprintln!("PDF_FONT_PATH could not be loaded; non-Latin PDF exports will fail")
+        }
+        Ok(_) => {}
+        Err(_) => {
+            eprintln!("PDF_FONT_PATH is not set; non-Latin (e.g. Korean) PDF exports will fail")
+        }
+    }
+}
+
+// 동기 함수입니다. (printpdf 문서는 Send가 아니므로 spawn_blocking 안에서 호출)
+pub fn render_approval_pdf(document: &ApprovalDocument) -> Result<Vec<u8>, PdfError> {
+    if external_font().is_none()
+        && let Some(c) = unsupported_char(document)
+    {
+        return Err(PdfError::MissingFont(c));
+    }
+    let (doc, page, layer) =
+        PdfDocument::new(&document.title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let (font, labels) = match external_font() {
+        Some(bytes) => (doc.add_external_font(bytes)?, &KO_LABELS),
+        None => (doc.add_builtin_font(BuiltinFont::Helvetica)?, &EN_LABELS),
+    };
+
+    let mut w = Writer {
+        layer: doc.get_page(page).get_layer(layer),
+        doc,
+        font,
+        y: PAGE_HEIGHT - MARGIN,
+    };
+
+    // 1. 결재란 (오른쪽 위) + 제목
+    let stamp_bottom = w.stamp_grid(&document.stamps, labels);
+    let title_width = if document.stamps.is_empty() {
+        PAGE_WIDTH - MARGIN * 2.0
+    } else {
+        PAGE_WIDTH / 2.0 - MARGIN
+    };
+    let mut title_y = w.y - 8.0;
+    for line in wrap(&document.title, title_width, 16.0) {
+        w.text(MARGIN, title_y, 16.0, &line);
+        title_y -= 7.0;
+    }
+    w.y = title_y.min(stamp_bottom) - 4.0;
+
+    // 2. 문서 정보
+    let status = status_label(&document.status, labels);
+    let mut info = vec![
+        (labels.document_number, document.document_number.as_str()),
+        (labels.status, status),
+        (labels.requester, document.requester_name.as_str()),
+    ];
+    if let Some(department) = &document.department_name {
+        info.push((labels.department, department));
+    }
+    if let Some(template) = &document.template_name {
+        info.push((labels.template, template));
+    }
+    info.push((labels.created_at, document.created_at.as_str()));
+    for (label, value) in info {
+        w.row(label, value);
+    }
+
+    // 3. 양식 내용
+    w.section(labels.form);
+    for (label, value) in &document.fields {
+        w.row(label, value);
+    }
+
+    // 4. 결재 이력 / 코멘트
+    w.section(labels.history);
+    for entry in &document.comments {
+        let heading = format!(
+            "{}  {}  [{}]",
+            entry.created_at, entry.actor_name, entry.action_type
+        );
+        w.paragraph(&heading, 9.0, MARGIN);
+        if let Some(content) = &entry.content {
+            w.paragraph(content, 10.0, MARGIN + 5.0);
+        }
+        w.y -= 1.5;
+    }
+
+    Ok(w.doc.save_to_bytes()?)
+}
+
+// 기본 폰트(WinAnsi)로 표현할 수 없는 첫 글자
+fn unsupported_char(document: &ApprovalDocument) -> Option<char> {
+    let fields = document
+        .fields
+        .iter()
+        .flat_map(|(label, value)| [label.as_str(), value.as_str()]);
+    let stamps = document.stamps.iter().flat_map(|stamp| {
+        [
+            stamp.step_name.as_str(),
+            stamp.approver_name.as_str(),
+            stamp.status.as_str(),
+            stamp.processed_at.as_deref().unwrap_or_default(),
+        ]
+    });
+    let comments = document.comments.iter().flat_map(|entry| {
+        [
+            entry.actor_name.as_str(),
+            entry.action_type.as_str(),
+            entry.content.as_deref().unwrap_or_default(),
+        ]
+    });
+    [
+        document.title.as_str(),
+        document.document_number.as_str(),
+        document.status.as_str(),
+        document.requester_name.as_str(),
+        document.department_name.as_deref().unwrap_or_default(),
+        document.template_name.as_deref().unwrap_or_default(),
+        document.created_at.as_str(),
+    ]
+    .into_iter()
+    .chain(fields)
+    .chain(stamps)
+    .chain(comments)
+    .flat_map(str::chars)
+    .find(|c| (*c as u32) >= 0x100)
+}
+
+fn status_label<'a>(status: &'a str, labels: &'a Labels) -> &'a str {
+    match status {
+        "approved" => labels.approved,
+        "rejected" => labels.rejected,
+        "pending" => labels.pending,
+        other => other,
+    }
+}
+
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    y: f32, // 다음 줄의 위쪽 (mm, 아래에서부터)
+}
+
+impl Writer {
+    fn text(&self, x: f32, y: f32, size: f32, text: &str) {
+        self.layer.use_text(text, size, Mm(x), Mm(y), &self.font);
+    }
+
+    fn line(&self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(x1), Mm(y1)), false),
+                (Point::new(Mm(x2), Mm(y2)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    fn rect(&self, x: f32, top: f32, width: f32, height: f32) {
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(x), Mm(top)), false),
+                (Point::new(Mm(x + width), Mm(top)), false),
+                (Point::new(Mm(x + width), Mm(top - height)), false),
+                (Point::new(Mm(x), Mm(top - height)), false),
+            ],
+            is_closed: true,
+        });
+    }
+
+    // 남은 공간이 부족하면 새 페이지
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self
+                .doc
+                .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn section(&mut self, title: &str) {
+        self.ensure_space(14.0);
+        self.y -= 6.0;
+        self.text(MARGIN, self.y - 5.0, 12.0, title);
+        self.y -= 7.0;
+        self.line(MARGIN, self.y, PAGE_WIDTH - MARGIN, self.y);
+        self.y -= 2.0;
+    }
+
+    // 라벨 | 값 (값은 여러 줄로 줄바꿈)
+    fn row(&mut self, label: &str, value: &str) {
+        let label_width = 35.0;
+        let lines = wrap(value, PAGE_WIDTH - MARGIN * 2.0 - label_width, 10.0);
+        for (idx, line) in lines.iter().enumerate() {
+            self.ensure_space(6.0);
+            if idx == 0 {
+                self.text(MARGIN, self.y - 4.5, 10.0, label);
+            }
+            self.text(MARGIN + label_width, self.y - 4.5, 10.0, line);
+            self.y -= 6.0;
+        }
+    }
+
+    fn paragraph(&mut self, text: &str, size: f32, x: f32) {
+        let line_height = size * PT_TO_MM * 1.5;
+        for line in wrap(text, PAGE_WIDTH - MARGIN - x, size) {
+            self.ensure_space(line_height);
+            self.text(x, self.y - line_height * 0.75, size, &line);
+            self.y -= line_height;
+        }
+    }
+
+    // 결재란: 단계명 / 결재자 + 상태 / 처리일 3단 칸을 오른쪽 정렬로 그립니다.
+    // 한 줄에 들어가지 않으면 다음 줄로 넘기며, 가장 아래 y를 반환합니다.
+    fn stamp_grid(&mut self, stamps: &[StampCell], labels: &Labels) -> f32 {
+        let per_row = ((PAGE_WIDTH - MARGIN * 2.0) / 2.0 / STAMP_WIDTH).floor() as usize;
+        let (header, body, footer) = (6.0, 14.0, 6.0);
+        let mut top = self.y;
+
+        for row in stamps.chunks(per_row.max(1)) {
+            let mut x = PAGE_WIDTH - MARGIN - STAMP_WIDTH * row.len() as f32;
+            for stamp in row {
+                self.rect(x, top, STAMP_WIDTH, header + body + footer);
+                self.line(x, top - header, x + STAMP_WIDTH, top - header);
+                self.line(x, top - header - body, x + STAMP_WIDTH, top - header - body);
+
+                self.text(
+                    x + 1.5,
+                    top - 4.3,
+                    8.0,
+                    &fit(&stamp.step_name, STAMP_WIDTH - 3.0, 8.0),
+                );
+                self.text(
+                    x + 1.5,
+                    top - header - 5.5,
+                    9.0,
+                    &fit(&stamp.approver_name, STAMP_WIDTH - 3.0, 9.0),
+                );
+                self.text(
+                    x + 1.5,
+                    top - header - 11.5,
+                    9.0,
+                    status_label(&stamp.status, labels),
+                );
+                if let Some(processed_at) = &stamp.processed_at {
+                    self.text(
+                        x + 1.5,
+                        top - header - body - 4.3,
+                        7.0,
+                        &fit(processed_at, STAMP_WIDTH - 3.0, 7.0),
+                    );
+                }
+                x += STAMP_WIDTH;
+            }
+            top -= header + body + footer + 2.0;
+        }
+        top
+    }
+}
+
+// 글자 폭 추정 (한글/한자 등 전각 문자는 1em, 나머지는 0.55em)
+fn char_width(c: char, size: f32) -> f32 {
+    let em = size * PT_TO_MM;
+    if (c as u32) >= 0x1100 { em } else { em * 0.55 }
+}
+
+fn wrap(text: &str, max_width: f32, size: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for raw_line in text.lines() {
+        let mut line = String::new();
+        let mut width = 0.0;
+        for c in raw_line.chars() {
+            let w = char_width(c, size);
+            if width + w > max_width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                width = 0.0;
+            }
+            line.push(c);
+            width += w;
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+// 칸 너비에 맞게 자르기
+fn fit(text: &str, max_width: f32, size: f32) -> String {
+    let mut width = 0.0;
+    text.chars()
+        .take_while(|c| {
+            width += char_width(*c, size);
+            width <= max_width
+        })
+        .collect()
+}
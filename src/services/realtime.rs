@@ -0,0 +1,127 @@
+use crate::domain::event::OutboxEvent;
+use crate::domain::realtime::{UserEvent, recipients_of, summary_of};
+use crate::repositories::realtime_repository::{RealtimeRepository, USER_EVENTS_CHANNEL};
+use crate::services::outbox::EventConsumer;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+// [Realtime Fan-out]
+// 1. RealtimeConsumer: outbox 이벤트 -> 수신자별 user_events 저장 + NOTIFY (어느 인스턴스에서든)
+// 2. RealtimeHub: 모든 인스턴스가 LISTEN 하다가 받은 이벤트를 자기 SSE 연결들에 broadcast
+// 3. SSE 스트림(handlers::realtime_handler)은 자기 사용자 이벤트만 골라 내보냅니다.
+
+// user_events 보관 기간 (이보다 오래 끊겨 있던 클라이언트는 목록을 새로 조회해야 함)
+const RETENTION_DAYS: i64 = 7;
+
+pub struct RealtimeConsumer {
+    repo: RealtimeRepository,
+}
+
+impl RealtimeConsumer {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: RealtimeRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl EventConsumer for RealtimeConsumer {
+    fn name(&self) -> &'static str {
+        "realtime"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        let recipients = recipients_of(&event.payload.0, event.actor_id);
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        let data = summary_of(&event.payload.0, event.actor_id);
+        self.repo
+            .publish(event.id, &recipients, &data)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HubMessage {
+    Event(Arc<UserEvent>),
+    // LISTEN 연결이 끊겼다 다시 붙음: 그 사이 NOTIFY를 놓쳤을 수 있으니 DB에서 다시 읽어야 함
+    Resync,
+}
+
+pub struct RealtimeHub {
+    sender: broadcast::Sender<HubMessage>,
+}
+
+impl RealtimeHub {
+    pub fn new() -> Arc<Self> {
+        let (sender, _) = broadcast::channel(1024);
+        Arc::new(Self { sender })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<HubMessage> {
+        self.sender.subscribe()
+    }
+
+    // LISTEN pxm_user_events 루프 (main에서 tokio::spawn)
+    pub async fn listen(self: Arc<Self>, pool: PgPool) {
+        loop {
+            let mut listener = match PgListener::connect_with(&pool).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("Realtime listener failed to connect: {:?}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen(USER_EVENTS_CHANNEL).await {
+                eprintln!("Realtime listener failed to LISTEN: {:?}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+            // (재)연결 직후: 끊겨 있던 동안의 이벤트는 각 스트림이 DB에서 다시 읽습니다.
+            let _ = self.sender.send(HubMessage::Resync);
+
+            loop {
+                match listener.try_recv().await {
+                    Ok(Some(notification)) => {
+                        match serde_json::from_str::<UserEvent>(notification.payload()) {
+                            // 구독자가 없으면 Err -> 무시
+                            Ok(event) => {
+                                let _ = self.sender.send(HubMessage::Event(Arc::new(event)));
+                            }
+                            Err(e) => eprintln!("Invalid realtime notification: {:?}", e),
+                        }
+                    }
+                    // 연결 끊김: 새로 연결해 LISTEN 후 Resync
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("Realtime listener error: {:?}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 보관 기간이 지난 user_events를 주기적으로 삭제합니다. (main에서 tokio::spawn)
+pub async fn purge_expired_events(pool: PgPool) {
+    let repo = RealtimeRepository::new(pool);
+    let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+    loop {
+        interval.tick().await;
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(RETENTION_DAYS);
+        if let Err(e) = repo.purge_before(cutoff).await {
+            eprintln!("Failed to purge user events: {:?}", e);
+        }
+    }
+}
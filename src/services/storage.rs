@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+// [Attachment Storage]
+// 첨부파일 본문 저장소. 기본 구현은 로컬 파일시스템(LocalStorage)이며,
+// S3 등 다른 백엔드는 이 trait을 구현해 AppState에 주입하면 됩니다.
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("Object not found: {0}")]
+    NotFound(String),
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+// ATTACHMENT_DIR (기본 ./data/attachments) 아래에 key 경로 그대로 저장합니다.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("ATTACHMENT_DIR").unwrap_or_else(|_| "./data/attachments".to_string()),
+        )
+    }
+
+    // key는 상대 경로만 허용합니다. ("..", 절대 경로 차단)
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalStorage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // 임시 파일에 쓴 뒤 rename 해서 중간 상태의 파일이 보이지 않게 합니다.
+        let tmp = path.with_extension("part");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(key.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
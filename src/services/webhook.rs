@@ -0,0 +1,218 @@
+use crate::domain::event::OutboxEvent;
+use crate::domain::webhook::{DeliveryStatus, DueDelivery, WebhookEvent};
+use crate::repositories::webhook_repository::WebhookRepository;
+use crate::services::outbox::{EventConsumer, RetryPolicy, claim_lease, poll_batches};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::time::Duration;
+
+// [Webhook Delivery]
+// webhook_deliveries 테이블을 대기열로 사용합니다.
+// - WebhookConsumer: outbox 이벤트를 구독별 delivery로 저장 (전송은 하지 않음)
+// - WebhookDispatcher: 백그라운드에서 due 상태의 delivery를 가져가 전송하고,
+//   실패하면 지수 백오프로 재시도하다가 max_attempts를 넘기면 dead로 둡니다.
+//
+// 수신 측 검증: HMAC-SHA256(secret, "{X-Pxm-Timestamp}.{raw body}")를 hex로 계산해
+// X-Pxm-Signature 헤더의 "sha256=..." 값과 비교합니다.
+
+pub const SIGNATURE_HEADER: &str = "x-pxm-signature";
+pub const TIMESTAMP_HEADER: &str = "x-pxm-timestamp";
+pub const EVENT_HEADER: &str = "x-pxm-event";
+pub const DELIVERY_HEADER: &str = "x-pxm-delivery";
+
+pub struct WebhookConsumer {
+    repo: WebhookRepository,
+}
+
+impl WebhookConsumer {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: WebhookRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl EventConsumer for WebhookConsumer {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        let Some(webhook_event) = WebhookEvent::from_outbox(event) else {
+            return Ok(());
+        };
+        self.repo
+            .enqueue(&webhook_event)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+// WEBHOOK_MAX_ATTEMPTS, WEBHOOK_TIMEOUT_SECS, WEBHOOK_POLL_INTERVAL_SECS
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub max_attempts: i32,
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+    pub batch_size: i64,
+    // 첫 재시도 간격 (이후 2배씩, backoff_cap까지)
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            timeout: Duration::from_secs(10),
+            poll_interval: Duration::from_secs(5),
+            batch_size: 20,
+            backoff_base: Duration::from_secs(30),
+            backoff_cap: Duration::from_secs(6 * 60 * 60),
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            max_attempts: env_u64("WEBHOOK_MAX_ATTEMPTS")
+                .map(|v| v.max(1) as i32)
+                .unwrap_or(default.max_attempts),
+            timeout: env_u64("WEBHOOK_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+            poll_interval: env_u64("WEBHOOK_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.poll_interval),
+            ..default
+        }
+    }
+
+    pub fn retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            backoff_base: self.backoff_base,
+            backoff_cap: self.backoff_cap,
+        }
+    }
+}
+
+pub struct WebhookDispatcher {
+    repo: WebhookRepository,
+    client: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl WebhookDispatcher {
+    pub fn new(pool: PgPool, config: WebhookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .user_agent("pxm-webhooks/1.0")
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        Self {
+            repo: WebhookRepository::new(pool),
+            client,
+            config,
+        }
+    }
+
+    // 서버 실행 동안 계속 돌며 due delivery를 전송합니다. (main에서 tokio::spawn)
+    pub async fn run(self) {
+        poll_batches(
+            "Webhook",
+            self.config.batch_size,
+            self.config.poll_interval,
+            || self.deliver_due(),
+        )
+        .await
+    }
+
+    // due delivery를 한 묶음 전송하고 처리한 건수를 반환합니다.
+    pub async fn deliver_due(&self) -> Result<usize, sqlx::Error> {
+        // 한 묶음을 동시에 보내므로 전송 timeout 한 번만큼 lease를 잡아 둡니다.
+        let lease = claim_lease(self.config.timeout);
+        let deliveries = self.repo.claim_due(self.config.batch_size, lease).await?;
+        let count = deliveries.len();
+
+        let results = futures::future::join_all(
+            deliveries
+                .into_iter()
+                .map(|delivery| async move { (self.send(&delivery).await, delivery) }),
+        )
+        .await;
+
+        for (result, delivery) in results {
+            match result {
+                Ok(status) => self.repo.mark_succeeded(delivery.id, status).await?,
+                Err((status, error)) => {
+                    let (next, next_attempt_at) =
+                        match self.config.retry().next_attempt_at(delivery.attempts) {
+                            Some(next_attempt_at) => (DeliveryStatus::Pending, next_attempt_at),
+                            None => (DeliveryStatus::Dead, Utc::now()),
+                        };
+                    if next == DeliveryStatus::Dead {
+                        eprintln!(
+                            "Webhook delivery {} gave up after {} attempts: {}",
+                            delivery.id, delivery.attempts, error
+                        );
+                    }
+                    self.repo
+                        .mark_failed(delivery.id, next, next_attempt_at, status, error)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
+    // 2xx 응답이면 성공. 실패 시 (응답 코드, 오류 메시지)
+    async fn send(&self, delivery: &DueDelivery) -> Result<i32, (Option<i32>, String)> {
+        let body = serde_json::to_vec(&delivery.payload.0).map_err(|e| (None, e.to_string()))?;
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(&delivery.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| (None, e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(status.as_u16() as i32)
+        } else {
+            // 응답 본문 일부를 오류 메시지로 남깁니다.
+            let text = response.text().await.unwrap_or_default();
+            let snippet: String = text.chars().take(500).collect();
+            Err((
+                Some(status.as_u16() as i32),
+                format!("HTTP {}: {}", status, snippet),
+            ))
+        }
+    }
+}
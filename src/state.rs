@@ -0,0 +1,41 @@
+use crate::domain::attachment::AttachmentPolicy;
+use crate::services::chat::ChatConfig;
+use crate::services::password_reset::PasswordResetService;
+use crate::services::realtime::RealtimeHub;
+use crate::services::storage::AttachmentStorage;
+use axum::extract::FromRef;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+// Router 공유 상태
+// 기존 핸들러는 FromRef 덕분에 State<PgPool>을 그대로 사용할 수 있습니다.
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: PgPool,
+    pub storage: Arc<dyn AttachmentStorage>,
+    pub attachment_policy: Arc<AttachmentPolicy>,
+    // SSE 연결들에 실시간 이벤트를 나눠주는 hub (LISTEN/NOTIFY)
+    pub realtime: Arc<RealtimeHub>,
+    // 채팅 버튼 토큰 서명 키 등 (POST /chat/interactions)
+    pub chat: Arc<ChatConfig>,
+    // 비밀번호 재설정 토큰 전달 (POST /auth/password/forgot)
+    pub password_reset: Arc<PasswordResetService>,
+}
+
+impl FromRef<AppState> for PgPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ChatConfig> {
+    fn from_ref(state: &AppState) -> Self {
+        state.chat.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<PasswordResetService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.password_reset.clone()
+    }
+}
@@ -2,16 +2,65 @@ use argon2::{
     Argon2,
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
-use jsonwebtoken::{EncodingKey, Header, encode};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::OnceLock;
+use std::time::Duration;
 use uuid::Uuid;
 
+use crate::utils::jwt_keys::jwt_keys;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // User ID
     pub exp: usize,  // Expiration
+    pub iat: usize,  // Issued at
+    pub iss: String, // Issuer (JWT_ISSUER)
+    pub aud: String, // Audience (JWT_AUDIENCE)
+    pub sid: Uuid,   // Session ID (auth_sessions.id) - 폐기된 세션의 토큰은 거부
+}
+
+// 토큰 유효 시간 (JWT_ACCESS_TTL_SECS, default 15분 / REFRESH_TOKEN_TTL_DAYS, default 30일)
+#[derive(Debug, Clone, Copy)]
+pub struct TokenTtl {
+    pub access: Duration,
+    pub refresh: Duration,
+}
+
+pub fn token_ttl() -> TokenTtl {
+    static TTL: OnceLock<TokenTtl> = OnceLock::new();
+    *TTL.get_or_init(|| {
+        let env_u64 = |name: &str| env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        TokenTtl {
+            access: Duration::from_secs(env_u64("JWT_ACCESS_TTL_SECS").unwrap_or(15 * 60).max(1)),
+            refresh: Duration::from_secs(
+                env_u64("REFRESH_TOKEN_TTL_DAYS").unwrap_or(30).max(1) * 24 * 60 * 60,
+            ),
+        }
+    })
+}
+
+// refresh token 원문 (클라이언트에만 전달)과 DB에 저장할 hash
+pub fn generate_refresh_token() -> (String, String) {
+    generate_token()
+}
+
+// 일회용 무작위 토큰 원문과 hash (refresh token, 비밀번호 재설정 토큰)
+pub fn generate_token() -> (String, String) {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    let token = URL_SAFE_NO_PAD.encode(bytes);
+    let hash = hash_token(&token);
+    (token, hash)
+}
+
+// hex(SHA-256) - 충분히 긴 무작위 토큰이므로 salt 없이 조회용으로 사용합니다.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
 }
 
 pub fn hash_password(password: &str) -> Result<String, String> {
@@ -24,6 +73,12 @@ pub fn hash_password(password: &str) -> Result<String, String> {
     Ok(password_hash)
 }
 
+// 없는 계정으로 로그인할 때도 같은 시간이 걸리도록 검증에 쓰는 hash
+pub fn dummy_password_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| hash_password("dummy-password").unwrap_or_default())
+}
+
 pub fn verify_password(hash: &str, password: &str) -> bool {
     let parsed_hash = match PasswordHash::new(hash) {
         Ok(h) => h,
@@ -34,24 +89,8 @@ pub fn verify_password(hash: &str, password: &str) -> bool {
         .is_ok()
 }
 
-pub fn create_jwt(user_id: Uuid) -> Result<String, String> {
-    let expiration = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| e.to_string())?
-        .as_secs()
-        + 3600 * 24; // 24 hours
-
-    let claims = Claims {
-        sub: user_id.to_string(),
-        exp: expiration as usize,
-    };
-
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
-
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
-    )
-    .map_err(|e| e.to_string())
+// 설치된 서명 키(utils::jwt_keys)로 access token 발급
+pub fn create_jwt(user_id: Uuid, session_id: Uuid) -> Result<String, String> {
+    let keys = jwt_keys()?;
+    keys.sign(&keys.claims(user_id, session_id, token_ttl().access.as_secs())?)
 }
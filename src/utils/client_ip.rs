@@ -0,0 +1,62 @@
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+// 요청한 클라이언트 IP (로그인 실패 집계 등)
+// 기본은 TCP 연결의 주소(ConnectInfo)이고, 프록시 뒤에서는 TRUST_PROXY_HEADERS=true로
+// X-Forwarded-For를 사용합니다. 클라이언트가 보낸 값은 그대로 앞에 남으므로, 신뢰하는 프록시가
+// 덧붙인 오른쪽 끝에서 TRUSTED_PROXY_HOPS(기본 1)번째 주소를 클라이언트 IP로 봅니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+// None = 헤더를 신뢰하지 않음
+fn trusted_proxy_hops() -> Option<usize> {
+    static HOPS: OnceLock<Option<usize>> = OnceLock::new();
+    *HOPS.get_or_init(|| {
+        if !std::env::var("TRUST_PROXY_HEADERS").is_ok_and(|v| v == "true" || v == "1") {
+            return None;
+        }
+        Some(
+            std::env::var("TRUSTED_PROXY_HOPS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1)
+                .max(1),
+        )
+    })
+}
+
+// X-Forwarded-For: "{클라이언트가 보낸 값...}, {첫 프록시가 본 주소}, {다음 프록시가 본 주소}..."
+// 오른쪽에서 hops번째 값 (항목이 모자라면 None)
+pub fn forwarded_client_ip(header: &str, hops: usize) -> Option<IpAddr> {
+    let entries: Vec<&str> = header.split(',').map(str::trim).collect();
+    let index = entries.len().checked_sub(hops.max(1))?;
+    entries[index].parse().ok()
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(hops) = trusted_proxy_hops()
+            && let Some(ip) = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| forwarded_client_ip(value, hops))
+        {
+            return Ok(Self(Some(ip)));
+        }
+
+        Ok(Self(
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip()),
+        ))
+    }
+}
@@ -0,0 +1,346 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+};
+use pkcs1::der::Decode;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+use crate::utils::auth::Claims;
+
+// [JWT Keys]
+// access token 서명/검증 키. 서버 시작 시 한 번 읽어 설치하며(install_jwt_keys), 키가 없으면 시작하지 않습니다.
+// - RS256 / EdDSA: JWT_PRIVATE_KEY_FILE + JWT_PUBLIC_KEY_FILE (PEM). 알고리즘은 키 종류로 정합니다.
+// - HS256: JWT_SECRET (32바이트 이상). 다른 서비스가 토큰을 검증해야 하면 비대칭 키를 사용하세요.
+// - 키 교체: 새 키로 서명하고, 이전 공개키는 JWT_VERIFY_KEY_FILES ("path" 또는 "kid=path", 쉼표 구분)에 남겨
+//   이미 발급된 토큰이 만료될 때까지 검증합니다.
+// - header에 kid, claims에 iss(JWT_ISSUER) / aud(JWT_AUDIENCE)를 넣고 검증 시 확인합니다.
+// - 공개키는 GET /.well-known/jwks.json 으로 공개합니다.
+
+const MIN_SECRET_BYTES: usize = 32;
+const RSA_OID: &str = "1.2.840.113549.1.1.1";
+const ED25519_OID: &str = "1.3.101.112";
+
+pub const DEFAULT_ISSUER: &str = "pxm";
+pub const DEFAULT_AUDIENCE: &str = "pxm-api";
+
+// JWKS에 공개하는 공개키 (RFC 7517)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Jwk {
+    pub kty: &'static str,
+    pub kid: String,
+    #[serde(rename = "use")]
+    pub key_use: &'static str,
+    pub alg: &'static str,
+    // RSA
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    // Ed25519 (OKP)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+struct VerificationKey {
+    kid: String,
+    algorithm: Algorithm,
+    decoding: DecodingKey,
+    // HS256 키는 공개하지 않음
+    jwk: Option<Jwk>,
+}
+
+pub struct JwtKeys {
+    signing_kid: String,
+    algorithm: Algorithm,
+    encoding: EncodingKey,
+    // 서명 키(첫 번째) + 교체 전 키들
+    keys: Vec<VerificationKey>,
+    issuer: String,
+    audience: String,
+}
+
+static JWT_KEYS: OnceLock<JwtKeys> = OnceLock::new();
+
+// 서버 시작 시 한 번 (이미 설치되어 있으면 false)
+pub fn install_jwt_keys(keys: JwtKeys) -> bool {
+    JWT_KEYS.set(keys).is_ok()
+}
+
+pub fn jwt_keys() -> Result<&'static JwtKeys, String> {
+    JWT_KEYS
+        .get()
+        .ok_or_else(|| "JWT keys are not installed".to_string())
+}
+
+impl JwtKeys {
+    // JWT_PRIVATE_KEY_FILE, JWT_PUBLIC_KEY_FILE, JWT_SECRET, JWT_KEY_ID, JWT_VERIFY_KEY_FILES,
+    // JWT_ISSUER, JWT_AUDIENCE
+    pub fn from_env() -> Result<Self, String> {
+        let kid = env::var("JWT_KEY_ID").ok().filter(|kid| !kid.is_empty());
+        let keys = match (env::var("JWT_PRIVATE_KEY_FILE"), env::var("JWT_SECRET")) {
+            (Ok(private_path), secret) => {
+                if secret.is_ok() {
+                    eprintln!("JWT_SECRET is ignored because JWT_PRIVATE_KEY_FILE is set");
+                }
+                let public_path = env::var("JWT_PUBLIC_KEY_FILE").map_err(|_| {
+                    "JWT_PUBLIC_KEY_FILE must be set together with JWT_PRIVATE_KEY_FILE".to_string()
+                })?;
+                Self::asymmetric(&read_key(&private_path)?, &read_key(&public_path)?, kid)?
+            }
+            (Err(_), Ok(secret)) => Self::hmac(secret.as_bytes(), kid)?,
+            (Err(_), Err(_)) => {
+                return Err("JWT key material is missing: set JWT_PRIVATE_KEY_FILE and \
+                     JWT_PUBLIC_KEY_FILE (RS256/EdDSA) or JWT_SECRET (HS256)"
+                    .to_string());
+            }
+        };
+
+        let mut keys = keys.with_claims(
+            env::var("JWT_ISSUER").unwrap_or_else(|_| DEFAULT_ISSUER.to_string()),
+            env::var("JWT_AUDIENCE").unwrap_or_else(|_| DEFAULT_AUDIENCE.to_string()),
+        );
+        if let Ok(files) = env::var("JWT_VERIFY_KEY_FILES") {
+            for entry in files.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (kid, path) = match entry.split_once('=') {
+                    Some((kid, path)) => (Some(kid.trim().to_string()), path.trim()),
+                    None => (None, entry),
+                };
+                keys = keys.with_verification_key(&read_key(path)?, kid)?;
+            }
+        }
+
+        Ok(keys)
+    }
+
+    // HS256 공유 비밀키
+    pub fn hmac(secret: &[u8], kid: Option<String>) -> Result<Self, String> {
+        if secret.len() < MIN_SECRET_BYTES {
+            return Err(format!(
+                "JWT_SECRET must be at least {} bytes",
+                MIN_SECRET_BYTES
+            ));
+        }
+        let kid = kid.unwrap_or_else(|| "hs256".to_string());
+
+        Ok(Self {
+            signing_kid: kid.clone(),
+            algorithm: Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret),
+            keys: vec![VerificationKey {
+                kid,
+                algorithm: Algorithm::HS256,
+                decoding: DecodingKey::from_secret(secret),
+                jwk: None,
+            }],
+            issuer: DEFAULT_ISSUER.to_string(),
+            audience: DEFAULT_AUDIENCE.to_string(),
+        })
+    }
+
+    // RS256 / EdDSA 키쌍 (PEM). kid가 없으면 공개키의 JWK thumbprint (RFC 7638)
+    pub fn asymmetric(
+        private_pem: &[u8],
+        public_pem: &[u8],
+        kid: Option<String>,
+    ) -> Result<Self, String> {
+        let key = verification_key(public_pem, kid)?;
+        let encoding = match key.algorithm {
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(private_pem),
+            _ => EncodingKey::from_ed_pem(private_pem),
+        }
+        .map_err(|e| format!("Invalid JWT private key: {}", e))?;
+
+        let keys = Self {
+            signing_kid: key.kid.clone(),
+            algorithm: key.algorithm,
+            encoding,
+            keys: vec![key],
+            issuer: DEFAULT_ISSUER.to_string(),
+            audience: DEFAULT_AUDIENCE.to_string(),
+        };
+        // 개인키와 공개키가 한 쌍인지 시작 시 확인
+        let probe = keys.sign(&keys.claims(Uuid::nil(), Uuid::nil(), 60)?)?;
+        keys.verify(&probe)
+            .map_err(|_| "JWT private key does not match the public key".to_string())?;
+
+        Ok(keys)
+    }
+
+    // 교체 전 공개키 (검증과 JWKS 공개에만 사용)
+    pub fn with_verification_key(
+        mut self,
+        public_pem: &[u8],
+        kid: Option<String>,
+    ) -> Result<Self, String> {
+        let key = verification_key(public_pem, kid)?;
+        if self.keys.iter().any(|k| k.kid == key.kid) {
+            return Err(format!("Duplicate JWT key id '{}'", key.kid));
+        }
+        self.keys.push(key);
+        Ok(self)
+    }
+
+    pub fn with_claims(mut self, issuer: String, audience: String) -> Self {
+        self.issuer = issuer;
+        self.audience = audience;
+        self
+    }
+
+    pub fn signing_kid(&self) -> &str {
+        &self.signing_kid
+    }
+
+    // ttl_secs 뒤 만료되는 access token claims
+    pub fn claims(&self, user_id: Uuid, session_id: Uuid, ttl_secs: u64) -> Result<Claims, String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| e.to_string())?
+            .as_secs();
+
+        Ok(Claims {
+            sub: user_id.to_string(),
+            exp: (now + ttl_secs) as usize,
+            iat: now as usize,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            sid: session_id,
+        })
+    }
+
+    pub fn sign(&self, claims: &Claims) -> Result<String, String> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.signing_kid.clone());
+        encode(&header, claims, &self.encoding).map_err(|e| e.to_string())
+    }
+
+    // kid로 키를 고르고 서명, exp, iss, aud를 확인합니다.
+    pub fn verify(&self, token: &str) -> Result<Claims, String> {
+        let header = decode_header(token).map_err(|e| e.to_string())?;
+        let kid = header.kid.ok_or("Token has no kid")?;
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.kid == kid)
+            .ok_or_else(|| format!("Unknown key id '{}'", kid))?;
+        if header.alg != key.algorithm {
+            return Err("Token algorithm does not match its key".to_string());
+        }
+
+        let mut validation = Validation::new(key.algorithm);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "sub", "iss", "aud"]);
+        decode::<Claims>(token, &key.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| e.to_string())
+    }
+
+    // GET /.well-known/jwks.json
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.keys.iter().filter_map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+fn read_key(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read JWT key file {}: {}", path, e))
+}
+
+// PEM 공개키 (SPKI "PUBLIC KEY" 또는 PKCS#1 "RSA PUBLIC KEY")
+fn verification_key(public_pem: &[u8], kid: Option<String>) -> Result<VerificationKey, String> {
+    let pem = pem::parse(public_pem).map_err(|e| format!("Invalid JWT public key: {}", e))?;
+    let (algorithm, mut jwk) = match pem.tag() {
+        "RSA PUBLIC KEY" => (Algorithm::RS256, rsa_jwk(pem.contents())?),
+        "PUBLIC KEY" => {
+            let spki = spki::SubjectPublicKeyInfoRef::from_der(pem.contents())
+                .map_err(|e| format!("Invalid JWT public key: {}", e))?;
+            let key = spki
+                .subject_public_key
+                .as_bytes()
+                .ok_or("Invalid JWT public key bits")?;
+            match spki.algorithm.oid.to_string().as_str() {
+                RSA_OID => (Algorithm::RS256, rsa_jwk(key)?),
+                ED25519_OID => (Algorithm::EdDSA, ed25519_jwk(key)?),
+                oid => return Err(format!("Unsupported JWT key algorithm {}", oid)),
+            }
+        }
+        tag => return Err(format!("Expected a PUBLIC KEY PEM, found '{}'", tag)),
+    };
+    jwk.kid = kid.unwrap_or_else(|| thumbprint(&jwk));
+
+    let decoding = match algorithm {
+        Algorithm::RS256 => DecodingKey::from_rsa_components(
+            jwk.n.as_deref().unwrap_or_default(),
+            jwk.e.as_deref().unwrap_or_default(),
+        ),
+        _ => DecodingKey::from_ed_components(jwk.x.as_deref().unwrap_or_default()),
+    }
+    .map_err(|e| format!("Invalid JWT public key: {}", e))?;
+
+    Ok(VerificationKey {
+        kid: jwk.kid.clone(),
+        algorithm,
+        decoding,
+        jwk: Some(jwk),
+    })
+}
+
+fn rsa_jwk(der: &[u8]) -> Result<Jwk, String> {
+    let key =
+        pkcs1::RsaPublicKey::from_der(der).map_err(|e| format!("Invalid RSA public key: {}", e))?;
+    if key.modulus.as_bytes().len() < 256 {
+        return Err("RSA keys must be at least 2048 bits".to_string());
+    }
+
+    Ok(Jwk {
+        kty: "RSA",
+        kid: String::new(),
+        key_use: "sig",
+        alg: "RS256",
+        n: Some(URL_SAFE_NO_PAD.encode(key.modulus.as_bytes())),
+        e: Some(URL_SAFE_NO_PAD.encode(key.public_exponent.as_bytes())),
+        crv: None,
+        x: None,
+    })
+}
+
+fn ed25519_jwk(key: &[u8]) -> Result<Jwk, String> {
+    if key.len() != 32 {
+        return Err("Invalid Ed25519 public key".to_string());
+    }
+
+    Ok(Jwk {
+        kty: "OKP",
+        kid: String::new(),
+        key_use: "sig",
+        alg: "EdDSA",
+        n: None,
+        e: None,
+        crv: Some("Ed25519"),
+        x: Some(URL_SAFE_NO_PAD.encode(key)),
+    })
+}
+
+// RFC 7638: 필수 멤버만 사전순으로 담은 JSON의 SHA-256
+fn thumbprint(jwk: &Jwk) -> String {
+    let canonical = match (&jwk.n, &jwk.e, &jwk.x) {
+        (Some(n), Some(e), _) => format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, e, n),
+        (_, _, Some(x)) => format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#, x),
+        _ => String::new(),
+    };
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
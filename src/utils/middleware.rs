@@ -5,15 +5,17 @@ use axum::{
     middleware::Next,
     response::Response,
 };
-use jsonwebtoken::{DecodingKey, Validation, decode};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::utils::auth::Claims;
+use crate::domain::role::Permission;
+use crate::domain::user::{AuthUser, STATUS_ACTIVE};
+use crate::repositories::session_repository::SessionRepository;
+use crate::services::auth_context::auth_cache;
+use crate::utils::jwt_keys::jwt_keys;
 
-// State를 사용하지 않더라도 미들웨어 시그니처를 맞추기 위해 둠 (필요 시 DB 조회 등 확장에 대비)
 pub async fn auth_middleware(
-    State(_pool): State<PgPool>,
+    State(pool): State<PgPool>,
     mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
@@ -30,21 +32,68 @@ pub async fn auth_middleware(
     }
     let token = &auth_header[7..];
 
-    // 3. JWT 검증
-    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
-    let validation = Validation::default();
+    // 3. JWT 검증 (kid로 키 선택, 서명/exp/iss/aud 확인)
+    let claims = jwt_keys()
+        .map_err(|e| {
+            eprintln!("Failed to verify token: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .verify(token)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &validation,
-    )
-    .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    // 4. 세션 확인 (로그아웃/폐기된 세션의 토큰은 만료 전이라도 거부)
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let session_id = claims.sid;
+    let active = SessionRepository::new(pool.clone())
+        .is_active(session_id, user_id)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to check session: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !active {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
-    // 4. 요청에 User ID 주입 (Extension 사용)
-    let user_id = Uuid::parse_str(&token_data.claims.sub).map_err(|_| StatusCode::UNAUTHORIZED)?;
-    req.extensions_mut().insert(user_id);
+    // 5. 사용자 상태/부서/역할 (짧은 캐시, 권한 회수와 계정 정지가 곧바로 반영됨)
+    // ACTIVE가 아닌 계정(SUSPENDED, INACTIVE 등)은 유효한 토큰이 있어도 거부합니다.
+    let user = auth_cache()
+        .load(&pool, user_id)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to load auth context: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    if user.status != STATUS_ACTIVE {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    req.extensions_mut().insert(AuthUser {
+        id: user_id,
+        department_id: user.department_id,
+        roles: user.roles,
+        session_id,
+    });
+
+    // 6. 다음 핸들러로 진행
+    Ok(next.run(req).await)
+}
+
+// 라우트별 권한 검사 레이어 (auth_middleware 안쪽에서 실행되어야 함)
+// 사용 예: .route_layer(from_fn_with_state(Permission::TemplateManage, require_permission))
+pub async fn require_permission(
+    State(permission): State<Permission>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let user = req
+        .extensions()
+        .get::<AuthUser>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if !user.has(permission) {
+        return Err(StatusCode::FORBIDDEN);
+    }
 
-    // 5. 다음 핸들러로 진행
     Ok(next.run(req).await)
 }
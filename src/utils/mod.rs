@@ -1,2 +1,5 @@
 pub mod auth;
+pub mod client_ip;
+pub mod jwt_keys;
 pub mod middleware;
+pub mod timezone;
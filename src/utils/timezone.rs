@@ -0,0 +1,15 @@
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+// 조직 기준 타임존 (ORG_TIMEZONE, 예: "Asia/Seoul"). 설정이 없거나 잘못되면 UTC.
+// 결재 제목의 날짜 등 사용자에게 보여지는 날짜 계산에 사용합니다.
+pub fn org_timezone() -> Tz {
+    static ORG_TZ: OnceLock<Tz> = OnceLock::new();
+    *ORG_TZ.get_or_init(|| match std::env::var("ORG_TIMEZONE") {
+        Ok(name) => name.parse().unwrap_or_else(|_| {
+            eprintln!("Invalid ORG_TIMEZONE '{}', falling back to UTC", name);
+            Tz::UTC
+        }),
+        Err(_) => Tz::UTC,
+    })
+}
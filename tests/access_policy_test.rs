@@ -0,0 +1,161 @@
+use backend::domain::approval::{ApprovalListQuery, ApprovalStep, FlowProcess};
+use backend::domain::role::UserRoles;
+use backend::establish_connection;
+use backend::repositories::approval_repository::{ApprovalRepository, ApprovalScope};
+use backend::repositories::user_repository::UserRepository;
+use backend::services::access_policy::ApprovalAccessPolicy;
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_approval_visibility_rules() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let approval_repo = ApprovalRepository::new(pool.clone());
+    let user_repo = UserRepository::new(pool.clone());
+    let policy = ApprovalAccessPolicy::new(pool.clone());
+
+    // 1. 사용자 준비: 요청자, 결재자, 참조자, 상위 부서장, 무관한 사용자
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let mut users = Vec::new();
+    for name in ["requester", "approver", "reference", "director", "stranger"] {
+        let user = user_repo
+            .create(
+                format!("{name}-{suffix}@pxm.com"),
+                "hash".to_string(),
+                name.to_string(),
+                None,
+                None,
+            )
+            .await
+            .expect("Failed to create user");
+        users.push(user.id);
+    }
+    let [requester, approver, reference, director, stranger] = users[..] else {
+        unreachable!()
+    };
+
+    // 2. 부서 계층: division(부서장 = director) -> team(요청자 소속)
+    let (division_id, team_id) = (Uuid::new_v4(), Uuid::new_v4());
+    sqlx::query("INSERT INTO departments (id, name, code, manager_id) VALUES ($1, $2, $2, $3)")
+        .bind(division_id)
+        .bind(format!("DIV-{suffix}"))
+        .bind(director)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO departments (id, name, code, parent_id) VALUES ($1, $2, $2, $3)")
+        .bind(team_id)
+        .bind(format!("TEAM-{suffix}"))
+        .bind(division_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET department_id = $1 WHERE id = $2")
+        .bind(team_id)
+        .bind(requester)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // 3. 결재 요청
+    let request = approval_repo
+        .create(
+            "Visibility Test".to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Approval".to_string(),
+                    approver_id: approver,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                }],
+                references: vec![reference],
+            },
+        )
+        .await
+        .expect("Failed to create approval request");
+
+    // 4. 단건 판정과 목록 필터가 같은 결과를 내야 함
+    let no_roles = UserRoles::default();
+    for (viewer, expected) in [
+        (requester, true),
+        (approver, true),
+        (reference, true),
+        (director, true),
+        (stranger, false),
+    ] {
+        assert_eq!(
+            policy.can_view(viewer, &no_roles, &request).await.unwrap(),
+            expected
+        );
+
+        let listed = approval_repo
+            .find_page(
+                ApprovalScope::Visible {
+                    viewer_id: viewer,
+                    roles: &no_roles,
+                },
+                &ApprovalListQuery {
+                    requester_id: Some(requester),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(listed.items.iter().any(|r| r.id == request.id), expected);
+    }
+
+    // 5. approval:read_all 권한은 모든 문서 열람
+    let auditor = UserRoles {
+        roles: vec!["AUDITOR".to_string()],
+        permissions: ["approval:read_all".to_string()].into_iter().collect(),
+    };
+    assert!(policy.can_view(stranger, &auditor, &request).await.unwrap());
+
+    // 6. 임시저장 문서는 요청자 본인만 (결재자/참조자/부서장/read_all 모두 불가)
+    let draft = approval_repo
+        .create_draft(
+            "Draft Visibility Test".to_string(),
+            requester,
+            serde_json::json!({}),
+            request.flow_process.0.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+    for (viewer, roles, expected) in [
+        (requester, &no_roles, true),
+        (approver, &no_roles, false),
+        (reference, &no_roles, false),
+        (director, &no_roles, false),
+        (stranger, &auditor, false),
+    ] {
+        assert_eq!(
+            policy.can_view(viewer, roles, &draft).await.unwrap(),
+            expected
+        );
+
+        let listed = approval_repo
+            .find_page(
+                ApprovalScope::Visible {
+                    viewer_id: viewer,
+                    roles,
+                },
+                &ApprovalListQuery {
+                    requester_id: Some(requester),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(listed.items.iter().any(|r| r.id == draft.id), expected);
+    }
+}
@@ -19,6 +19,7 @@ async fn test_create_and_find_approval() {
     let flow_process = FlowProcess {
         current_step: 1,
         steps: vec![],
+        references: vec![],
     };
 
     // 2. 생성 (Create)
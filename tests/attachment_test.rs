@@ -0,0 +1,161 @@
+use backend::domain::approval::FlowProcess;
+use backend::domain::attachment::{AttachmentPolicy, detect_content_type, sanitize_file_name};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::attachment_repository::{AttachmentRepository, NewAttachment};
+use backend::services::storage::{AttachmentStorage, LocalStorage, StorageError};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_local_storage_roundtrip_and_key_validation() {
+    let root = env::temp_dir().join(format!("pxm-attachments-{}", Uuid::new_v4()));
+    let storage = LocalStorage::new(&root);
+
+    storage.put("a/b.bin", b"receipt").await.unwrap();
+    assert_eq!(storage.get("a/b.bin").await.unwrap(), b"receipt");
+    storage.delete("a/b.bin").await.unwrap();
+    assert!(matches!(
+        storage.get("a/b.bin").await,
+        Err(StorageError::NotFound(_))
+    ));
+
+    // 저장소 밖 경로 차단
+    assert!(matches!(
+        storage.put("../escape", b"x").await,
+        Err(StorageError::InvalidKey(_))
+    ));
+    assert!(matches!(
+        storage.get("/etc/passwd").await,
+        Err(StorageError::InvalidKey(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn test_attachment_policy_and_file_names() {
+    let policy = AttachmentPolicy {
+        max_bytes: 10,
+        ..Default::default()
+    };
+    assert!(policy.check("application/pdf", 10).is_ok());
+    assert!(policy.check("application/pdf", 11).is_err());
+    assert!(policy.check("application/pdf", 0).is_err());
+    assert!(policy.check("application/x-msdownload", 1).is_err());
+
+    assert_eq!(sanitize_file_name("../../etc/영수증.pdf"), "영수증.pdf");
+    assert_eq!(sanitize_file_name("C:\\temp\\a\"b.png"), "ab.png");
+    assert_eq!(sanitize_file_name(".."), "attachment");
+}
+
+#[test]
+fn test_content_type_detected_from_bytes() {
+    assert_eq!(
+        detect_content_type("receipt.png", b"%PDF-1.7\n"),
+        "application/pdf"
+    );
+    assert_eq!(
+        detect_content_type("scan", b"\x89PNG\r\n\x1a\n\0\0"),
+        "image/png"
+    );
+    assert_eq!(
+        detect_content_type("photo.jpg", &[0xFF, 0xD8, 0xFF, 0xE0]),
+        "image/jpeg"
+    );
+    assert_eq!(
+        detect_content_type("budget.xlsx", b"PK\x03\x04rest"),
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+    assert_eq!(
+        detect_content_type("bundle.zip", b"PK\x03\x04rest"),
+        "application/zip"
+    );
+    assert_eq!(
+        detect_content_type(
+            "report.hwp",
+            &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]
+        ),
+        "application/x-hwp"
+    );
+    assert_eq!(
+        detect_content_type("items.csv", "품목,금액\n".as_bytes()),
+        "text/csv"
+    );
+    assert_eq!(detect_content_type("memo.txt", b"hello\r\n"), "text/plain");
+
+    // 확장자만 바꾼 실행 파일/바이너리는 허용 목록에 없는 형식으로 판별
+    assert_eq!(
+        detect_content_type("invoice.pdf", b"MZ\x90\0"),
+        "application/octet-stream"
+    );
+    assert_eq!(
+        detect_content_type("memo.txt", b"\0\x01binary"),
+        "application/octet-stream"
+    );
+    assert!(
+        AttachmentPolicy::default()
+            .check(detect_content_type("invoice.pdf", b"MZ\x90\0"), 4)
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_attachments_locked_after_submit() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let approval_repo = ApprovalRepository::new(pool.clone());
+    let repo = AttachmentRepository::new(pool);
+
+    let requester = Uuid::new_v4();
+    let request = approval_repo
+        .create_draft(
+            "Attachment Test".to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let new_attachment = || {
+        let id = Uuid::new_v4();
+        NewAttachment {
+            id,
+            approval_id: request.id,
+            file_name: "receipt.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 3,
+            sha256: "0".repeat(64),
+            storage_key: format!("{}/{}", request.id, id),
+            uploaded_by: requester,
+        }
+    };
+
+    // 1. 임시저장 상태: 첨부 가능
+    let attachment = repo.create(new_attachment()).await.unwrap().unwrap();
+    assert_eq!(repo.find_by_approval(request.id).await.unwrap().len(), 1);
+
+    // 2. 상신 후: 추가/삭제 불가
+    approval_repo.submit(request.id).await.unwrap().unwrap();
+    assert!(repo.create(new_attachment()).await.unwrap().is_none());
+    assert!(
+        repo.delete(request.id, attachment.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        repo.find_by_id(request.id, attachment.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
@@ -0,0 +1,200 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use backend::domain::session::RefreshDto;
+use backend::domain::user::LoginDto;
+use backend::establish_connection;
+use backend::handlers::auth_handler::{login, logout, refresh};
+use backend::repositories::user_repository::UserRepository;
+use backend::services::auth_context::auth_cache;
+use backend::utils::auth::hash_password;
+use backend::utils::client_ip::ClientIp;
+use backend::utils::jwt_keys::{JwtKeys, install_jwt_keys};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+mod common;
+use common::{TEST_JWT_SECRET, me, start_server};
+
+#[tokio::test]
+async fn test_refresh_token_rotation_and_logout() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let server = start_server(
+        pool.clone(),
+        Router::new().route("/auth/logout", post(logout)),
+    )
+    .await;
+
+    let email = format!("session_{}@pxm.com", Uuid::new_v4().simple());
+    UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Session User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let credentials = || {
+        Json(LoginDto {
+            email: email.clone(),
+            password: "password123".to_string(),
+        })
+    };
+
+    // 1. 로그인 -> access token으로 보호된 API 사용
+    let Json(session) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        credentials(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(me(&server, &session.token).await, StatusCode::OK);
+
+    // 2. refresh -> 새 토큰 쌍, 이전 access token도 세션이 살아 있는 동안은 유효
+    let Json(rotated) = refresh(
+        State(pool.clone()),
+        Json(RefreshDto {
+            refresh_token: session.refresh_token.clone(),
+        }),
+    )
+    .await
+    .unwrap();
+    assert_ne!(rotated.refresh_token, session.refresh_token);
+    assert_eq!(me(&server, &rotated.token).await, StatusCode::OK);
+
+    // 3. 이미 사용한 refresh token 재사용 -> 거부 + 세션 전체 폐기
+    let reused = refresh(
+        State(pool.clone()),
+        Json(RefreshDto {
+            refresh_token: session.refresh_token.clone(),
+        }),
+    )
+    .await;
+    assert_eq!(reused.unwrap_err().0, StatusCode::UNAUTHORIZED);
+    assert_eq!(me(&server, &rotated.token).await, StatusCode::UNAUTHORIZED);
+    let after_reuse = refresh(
+        State(pool.clone()),
+        Json(RefreshDto {
+            refresh_token: rotated.refresh_token.clone(),
+        }),
+    )
+    .await;
+    assert_eq!(after_reuse.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+    // 4. 로그아웃 -> 해당 세션만 폐기
+    let Json(first) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        credentials(),
+    )
+    .await
+    .unwrap();
+    let Json(second) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        credentials(),
+    )
+    .await
+    .unwrap();
+    let client = reqwest::Client::new();
+    let status = client
+        .post(format!("{}/auth/logout", server))
+        .bearer_auth(&first.token)
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(me(&server, &first.token).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(me(&server, &second.token).await, StatusCode::OK);
+    let refreshed = refresh(
+        State(pool.clone()),
+        Json(RefreshDto {
+            refresh_token: first.refresh_token,
+        }),
+    )
+    .await;
+    assert_eq!(refreshed.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+    // 5. 모든 기기에서 로그아웃
+    let status = client
+        .post(format!("{}/auth/logout", server))
+        .bearer_auth(&second.token)
+        .json(&serde_json::json!({ "everywhere": true }))
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(me(&server, &second.token).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_inactive_user_is_rejected() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let server = start_server(
+        pool.clone(),
+        Router::new().route("/auth/logout", post(logout)),
+    )
+    .await;
+
+    let email = format!("status_{}@pxm.com", Uuid::new_v4().simple());
+    let repo = UserRepository::new(pool.clone());
+    let user = repo
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Status User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let credentials = || {
+        Json(LoginDto {
+            email: email.clone(),
+            password: "password123".to_string(),
+        })
+    };
+    let Json(session) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        credentials(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(me(&server, &session.token).await, StatusCode::OK);
+
+    // 1. 정지된 계정 -> 기존 토큰도 거부, 새 로그인 불가
+    repo.set_status(user.id, "SUSPENDED").await.unwrap();
+    auth_cache().invalidate(user.id);
+    assert_eq!(me(&server, &session.token).await, StatusCode::FORBIDDEN);
+    let denied = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        credentials(),
+    )
+    .await;
+    assert_eq!(denied.unwrap_err().0, StatusCode::FORBIDDEN);
+
+    // 2. 다시 활성화 -> 기존 세션 사용 가능
+    repo.set_status(user.id, "ACTIVE").await.unwrap();
+    auth_cache().invalidate(user.id);
+    assert_eq!(me(&server, &session.token).await, StatusCode::OK);
+}
@@ -0,0 +1,482 @@
+use axum::body::Bytes;
+use axum::extract::{Extension, Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::routing::post;
+use axum::{Json, Router};
+use backend::domain::approval::{ApprovalStep, FlowProcess};
+use backend::domain::chat::{
+    ChatAction, ChatTokenError, Interaction, InteractionToken, SlashCommand, slack_signature,
+    verify_shared_token, verify_slack_signature,
+};
+use backend::domain::role::UserRoles;
+use backend::domain::user::AuthUser;
+use backend::establish_connection;
+use backend::handlers::chat_handler::{chat_command, chat_interaction, create_chat_link_code};
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::chat_repository::ChatRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::chat::{ChatConfig, ChatConsumer, ChatDispatcher};
+use backend::services::outbox::EventConsumer;
+use dotenvy::dotenv;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+type Received = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+
+const SLACK_SECRET: &str = "test-slack-signing-secret";
+const MATTERMOST_TOKEN: &str = "test-mattermost-token";
+
+// 로컬 incoming webhook stub: POST /{channel} 본문을 모아 둡니다.
+async fn start_chat_stub() -> (String, Received) {
+    let received: Received = Arc::default();
+    let app = Router::new()
+        .route(
+            "/{channel}",
+            post(
+                |State(received): State<Received>,
+                 Path(channel): Path<String>,
+                 Json(body): Json<serde_json::Value>| async move {
+                    received.lock().unwrap().push((channel, body));
+                    "ok"
+                },
+            ),
+        )
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, received)
+}
+
+fn messages(received: &Received, channel: &str) -> Vec<serde_json::Value> {
+    received
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(c, _)| c == channel)
+        .map(|(_, body)| body.clone())
+        .collect()
+}
+
+// 버튼 토큰 (0 = 승인, 1 = 반려)
+fn button_token(message: &serde_json::Value, index: usize) -> String {
+    message["attachments"][0]["actions"][index]["integration"]["context"]["token"]
+        .as_str()
+        .expect("message has no buttons")
+        .to_string()
+}
+
+// form 본문은 Slack 요청으로 보고 서명 헤더를 붙입니다. (timestamp_offset: 현재 시각과의 차이)
+fn chat_headers(content_type: &'static str, body: &str, timestamp_offset: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    if content_type.starts_with("application/x-www-form-urlencoded") {
+        let timestamp = (chrono::Utc::now().timestamp() + timestamp_offset).to_string();
+        let signature = slack_signature(SLACK_SECRET, &timestamp, body.as_bytes());
+        headers.insert("x-slack-request-timestamp", timestamp.parse().unwrap());
+        headers.insert("x-slack-signature", signature.parse().unwrap());
+    }
+    headers
+}
+
+async fn click(
+    pool: &sqlx::PgPool,
+    config: &Arc<ChatConfig>,
+    content_type: &'static str,
+    body: String,
+) -> Result<serde_json::Value, (StatusCode, String)> {
+    let headers = chat_headers(content_type, &body, 0);
+    chat_interaction(
+        State(pool.clone()),
+        State(config.clone()),
+        headers,
+        Bytes::from(body),
+    )
+    .await
+    .map(|Json(body)| body)
+}
+
+// Slack slash command: /pxm link {code}
+async fn link(
+    pool: &sqlx::PgPool,
+    config: &Arc<ChatConfig>,
+    chat_user_id: &str,
+    code: &str,
+) -> String {
+    let body = format!(
+        "command=%2Fpxm&user_id={}&text=link+{}",
+        chat_user_id,
+        code.to_lowercase()
+    );
+    let headers = chat_headers("application/x-www-form-urlencoded", &body, 0);
+    let Json(response) = chat_command(
+        State(pool.clone()),
+        State(config.clone()),
+        headers,
+        Bytes::from(body),
+    )
+    .await
+    .unwrap();
+    response["text"].as_str().unwrap().to_string()
+}
+
+async fn link_code(pool: &sqlx::PgPool, config: &Arc<ChatConfig>, user_id: Uuid) -> String {
+    let (status, Json(body)) = create_chat_link_code(
+        State(pool.clone()),
+        State(config.clone()),
+        Extension(AuthUser {
+            id: user_id,
+            department_id: None,
+            roles: UserRoles::default(),
+            session_id: Uuid::new_v4(),
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    body["code"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_interaction_token_and_payload_parsing() {
+    let token = InteractionToken {
+        approval_id: Uuid::new_v4(),
+        step: 2,
+        approver_id: Uuid::new_v4(),
+        action: ChatAction::Reject,
+        channel: true,
+        expires_at: 1_000,
+    };
+    let signed = token.sign("secret");
+    assert_eq!(InteractionToken::verify(&signed, "secret", 999), Ok(token));
+    assert_eq!(
+        InteractionToken::verify(&signed, "other", 999),
+        Err(ChatTokenError::BadSignature)
+    );
+    assert_eq!(
+        InteractionToken::verify(&signed, "secret", 1_001),
+        Err(ChatTokenError::Expired)
+    );
+    assert_eq!(
+        InteractionToken::verify("garbage", "secret", 0),
+        Err(ChatTokenError::Malformed)
+    );
+
+    // Mattermost (JSON) / Slack (form payload)
+    let mattermost = Interaction::parse(
+        "application/json",
+        br#"{"user_id":"mm1","context":{"token":"t.1"}}"#,
+    )
+    .unwrap();
+    assert_eq!(mattermost.token, "t.1");
+    assert_eq!(mattermost.chat_user_id.as_deref(), Some("mm1"));
+    let slack = Interaction::parse(
+        "application/x-www-form-urlencoded",
+        b"payload=%7B%22user%22%3A%7B%22id%22%3A%22U1%22%7D%2C%22actions%22%3A%5B%7B%22value%22%3A%22t.2%22%7D%5D%7D",
+    )
+    .unwrap();
+    assert_eq!(slack.token, "t.2");
+    assert_eq!(slack.chat_user_id.as_deref(), Some("U1"));
+
+    // Slack 요청 서명: 본문이 바뀌거나 timestamp가 replay window 밖이면 거부
+    let signature = slack_signature("secret", "1000", b"body");
+    assert_eq!(
+        verify_slack_signature("secret", "1000", &signature, b"body", 1_100),
+        Ok(())
+    );
+    assert_eq!(
+        verify_slack_signature("secret", "1000", &signature, b"bodx", 1_100),
+        Err(ChatTokenError::Unverified)
+    );
+    assert_eq!(
+        verify_slack_signature("other", "1000", &signature, b"body", 1_100),
+        Err(ChatTokenError::Unverified)
+    );
+    assert_eq!(
+        verify_slack_signature("secret", "1000", &signature, b"body", 1_000 + 301),
+        Err(ChatTokenError::Stale)
+    );
+    assert_eq!(verify_shared_token("token", Some("token")), Ok(()));
+    assert_eq!(
+        verify_shared_token("token", Some("tokem")),
+        Err(ChatTokenError::Unverified)
+    );
+    assert_eq!(
+        verify_shared_token("token", None),
+        Err(ChatTokenError::Unverified)
+    );
+
+    let command = SlashCommand::parse(b"token=t&user_id=U1&command=%2Fpxm&text=link+ab12").unwrap();
+    assert_eq!(command.token.as_deref(), Some("t"));
+    assert_eq!(command.user_id, "U1");
+    assert_eq!(command.command, "/pxm");
+    assert_eq!(command.text, "link ab12");
+}
+
+#[tokio::test]
+async fn test_chat_messages_and_button_callbacks() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let users = UserRepository::new(pool.clone());
+    let chat = ChatRepository::new(pool.clone());
+    let approvals = ApprovalRepository::new(pool.clone());
+    let outbox = OutboxRepository::new(pool.clone());
+    let (stub_url, received) = start_chat_stub().await;
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let mut created = Vec::new();
+    for name in ["requester", "first", "second"] {
+        created.push(
+            users
+                .create(
+                    format!("chat_{}_{}@pxm.com", name, suffix),
+                    "x".into(),
+                    format!("Chat {}", name),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap(),
+        );
+    }
+    let (requester, first, second) = (&created[0], &created[1], &created[2]);
+
+    // 1단계 결재자의 개인 webhook + 배정/승인 알림을 받는 채널 webhook
+    let dm = format!("dm-{}", suffix);
+    let channel = format!("channel-{}", suffix);
+    chat.create_webhook(
+        Some(first.id),
+        "DM".into(),
+        format!("{}/{}", stub_url, dm),
+        "en".into(),
+        vec![],
+        first.id,
+    )
+    .await
+    .unwrap();
+    let channel_webhook = chat
+        .create_webhook(
+            None,
+            "Approvals".into(),
+            format!("{}/{}", stub_url, channel),
+            "ko".into(),
+            vec!["approved".into(), "assigned".into()],
+            requester.id,
+        )
+        .await
+        .unwrap();
+
+    let config = Arc::new(ChatConfig {
+        batch_size: 100_000,
+        backoff_base: Duration::ZERO,
+        secret: "test-secret".to_string(),
+        slack_signing_secret: Some(SLACK_SECRET.to_string()),
+        mattermost_token: Some(MATTERMOST_TOKEN.to_string()),
+        ..ChatConfig::default()
+    });
+    let consumer = ChatConsumer::new(pool.clone(), config.clone());
+    let dispatcher = ChatDispatcher::new(pool.clone(), config.clone());
+    let deliver = |approval_id: Uuid| {
+        let (outbox, consumer, dispatcher) = (&outbox, &consumer, &dispatcher);
+        async move {
+            // 같은 이벤트를 다시 받아도 메시지는 한 번만
+            let events = outbox.find_by_approval(approval_id).await.unwrap();
+            for event in events.iter().chain(events.iter()) {
+                consumer.handle(event).await.unwrap();
+            }
+            dispatcher.deliver_due().await.unwrap();
+        }
+    };
+
+    let request = approvals
+        .create(
+            "Chat request".to_string(),
+            requester.id,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: [first.id, second.id]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, approver_id)| ApprovalStep {
+                        seq: i as i32 + 1,
+                        name: format!("Step {}", i + 1),
+                        approver_id: *approver_id,
+                        status: "pending".to_string(),
+                        timestamp: None,
+                    })
+                    .collect(),
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    // 1. 배정 -> 개인 DM(영어)과 채널(한국어)에 버튼이 붙은 메시지
+    deliver(request.id).await;
+    let dm_messages = messages(&received, &dm);
+    assert_eq!(dm_messages.len(), 1);
+    assert_eq!(dm_messages[0]["text"], "Approval needed: Chat request");
+    assert_eq!(
+        dm_messages[0]["attachments"][0]["actions"][0]["name"],
+        "Approve"
+    );
+    assert_eq!(messages(&received, &channel).len(), 1);
+
+    // 2. 개인 DM의 승인 버튼 (Mattermost 형식)
+    let mm_first = format!("mm-first-{}", suffix);
+    let context = &dm_messages[0]["attachments"][0]["actions"][0]["integration"]["context"];
+    assert_eq!(context["verify_token"], MATTERMOST_TOKEN);
+    let approve_body = serde_json::json!({
+        "user_id": mm_first,
+        "context": context,
+    })
+    .to_string();
+
+    // Mattermost 공유 토큰이 없으면 (채팅 서버를 거치지 않은 요청) 401
+    let direct = serde_json::json!({
+        "user_id": mm_first,
+        "context": { "token": button_token(&dm_messages[0], 0) },
+    })
+    .to_string();
+    let direct = click(&pool, &config, "application/json", direct).await;
+    assert_eq!(direct.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+    // 채팅 계정을 연결하지 않으면 개인 메시지 버튼도 사용할 수 없음
+    let unlinked = click(&pool, &config, "application/json", approve_body.clone()).await;
+    assert_eq!(unlinked.unwrap_err().0, StatusCode::FORBIDDEN);
+
+    // 연결 코드를 발급받아 채팅에서 "/pxm link {code}" -> 채팅 서버가 확인한 ID로 연결
+    let code = link_code(&pool, &config, first.id).await;
+    assert_eq!(
+        link(&pool, &config, &mm_first, &code).await,
+        "🔗 채팅 계정이 연결되었습니다."
+    );
+    assert_eq!(
+        chat.find_account(first.id).await.unwrap(),
+        Some(mm_first.clone())
+    );
+    // 코드는 한 번만 사용
+    assert!(
+        link(&pool, &config, "U-ATTACKER", &code)
+            .await
+            .contains("expired")
+    );
+
+    let response = click(&pool, &config, "application/json", approve_body.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        response["update"]["message"],
+        "✅ 승인했습니다: Chat request"
+    );
+    let updated = approvals.find_by_id(request.id).await.unwrap().unwrap();
+    assert_eq!(updated.flow_process.0.current_step, 2);
+
+    // 같은 버튼을 다시 누르면 409
+    let again = click(&pool, &config, "application/json", approve_body).await;
+    assert_eq!(again.unwrap_err().0, StatusCode::CONFLICT);
+
+    // 서명이 바뀐 토큰은 401
+    let forged = serde_json::json!({
+        "context": {
+            "token": format!("{}00", button_token(&dm_messages[0], 1)),
+            "verify_token": MATTERMOST_TOKEN,
+        },
+    })
+    .to_string();
+    let forged = click(&pool, &config, "application/json", forged).await;
+    assert_eq!(forged.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+    // 3. 2단계 배정 -> 채널 메시지의 반려 버튼 (Slack 형식)
+    deliver(request.id).await;
+    let channel_messages = messages(&received, &channel);
+    assert_eq!(channel_messages.len(), 2);
+    let slack_click = |user_id: &str| {
+        let payload = serde_json::json!({
+            "user": { "id": user_id },
+            "actions": [{ "value": button_token(&channel_messages[1], 1) }],
+        })
+        .to_string();
+        format!(
+            "payload={}",
+            percent_encoding::utf8_percent_encode(&payload, percent_encoding::NON_ALPHANUMERIC)
+        )
+    };
+
+    // Slack 서명이 없거나 오래된 요청은 401
+    let body = slack_click(&format!("U-SECOND-{}", suffix));
+    let mut unsigned = chat_headers("application/x-www-form-urlencoded", &body, 0);
+    unsigned.remove("x-slack-signature");
+    let stale = chat_headers("application/x-www-form-urlencoded", &body, -600);
+    for headers in [unsigned, stale] {
+        let result = chat_interaction(
+            State(pool.clone()),
+            State(config.clone()),
+            headers,
+            Bytes::from(body.clone()),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+
+    // 채팅 계정을 연결하지 않으면 채널 버튼은 사용할 수 없음
+    let unlinked = click(
+        &pool,
+        &config,
+        "application/x-www-form-urlencoded",
+        slack_click(&format!("U-SECOND-{}", suffix)),
+    )
+    .await;
+    assert_eq!(unlinked.unwrap_err().0, StatusCode::FORBIDDEN);
+
+    let code = link_code(&pool, &config, second.id).await;
+    link(&pool, &config, &format!("U-SECOND-{}", suffix), &code).await;
+    // 다른 사람이 누르면 거부
+    let other = click(
+        &pool,
+        &config,
+        "application/x-www-form-urlencoded",
+        slack_click("U-SOMEONE"),
+    )
+    .await;
+    assert_eq!(other.unwrap_err().0, StatusCode::FORBIDDEN);
+
+    // 정지/비활성 계정은 연결된 채팅 계정으로도 결재할 수 없음
+    for status in ["SUSPENDED", "INACTIVE"] {
+        users.set_status(second.id, status).await.unwrap();
+        let inactive = click(
+            &pool,
+            &config,
+            "application/x-www-form-urlencoded",
+            slack_click(&format!("U-SECOND-{}", suffix)),
+        )
+        .await;
+        assert_eq!(inactive.unwrap_err().0, StatusCode::FORBIDDEN);
+    }
+    let untouched = approvals.find_by_id(request.id).await.unwrap().unwrap();
+    assert_eq!(untouched.status, "pending");
+    users.set_status(second.id, "ACTIVE").await.unwrap();
+
+    let response = click(
+        &pool,
+        &config,
+        "application/x-www-form-urlencoded",
+        slack_click(&format!("U-SECOND-{}", suffix)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response["text"], "❌ 반려했습니다: Chat request");
+    let rejected = approvals.find_by_id(request.id).await.unwrap().unwrap();
+    assert_eq!(rejected.status, "rejected");
+
+    // 채널은 반려 알림을 구독하지 않음
+    deliver(request.id).await;
+    assert_eq!(messages(&received, &channel).len(), 2);
+
+    chat.delete_webhook(channel_webhook.id, None).await.unwrap();
+}
@@ -0,0 +1,36 @@
+use axum::Router;
+use axum::extract::Extension;
+use axum::middleware::from_fn_with_state;
+use axum::routing::get;
+use backend::domain::user::AuthUser;
+use backend::utils::middleware::auth_middleware;
+use sqlx::PgPool;
+
+// 인증 통합 테스트 공통 fixture (auth_session_test, password_test)
+
+pub const TEST_JWT_SECRET: &[u8] = b"test-only-jwt-secret-0123456789abcdef";
+
+// auth_middleware 뒤의 보호된 라우트 (/me) + 테스트별 라우트
+pub async fn start_server(pool: PgPool, routes: Router<PgPool>) -> String {
+    let app = routes
+        .route(
+            "/me",
+            get(|Extension(user): Extension<AuthUser>| async move { user.id.to_string() }),
+        )
+        .layer(from_fn_with_state(pool.clone(), auth_middleware))
+        .with_state(pool);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+pub async fn me(server: &str, token: &str) -> reqwest::StatusCode {
+    reqwest::Client::new()
+        .get(format!("{}/me", server))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
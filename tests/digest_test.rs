@@ -0,0 +1,270 @@
+use async_trait::async_trait;
+use backend::domain::approval::{ApprovalAction, ApprovalStep, FlowProcess};
+use backend::domain::digest::{Digest, PendingDigestItem, format_wait, parse_send_time};
+use backend::domain::email::{DIGEST_TEMPLATE, EmailTemplate};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::digest_repository::DigestRepository;
+use backend::repositories::email_repository::EmailRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::digest::{DigestConfig, DigestScheduler, local_day_range};
+use backend::services::email::{EmailMessage, EmailTransport};
+use chrono::{Days, Utc};
+use dotenvy::dotenv;
+use std::env;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+// 보낸 메일을 모아 두는 transport (fail이면 항상 실패)
+#[derive(Default)]
+struct Outbox {
+    sent: Mutex<Vec<EmailMessage>>,
+    fail: bool,
+}
+
+#[async_trait]
+impl EmailTransport for Outbox {
+    async fn send(&self, message: &EmailMessage) -> Result<(), String> {
+        if self.fail {
+            return Err("connection refused".to_string());
+        }
+        self.sent.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+impl Outbox {
+    fn sent_to(&self, email: &str) -> Vec<EmailMessage> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.to_email == email)
+            .cloned()
+            .collect()
+    }
+}
+
+fn step(seq: i32, approver_id: Uuid) -> ApprovalStep {
+    ApprovalStep {
+        seq,
+        name: format!("Step {}", seq),
+        approver_id,
+        status: "pending".to_string(),
+        timestamp: None,
+    }
+}
+
+#[test]
+fn test_digest_formatting() {
+    assert_eq!(format_wait(chrono::Duration::minutes(42), true), "42m");
+    assert_eq!(
+        format_wait(chrono::Duration::minutes(26 * 60 + 5), false),
+        "1일 2시간"
+    );
+    assert_eq!(
+        parse_send_time("07:30").unwrap(),
+        parse_send_time("07:30:00").unwrap()
+    );
+    assert!(parse_send_time("25:00").is_err());
+
+    // 문구는 템플릿에서, 목록 값은 escape (작은따옴표 포함)
+    let template = EmailTemplate {
+        notification_type: DIGEST_TEMPLATE.to_string(),
+        locale: "en".to_string(),
+        subject: "{{date}}: {{pending_count}}".to_string(),
+        body_text: "{{recipient}}\n{{pending_items}}{{status_changes}}".to_string(),
+        body_html: "<p>{{recipient}}</p>{{pending_items}}{{status_changes}}".to_string(),
+        updated_by: None,
+        updated_at: Utc::now(),
+    };
+    let now = Utc::now();
+    let id = Uuid::new_v4();
+    let digest = Digest {
+        pending: vec![PendingDigestItem {
+            id,
+            title: "O'Brien <trip>".to_string(),
+            requester_name: Some("Kim".to_string()),
+            step_name: None,
+            waiting_since: now,
+        }],
+        pending_total: 1,
+        changes: vec![],
+    };
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+    let rendered = digest
+        .render(
+            &template,
+            "en",
+            "Lee & co",
+            date,
+            "https://pxm.example",
+            now,
+        )
+        .unwrap();
+    assert_eq!(rendered.subject, "2026-02-01: 1");
+    assert_eq!(
+        rendered.text,
+        format!(
+            "Lee & co\n- O'Brien <trip> (Kim · - · waiting 0m)\n  https://pxm.example/approvals/{}\n- None.\n",
+            id
+        )
+    );
+    assert!(rendered.html.starts_with("<p>Lee &amp; co</p><ul><li>"));
+    assert!(rendered.html.contains("O&#39;Brien &lt;trip&gt;"));
+}
+
+#[tokio::test]
+async fn test_daily_digest_is_sent_once_per_local_day() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let users = UserRepository::new(pool.clone());
+    let approvals = ApprovalRepository::new(pool.clone());
+    let digests = DigestRepository::new(pool.clone());
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let mut created = Vec::new();
+    for name in ["requester", "approver", "idle"] {
+        created.push(
+            users
+                .create(
+                    format!("digest_{}_{}@pxm.com", name, suffix),
+                    "x".into(),
+                    format!("Digest {}", name),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap(),
+        );
+    }
+    let (requester, approver, idle) = (&created[0], &created[1], &created[2]);
+    EmailRepository::new(pool.clone())
+        .set_user_locale(approver.id, "en")
+        .await
+        .unwrap();
+
+    // 1. 결재자에게 대기 중인 문서 1건
+    approvals
+        .create(
+            "Waiting request".to_string(),
+            requester.id,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![step(1, approver.id)],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    // 2. 요청자 문서가 어제(UTC) 최종 승인됨
+    let mut approved = approvals
+        .create(
+            "Approved yesterday".to_string(),
+            requester.id,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![step(1, idle.id)],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    approved
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, idle.id)
+        .unwrap();
+    approved.status = "approved".to_string();
+    let approved = approvals
+        .record_action(approved, idle.id, ApprovalAction::Approve, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let today = Utc::now().date_naive();
+    let (yesterday_start, _) = local_day_range(chrono_tz::UTC, today - Days::new(1));
+    sqlx::query(
+        "UPDATE approval_logs SET created_at = $2 WHERE approval_id = $1 AND action_type = 'APPROVED'",
+    )
+    .bind(approved.id)
+    .bind(yesterday_start + chrono::Duration::hours(12))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // 요청자/결재자는 신청 (00:00 = 언제나 send_time 이후), idle은 신청 안 함
+    let midnight = parse_send_time("00:00").unwrap();
+    for user in [requester, approver] {
+        digests
+            .upsert_settings(user.id, true, midnight, Some("UTC"))
+            .await
+            .unwrap();
+    }
+    digests
+        .upsert_settings(idle.id, false, midnight, None)
+        .await
+        .unwrap();
+
+    let config = DigestConfig {
+        batch_size: 100_000,
+        base_url: "https://pxm.example".to_string(),
+        ..DigestConfig::default()
+    };
+
+    // 3. 전송 실패 -> 오늘 보낸 것으로 남지 않음
+    let failing = Arc::new(Outbox {
+        fail: true,
+        ..Outbox::default()
+    });
+    DigestScheduler::new(pool.clone(), failing, config.clone())
+        .send_due()
+        .await
+        .unwrap();
+    let settings = digests.find_settings(approver.id).await.unwrap().unwrap();
+    assert_eq!(settings.last_sent_on, None);
+
+    // 4. 성공 -> 사용자별 한 통, 같은 날 다시 돌아도 보내지 않음
+    let outbox = Arc::new(Outbox::default());
+    let scheduler = DigestScheduler::new(pool.clone(), outbox.clone(), config);
+    scheduler.send_due().await.unwrap();
+    scheduler.send_due().await.unwrap();
+
+    let approver_mail = outbox.sent_to(&approver.email);
+    assert_eq!(approver_mail.len(), 1);
+    assert!(approver_mail[0].subject.contains("1 waiting for you"));
+    assert!(
+        approver_mail[0]
+            .text
+            .contains("Waiting request (Digest requester · Step 1 · waiting 0m)")
+    );
+    assert!(!approver_mail[0].text.contains("Approved yesterday"));
+
+    let requester_mail = outbox.sent_to(&requester.email);
+    assert_eq!(requester_mail.len(), 1);
+    assert!(requester_mail[0].subject.contains("대기 0건"));
+    assert!(
+        requester_mail[0]
+            .text
+            .contains("Approved yesterday (Digest idle · 최종 승인)")
+    );
+    assert!(
+        requester_mail[0]
+            .text
+            .contains(&format!("https://pxm.example/approvals/{}", approved.id))
+    );
+
+    assert!(outbox.sent_to(&idle.email).is_empty());
+    assert_eq!(
+        digests
+            .find_settings(approver.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .last_sent_on,
+        Some(today)
+    );
+}
@@ -0,0 +1,238 @@
+use backend::domain::approval::{ApprovalStep, FlowProcess};
+use backend::domain::email::{
+    EmailContext, EmailTemplate, EmailTemplateError, UpdateEmailTemplateDto,
+};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::email_repository::EmailRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::email::{EmailConfig, EmailDispatcher, SmtpConfig, SmtpTls, SmtpTransport};
+use backend::services::notification::NotificationConsumer;
+use backend::services::outbox::EventConsumer;
+use chrono::Utc;
+use dotenvy::dotenv;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use uuid::Uuid;
+
+type Mailbox = Arc<Mutex<Vec<String>>>;
+
+// 로컬 SMTP sink: 모든 명령에 성공으로 답하고 DATA 내용을 모아 둡니다.
+async fn start_smtp_sink() -> (u16, Mailbox) {
+    let mailbox: Mailbox = Arc::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received = mailbox.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((socket, _)) = listener.accept().await else {
+                return;
+            };
+            let received = received.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = socket.into_split();
+                let mut lines = BufReader::new(read).lines();
+                write.write_all(b"220 sink ready\r\n").await.unwrap();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let command = line.to_uppercase();
+                    let reply: &[u8] = if command.starts_with("EHLO") {
+                        b"250-sink\r\n250 8BITMIME\r\n"
+                    } else if command.starts_with("DATA") {
+                        write.write_all(b"354 end with .\r\n").await.unwrap();
+                        let mut data = Vec::new();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            if line == "." {
+                                break;
+                            }
+                            data.push(line);
+                        }
+                        received.lock().unwrap().push(data.join("\n"));
+                        b"250 queued\r\n"
+                    } else if command.starts_with("QUIT") {
+                        let _ = write.write_all(b"221 bye\r\n").await;
+                        return;
+                    } else {
+                        b"250 OK\r\n"
+                    };
+                    write.write_all(reply).await.unwrap();
+                }
+            });
+        }
+    });
+    (port, mailbox)
+}
+
+fn smtp(port: u16) -> SmtpConfig {
+    SmtpConfig {
+        host: "127.0.0.1".to_string(),
+        port,
+        tls: SmtpTls::None,
+        username: None,
+        password: None,
+        from: "PXM <no-reply@pxm.local>".to_string(),
+        timeout: Duration::from_secs(5),
+    }
+}
+
+async fn delivery_state(pool: &sqlx::PgPool, recipient: Uuid) -> (String, i32, Option<String>) {
+    sqlx::query_as(
+        r#"
+        SELECT d.status, d.attempts, d.last_error
+        FROM email_deliveries d JOIN notifications n ON n.id = d.notification_id
+        WHERE n.user_id = $1
+        "#,
+    )
+    .bind(recipient)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[test]
+fn test_email_template_rendering() {
+    let template = EmailTemplate {
+        notification_type: "assigned".to_string(),
+        locale: "en".to_string(),
+        subject: "[Approval] {{ title }}\n".to_string(),
+        body_text: "{{recipient}}: {{title}} by {{requester}} ({{step_name}}) {{link}}".to_string(),
+        body_html: "<b>{{title}}</b>".to_string(),
+        updated_by: None,
+        updated_at: Utc::now(),
+    };
+    let rendered = template
+        .render(&EmailContext {
+            title: "<Laptop> & mouse".to_string(),
+            requester: "Kim".to_string(),
+            step_name: "Team Lead".to_string(),
+            link: "http://app/approvals/1".to_string(),
+            recipient: "Lee".to_string(),
+            ..EmailContext::default()
+        })
+        .unwrap();
+    assert_eq!(rendered.subject, "[Approval] <Laptop> & mouse ");
+    assert_eq!(
+        rendered.text,
+        "Lee: <Laptop> & mouse by Kim (Team Lead) http://app/approvals/1"
+    );
+    assert_eq!(rendered.html, "<b>&lt;Laptop&gt; &amp; mouse</b>");
+
+    let invalid = UpdateEmailTemplateDto {
+        subject: "{{title}}".to_string(),
+        body_text: "{{amount}}".to_string(),
+        body_html: "{{title".to_string(),
+    };
+    assert_eq!(
+        invalid.validate(),
+        Err(EmailTemplateError::Unknown("amount".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_notification_emails_are_queued_and_sent_over_smtp() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let users = UserRepository::new(pool.clone());
+    let email_repo = EmailRepository::new(pool.clone());
+    let (port, mailbox) = start_smtp_sink().await;
+
+    // 영어 메일을 받는 결재자
+    let suffix = Uuid::new_v4().simple().to_string();
+    let requester = users
+        .create(
+            format!("requester_{}@pxm.com", suffix),
+            "x".into(),
+            "Requester Kim".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let approver = users
+        .create(
+            format!("approver_{}@pxm.com", suffix),
+            "x".into(),
+            "Approver Lee".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(email_repo.set_user_locale(approver.id, "en").await.unwrap());
+
+    // 1. 상신 -> 결재자에게 "assigned" 알림 + 메일 예약
+    let request = ApprovalRepository::new(pool.clone())
+        .create(
+            "Laptop <purchase>".to_string(),
+            requester.id,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Team Lead".to_string(),
+                    approver_id: approver.id,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                }],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    let consumer = NotificationConsumer::new(pool.clone());
+    for event in OutboxRepository::new(pool.clone())
+        .find_by_approval(request.id)
+        .await
+        .unwrap()
+    {
+        consumer.handle(&event).await.unwrap();
+    }
+    assert_eq!(delivery_state(&pool, approver.id).await.0, "pending");
+
+    let config = EmailConfig {
+        batch_size: 100_000,
+        backoff_base: Duration::ZERO,
+        base_url: "https://pxm.example".to_string(),
+        ..EmailConfig::default()
+    };
+
+    // 2. SMTP 서버가 없으면 실패 기록 후 재시도 대기 (결재 데이터에는 영향 없음)
+    let closed_port = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let down = EmailDispatcher::new(
+        pool.clone(),
+        Arc::new(SmtpTransport::new(&smtp(closed_port)).unwrap()),
+        config.clone(),
+    );
+    down.deliver_due().await.unwrap();
+    let (status, attempts, last_error) = delivery_state(&pool, approver.id).await;
+    assert_eq!((status.as_str(), attempts), ("pending", 1));
+    assert!(last_error.is_some());
+
+    // 3. sink로 재전송 -> 영어 템플릿으로 렌더링
+    let dispatcher = EmailDispatcher::new(
+        pool.clone(),
+        Arc::new(SmtpTransport::new(&smtp(port)).unwrap()),
+        config,
+    );
+    dispatcher.deliver_due().await.unwrap();
+    let (status, attempts, _) = delivery_state(&pool, approver.id).await;
+    assert_eq!((status.as_str(), attempts), ("sent", 2));
+
+    let messages = mailbox.lock().unwrap().clone();
+    let message = messages
+        .iter()
+        .find(|m| m.contains(&approver.email))
+        .expect("email not received");
+    assert!(message.contains("Subject: [Approval needed] Laptop <purchase>"));
+    assert!(message.contains("Hi Approver Lee, a request from Requester Kim"));
+    assert!(message.contains("Step: Team Lead"));
+    assert!(message.contains(&format!("https://pxm.example/approvals/{}", request.id)));
+    assert!(message.contains("Laptop &lt;purchase&gt;"));
+}
@@ -0,0 +1,97 @@
+use backend::domain::approval::{ApprovalListQuery, ApprovalStep, FlowProcess};
+use backend::domain::role::UserRoles;
+use backend::establish_connection;
+use backend::repositories::approval_repository::{ApprovalRepository, ApprovalScope};
+use backend::services::export::{ExportColumns, completed_at, csv_cell};
+use chrono::Utc;
+use dotenvy::dotenv;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_export_rows_flatten_form_fields() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool);
+
+    // 1. 승인 완료된 문서
+    let (requester, approver) = (Uuid::new_v4(), Uuid::new_v4());
+    let approved_at = Utc::now();
+    let mut request = repo
+        .create(
+            "Expense".to_string(),
+            requester,
+            serde_json::json!({ "amount": 12000, "trip": { "city": "Busan" } }),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Lead".to_string(),
+                    approver_id: approver,
+                    status: "approved".to_string(),
+                    timestamp: Some(approved_at),
+                }],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    request.status = "approved".to_string();
+    repo.update(request).await.unwrap();
+
+    // 2. 목록 필터와 같은 조건으로 스트리밍
+    let roles = UserRoles::default();
+    let query = ApprovalListQuery {
+        requester_id: Some(requester),
+        status: Some("approved".to_string()),
+        ..Default::default()
+    };
+    let rows: Vec<_> = repo
+        .stream_all(
+            ApprovalScope::Visible {
+                viewer_id: requester,
+                roles: &roles,
+            },
+            &query,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(completed_at(&rows[0]).is_some());
+
+    // 3. 선택한 form_data 경로가 스키마 라벨로 펼쳐짐
+    let schema = serde_json::json!({
+        "fields": [
+            { "key": "amount", "label": "Amount" },
+            { "key": "trip.city", "label": "City" }
+        ]
+    });
+    let columns = ExportColumns::new(chrono_tz::Tz::UTC, Some(&schema), Some("trip.city, amount"));
+    let header = columns.header();
+    assert_eq!(header[header.len() - 2..], ["City", "Amount"]);
+
+    let names = HashMap::from([(approver, "Lee".to_string())]);
+    let row = columns.row(&rows[0], &names);
+    assert_eq!(row[row.len() - 2..], ["Busan", "12000"]);
+    assert!(row.contains(&"Lead: Lee (approved)".to_string()));
+}
+
+#[test]
+fn test_csv_cell_escapes_formulas_but_not_negative_numbers() {
+    assert_eq!(csv_cell("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+    assert_eq!(csv_cell("+1"), "'+1");
+    assert_eq!(csv_cell("@cmd"), "'@cmd");
+    assert_eq!(csv_cell("-1+2"), "'-1+2");
+    assert_eq!(csv_cell("-"), "'-");
+    assert_eq!(csv_cell("-.5"), "'-.5");
+    assert_eq!(csv_cell("-1e3"), "'-1e3");
+
+    assert_eq!(csv_cell("-1200"), "-1200");
+    assert_eq!(csv_cell("-3.5"), "-3.5");
+    assert_eq!(csv_cell("12000"), "12000");
+    assert_eq!(csv_cell("Busan"), "Busan");
+}
@@ -0,0 +1,89 @@
+use backend::domain::approval::{
+    ApprovalAction, ApprovalListQuery, ApprovalStep, FlowProcess, InboxBox,
+};
+use backend::establish_connection;
+use backend::repositories::approval_repository::{ApprovalRepository, ApprovalScope};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+fn step(seq: i32, approver_id: Uuid) -> ApprovalStep {
+    ApprovalStep {
+        seq,
+        name: format!("Step {seq}"),
+        approver_id,
+        status: "pending".to_string(),
+        timestamp: None,
+    }
+}
+
+#[tokio::test]
+async fn test_inbox_boxes_follow_current_step() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool);
+
+    let (requester, first, second) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let flow_process = FlowProcess {
+        current_step: 1,
+        steps: vec![step(1, first), step(2, second)],
+        references: vec![],
+    };
+
+    // 1. 상신 문서 1건 + 임시저장 1건
+    let request = repo
+        .create(
+            "Inbox Test".to_string(),
+            requester,
+            serde_json::json!({}),
+            flow_process.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(request.status, "pending");
+    repo.create_draft(
+        "Inbox Draft".to_string(),
+        requester,
+        serde_json::json!({}),
+        flow_process,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let counts = repo.count_inbox(requester).await.unwrap();
+    assert_eq!((counts.requested, counts.drafts), (1, 1));
+
+    // 2. 1단계 결재자에게만 결재 대기
+    let query = ApprovalListQuery {
+        requester_id: Some(requester),
+        ..Default::default()
+    };
+    let pending_first = repo
+        .find_page(
+            ApprovalScope::Inbox {
+                user_id: first,
+                inbox: InboxBox::Pending,
+            },
+            &query,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(pending_first.items.iter().any(|r| r.id == request.id));
+    assert_eq!(repo.count_inbox(second).await.unwrap().pending, 0);
+
+    // 3. 1단계 승인 후 2단계 결재자 차례
+    let mut request = request;
+    request
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, first)
+        .unwrap();
+    repo.update(request).await.unwrap();
+
+    let first_counts = repo.count_inbox(first).await.unwrap();
+    assert_eq!((first_counts.pending, first_counts.processed), (0, 1));
+    assert_eq!(repo.count_inbox(second).await.unwrap().pending, 1);
+}
@@ -0,0 +1,98 @@
+use backend::utils::jwt_keys::JwtKeys;
+use uuid::Uuid;
+
+const RS256_PRIVATE: &[u8] = include_bytes!("fixtures/jwt/rs256_private.pem");
+const RS256_PUBLIC: &[u8] = include_bytes!("fixtures/jwt/rs256_public.pem");
+const RS256_OLD_PRIVATE: &[u8] = include_bytes!("fixtures/jwt/rs256_old_private.pem");
+const RS256_OLD_PUBLIC: &[u8] = include_bytes!("fixtures/jwt/rs256_old_public.pem");
+const ED25519_PRIVATE: &[u8] = include_bytes!("fixtures/jwt/ed25519_private.pem");
+const ED25519_PUBLIC: &[u8] = include_bytes!("fixtures/jwt/ed25519_public.pem");
+
+fn issue(keys: &JwtKeys) -> String {
+    keys.sign(&keys.claims(Uuid::new_v4(), Uuid::new_v4(), 60).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn test_key_material_is_required() {
+    assert!(JwtKeys::hmac(b"secret", None).is_err());
+    assert!(JwtKeys::hmac(b"0123456789abcdef0123456789abcdef", None).is_ok());
+    // 짝이 맞지 않는 키쌍은 거부
+    assert!(JwtKeys::asymmetric(RS256_PRIVATE, RS256_OLD_PUBLIC, None).is_err());
+    assert!(JwtKeys::asymmetric(RS256_PRIVATE, b"not a pem", None).is_err());
+}
+
+#[test]
+fn test_rs256_and_eddsa_tokens() {
+    for (private, public, alg) in [
+        (RS256_PRIVATE, RS256_PUBLIC, "RS256"),
+        (ED25519_PRIVATE, ED25519_PUBLIC, "EdDSA"),
+    ] {
+        let keys = JwtKeys::asymmetric(private, public, None).unwrap();
+        let token = issue(&keys);
+        let header = jsonwebtoken::decode_header(&token).unwrap();
+        assert_eq!(header.kid.as_deref(), Some(keys.signing_kid()));
+        assert_eq!(format!("{:?}", header.alg), alg);
+        let claims = keys.verify(&token).unwrap();
+        assert_eq!(claims.iss, "pxm");
+        assert_eq!(claims.aud, "pxm-api");
+
+        // JWKS에는 공개키만, kid는 JWK thumbprint
+        let jwks = serde_json::to_value(keys.jwks()).unwrap();
+        let jwk = &jwks["keys"][0];
+        assert_eq!(jwk["kid"], keys.signing_kid());
+        assert_eq!(jwk["alg"], alg);
+        assert_eq!(jwk["use"], "sig");
+        assert!(jwk.get("d").is_none());
+    }
+
+    // HS256 비밀키는 공개하지 않음
+    let hmac = JwtKeys::hmac(b"0123456789abcdef0123456789abcdef", None).unwrap();
+    assert!(hmac.jwks().keys.is_empty());
+}
+
+#[test]
+fn test_issuer_and_audience_are_validated() {
+    let keys = JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, None).unwrap();
+    let other_audience = JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, None)
+        .unwrap()
+        .with_claims("pxm".to_string(), "billing".to_string());
+    let other_issuer = JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, None)
+        .unwrap()
+        .with_claims("someone-else".to_string(), "pxm-api".to_string());
+
+    assert!(keys.verify(&issue(&keys)).is_ok());
+    assert!(keys.verify(&issue(&other_audience)).is_err());
+    assert!(keys.verify(&issue(&other_issuer)).is_err());
+}
+
+#[test]
+fn test_key_rotation() {
+    // 이전 키로 발급된 토큰
+    let old =
+        JwtKeys::asymmetric(RS256_OLD_PRIVATE, RS256_OLD_PUBLIC, Some("2026-01".into())).unwrap();
+    let old_token = issue(&old);
+
+    // 새 키로 서명, 이전 공개키는 검증용으로 유지
+    let rotated = JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, Some("2026-02".into()))
+        .unwrap()
+        .with_verification_key(RS256_OLD_PUBLIC, Some("2026-01".into()))
+        .unwrap();
+    assert!(rotated.verify(&old_token).is_ok());
+    assert!(rotated.verify(&issue(&rotated)).is_ok());
+    let kids: Vec<_> = rotated.jwks().keys.into_iter().map(|k| k.kid).collect();
+    assert_eq!(kids, vec!["2026-02", "2026-01"]);
+
+    // 이전 키를 빼면 그 키의 토큰은 거부
+    let retired =
+        JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, Some("2026-02".into())).unwrap();
+    assert!(retired.verify(&old_token).is_err());
+
+    // kid 중복 불가
+    assert!(
+        JwtKeys::asymmetric(ED25519_PRIVATE, ED25519_PUBLIC, Some("2026-02".into()))
+            .unwrap()
+            .with_verification_key(RS256_OLD_PUBLIC, Some("2026-02".into()))
+            .is_err()
+    );
+}
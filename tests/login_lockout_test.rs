@@ -0,0 +1,233 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use backend::domain::login::{SCOPE_IP, account_key};
+use backend::domain::user::LoginDto;
+use backend::establish_connection;
+use backend::handlers::auth_handler::login;
+use backend::handlers::org_handler::unlock_user;
+use backend::repositories::login_attempt_repository::LoginAttemptRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::login_guard::login_throttle;
+use backend::utils::auth::hash_password;
+use backend::utils::client_ip::{ClientIp, forwarded_client_ip};
+use backend::utils::jwt_keys::{JwtKeys, install_jwt_keys};
+use dotenvy::dotenv;
+use std::env;
+use std::net::{IpAddr, Ipv6Addr};
+use uuid::Uuid;
+
+const TEST_JWT_SECRET: &[u8] = b"test-only-jwt-secret-0123456789abcdef";
+
+async fn try_login(
+    pool: &sqlx::PgPool,
+    ip: Option<IpAddr>,
+    email: &str,
+    password: &str,
+) -> Result<(), (StatusCode, String)> {
+    login(
+        State(pool.clone()),
+        ClientIp(ip),
+        HeaderMap::new(),
+        Json(LoginDto {
+            email: email.to_string(),
+            password: password.to_string(),
+        }),
+    )
+    .await
+    .map(|_| ())
+}
+
+#[tokio::test]
+async fn test_account_lockout_and_unlock() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let max_failures = login_throttle().max_failures;
+
+    let email = format!("lockout_{}@pxm.com", Uuid::new_v4().simple());
+    let user = UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Lockout User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // 1. max_failures번 실패 -> 잠금, 맞는 비밀번호도 같은 응답으로 거부
+    for _ in 0..max_failures {
+        let err = try_login(&pool, None, &email, "wrong").await.unwrap_err();
+        assert_eq!(
+            err,
+            (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
+        );
+    }
+    let locked = try_login(&pool, None, &email, "password123").await;
+    assert_eq!(
+        locked.unwrap_err(),
+        (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
+    );
+
+    // 2. 없는 이메일도 똑같이 잠김 (가입 여부 노출 없음)
+    let unknown = format!("nobody_{}@pxm.com", Uuid::new_v4().simple());
+    for _ in 0..=max_failures {
+        let err = try_login(&pool, None, &unknown, "wrong").await.unwrap_err();
+        assert_eq!(
+            err,
+            (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
+        );
+    }
+
+    // 3. 관리자 잠금 해제 -> 로그인 성공, last_login_at 기록
+    assert_eq!(
+        unlock_user(Path(user.id), State(pool.clone()))
+            .await
+            .unwrap(),
+        StatusCode::NO_CONTENT
+    );
+    try_login(&pool, None, &email, "password123").await.unwrap();
+    let user = UserRepository::new(pool.clone())
+        .find_by_id(user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(user.last_login_at.is_some());
+
+    // 4. 로그인 이력: 실패(잘못된 비밀번호, 잠김)와 성공
+    let history = sqlx::query!(
+        r#"
+        SELECT success, failure_reason FROM login_attempts
+        WHERE email = $1 ORDER BY created_at
+        "#,
+        account_key(&email)
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    let reasons: Vec<_> = history
+        .iter()
+        .map(|row| (row.success, row.failure_reason.as_deref()))
+        .collect();
+    assert_eq!(
+        reasons
+            .iter()
+            .filter(|r| r.1 == Some("invalid_credentials"))
+            .count(),
+        max_failures as usize
+    );
+    assert!(reasons.contains(&(false, Some("account_locked"))));
+    assert_eq!(reasons.last(), Some(&(true, None)));
+}
+
+#[tokio::test]
+async fn test_ip_block() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let config = login_throttle();
+
+    let email = format!("ipblock_{}@pxm.com", Uuid::new_v4().simple());
+    UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "IP Block User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // 같은 IP에서 여러 계정으로 실패가 쌓인 상태
+    let blocked_ip = IpAddr::V6(Ipv6Addr::from(Uuid::new_v4().as_u128()));
+    let repo = LoginAttemptRepository::new(pool.clone());
+    for _ in 0..config.ip_max_failures {
+        repo.reserve(
+            SCOPE_IP,
+            &blocked_ip.to_string(),
+            config.failure_window,
+            config.ip_max_failures,
+            config.lockout,
+        )
+        .await
+        .unwrap();
+    }
+
+    // 차단된 IP는 맞는 비밀번호도 429, 다른 IP는 정상
+    let blocked = try_login(&pool, Some(blocked_ip), &email, "password123").await;
+    assert_eq!(blocked.unwrap_err().0, StatusCode::TOO_MANY_REQUESTS);
+    let other_ip = IpAddr::V6(Ipv6Addr::from(Uuid::new_v4().as_u128()));
+    try_login(&pool, Some(other_ip), &email, "password123")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_attempts_cannot_exceed_limit() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let max_failures = login_throttle().max_failures;
+
+    let email = format!("race_{}@pxm.com", Uuid::new_v4().simple());
+    UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Race User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // 한도의 세 배를 동시에 시도해도 비밀번호 확인까지 가는 시도는 max_failures번뿐
+    let attempts = (0..max_failures * 3).map(|_| try_login(&pool, None, &email, "wrong"));
+    for result in futures::future::join_all(attempts).await {
+        assert_eq!(result.unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+    let checked = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!" FROM login_attempts
+        WHERE email = $1 AND failure_reason = 'invalid_credentials'
+        "#,
+        account_key(&email)
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(checked, max_failures as i64);
+
+    // 잠긴 뒤에는 맞는 비밀번호도 거부
+    let locked = try_login(&pool, None, &email, "password123").await;
+    assert_eq!(locked.unwrap_err().0, StatusCode::UNAUTHORIZED);
+}
+
+#[test]
+fn test_forwarded_client_ip_ignores_spoofed_entries() {
+    // 클라이언트가 "X-Forwarded-For: 1.2.3.4"를 보내도 프록시는 실제 주소를 뒤에 덧붙입니다.
+    let spoofed = "1.2.3.4, 203.0.113.7";
+    assert_eq!(
+        forwarded_client_ip(spoofed, 1),
+        Some("203.0.113.7".parse().unwrap())
+    );
+    // 프록시 두 단계: 오른쪽 끝은 앞단 프록시의 주소
+    let two_hops = "1.2.3.4, 203.0.113.7, 10.0.0.2";
+    assert_eq!(
+        forwarded_client_ip(two_hops, 2),
+        Some("203.0.113.7".parse().unwrap())
+    );
+    // 신뢰하는 프록시 수보다 항목이 적거나 주소가 아니면 사용하지 않음
+    assert_eq!(forwarded_client_ip("203.0.113.7", 2), None);
+    assert_eq!(forwarded_client_ip("1.2.3.4, garbage", 1), None);
+    assert_eq!(
+        forwarded_client_ip("2001:db8::1", 1),
+        Some("2001:db8::1".parse().unwrap())
+    );
+}
@@ -0,0 +1,260 @@
+use backend::domain::approval::{ApprovalAction, ApprovalStep, FlowProcess};
+use backend::domain::notification::{Notification, NotificationType, mentioned_emails};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::notification_repository::NotificationRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::notification::{DeadlineConfig, DeadlineNotifier, NotificationConsumer};
+use backend::services::outbox::EventConsumer;
+use chrono::Utc;
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn flow(approvers: &[Uuid]) -> FlowProcess {
+    FlowProcess {
+        current_step: 1,
+        steps: approvers
+            .iter()
+            .enumerate()
+            .map(|(i, approver)| ApprovalStep {
+                seq: i as i32 + 1,
+                name: format!("Step {}", i + 1),
+                approver_id: *approver,
+                status: "pending".to_string(),
+                timestamp: None,
+            })
+            .collect(),
+        references: vec![],
+    }
+}
+
+fn types(notifications: &[Notification]) -> Vec<&str> {
+    notifications
+        .iter()
+        .map(|n| n.notification_type.as_str())
+        .collect()
+}
+
+#[test]
+fn test_mentioned_emails() {
+    assert_eq!(
+        mentioned_emails("@Kim@pxm.com, (@lee@pxm.com) 확인 부탁드립니다 @kim@pxm.com @nobody"),
+        ["kim@pxm.com", "lee@pxm.com"]
+    );
+}
+
+#[tokio::test]
+async fn test_lifecycle_notifications_and_read_state() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+    let outbox = OutboxRepository::new(pool.clone());
+    let notifications = NotificationRepository::new(pool.clone());
+    let consumer = NotificationConsumer::new(pool.clone());
+
+    let email = format!("mention_{}@pxm.com", Uuid::new_v4().simple());
+    let mentioned = UserRepository::new(pool.clone())
+        .create(email.clone(), "x".into(), "Mentioned".into(), None, None)
+        .await
+        .unwrap()
+        .id;
+    // 결재선/참조에 없는 사용자는 언급되어도 알림을 받지 않음 (문서 열람 권한 없음)
+    let outsider_email = format!("outsider_{}@pxm.com", Uuid::new_v4().simple());
+    let outsider = UserRepository::new(pool.clone())
+        .create(
+            outsider_email.clone(),
+            "x".into(),
+            "Outsider".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .id;
+
+    // 2단계 결재자는 "배정" 알림을 꺼 둠
+    let requester = Uuid::new_v4();
+    let approvers = [Uuid::new_v4(), Uuid::new_v4()];
+    notifications
+        .set_preferences(approvers[1], &[(NotificationType::Assigned, false)])
+        .await
+        .unwrap();
+
+    // 1. 상신(언급될 사용자는 참조자) -> 1단계 승인 -> 의견(@언급) -> 2단계 반려
+    let mut flow_process = flow(&approvers);
+    flow_process.references.push(mentioned);
+    let mut request = repo
+        .create(
+            "Notification request".to_string(),
+            requester,
+            serde_json::json!({}),
+            flow_process,
+        )
+        .await
+        .unwrap();
+    for (approver, action) in [
+        (approvers[0], ApprovalAction::Approve),
+        (approvers[1], ApprovalAction::Reject),
+    ] {
+        if action == ApprovalAction::Reject {
+            repo.add_comment(
+                request.id,
+                approver,
+                format!("@{}, @{} please check", email, outsider_email),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+        request
+            .flow_process
+            .0
+            .handle_action(action, approver)
+            .unwrap();
+        if action == ApprovalAction::Reject {
+            request.status = "rejected".to_string();
+        }
+        request = repo
+            .record_action(request, approver, action, Some("No budget".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    // 2. 같은 이벤트를 두 번 받아도 알림은 한 번만
+    let events = outbox.find_by_approval(request.id).await.unwrap();
+    for event in events.iter().chain(events.iter()) {
+        consumer.handle(event).await.unwrap();
+    }
+
+    let list = |user_id: Uuid| {
+        let notifications = NotificationRepository::new(pool.clone());
+        async move {
+            notifications
+                .find_page(user_id, false, None, 100)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(types(&list(approvers[0]).await.items), ["assigned"]);
+    assert!(list(approvers[1]).await.items.is_empty());
+    assert_eq!(types(&list(mentioned).await.items), ["mentioned"]);
+    assert!(list(outsider).await.items.is_empty());
+    let requester_page = list(requester).await;
+    assert_eq!(types(&requester_page.items), ["rejected"]);
+    assert_eq!(requester_page.items[0].data.0["reason"], "No budget");
+    assert_eq!(requester_page.items[0].actor_id, Some(approvers[1]));
+
+    // 3. 읽음 처리
+    assert_eq!(notifications.count_unread(requester).await.unwrap(), 1);
+    let read = notifications
+        .mark_read(requester, requester_page.items[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(read.read_at.is_some());
+    assert_eq!(notifications.count_unread(requester).await.unwrap(), 0);
+    assert!(
+        notifications
+            .find_page(requester, true, None, 100)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+    // 다른 사용자의 알림은 읽음 처리할 수 없음
+    assert!(
+        notifications
+            .mark_read(mentioned, requester_page.items[0].id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(notifications.mark_all_read(mentioned).await.unwrap(), 1);
+    assert_eq!(notifications.count_unread(mentioned).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_deadline_notifications_are_sent_once_per_step() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+    let notifications = NotificationRepository::new(pool.clone());
+    let notifier = DeadlineNotifier::new(
+        pool.clone(),
+        DeadlineConfig {
+            window: Duration::from_secs(24 * 60 * 60),
+            poll_interval: Duration::from_secs(60),
+        },
+    );
+
+    let approvers = [Uuid::new_v4(), Uuid::new_v4()];
+    let request = repo
+        .create(
+            "Deadline request".to_string(),
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            flow(&approvers),
+        )
+        .await
+        .unwrap();
+
+    // 기한이 멀면 알리지 않음
+    repo.set_due_at(request.id, Some(Utc::now() + chrono::Duration::days(3)))
+        .await
+        .unwrap()
+        .unwrap();
+    notifier.notify_due_soon().await.unwrap();
+    assert_eq!(notifications.count_unread(approvers[0]).await.unwrap(), 0);
+
+    // 기한 임박 -> 현재 결재자에게 한 번만
+    let updated = repo
+        .set_due_at(request.id, Some(Utc::now() + chrono::Duration::hours(2)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(updated.due_at.is_some());
+    notifier.notify_due_soon().await.unwrap();
+    notifier.notify_due_soon().await.unwrap();
+    let page = notifications
+        .find_page(approvers[0], false, None, 100)
+        .await
+        .unwrap();
+    assert_eq!(types(&page.items), ["deadline_approaching"]);
+    assert_eq!(page.items[0].approval_id, Some(request.id));
+
+    // 다음 단계로 넘어가면 새 결재자에게 알림
+    let mut request = updated;
+    request
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, approvers[0])
+        .unwrap();
+    let request = repo
+        .record_action(request, approvers[0], ApprovalAction::Approve, None)
+        .await
+        .unwrap()
+        .unwrap();
+    notifier.notify_due_soon().await.unwrap();
+    assert_eq!(notifications.count_unread(approvers[1]).await.unwrap(), 1);
+
+    // 끝난 문서는 기한을 바꿀 수 없음
+    let mut request = request;
+    request
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, approvers[1])
+        .unwrap();
+    request.status = "approved".to_string();
+    let request = repo
+        .record_action(request, approvers[1], ApprovalAction::Approve, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(repo.set_due_at(request.id, None).await.unwrap().is_none());
+}
@@ -0,0 +1,257 @@
+use async_trait::async_trait;
+use backend::domain::approval::{ApprovalAction, ApprovalStep, FlowProcess};
+use backend::domain::event::{DomainEvent, OutboxEvent};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::services::outbox::{EventConsumer, OutboxConfig, OutboxDispatcher};
+use dotenvy::dotenv;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+// 특정 문서의 이벤트만 기록하는 consumer (fail이 켜져 있으면 실패)
+struct Recorder {
+    approval_id: Uuid,
+    fail: AtomicBool,
+    seen: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl EventConsumer for Recorder {
+    fn name(&self) -> &'static str {
+        "recorder"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        if event.approval_id != self.approval_id {
+            return Ok(());
+        }
+        if self.fail.load(Ordering::SeqCst) {
+            return Err("consumer down".to_string());
+        }
+        self.seen.lock().unwrap().push(event.event_type.clone());
+        Ok(())
+    }
+}
+
+async fn drain_all(dispatcher: &OutboxDispatcher) {
+    while dispatcher.drain().await.unwrap() > 0 {}
+}
+
+#[tokio::test]
+async fn test_state_changes_write_outbox_events_and_dispatch() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+    let outbox = OutboxRepository::new(pool.clone());
+
+    // 1. 임시저장 -> 상신 -> 1단계 승인 -> 의견 -> 2단계 반려
+    let requester = Uuid::new_v4();
+    let approvers = [Uuid::new_v4(), Uuid::new_v4()];
+    let draft = repo
+        .create_draft(
+            "Outbox request".to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: approvers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, approver)| ApprovalStep {
+                        seq: i as i32 + 1,
+                        name: format!("Step {}", i + 1),
+                        approver_id: *approver,
+                        status: "pending".to_string(),
+                        timestamp: None,
+                    })
+                    .collect(),
+                references: vec![],
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let mut request = repo.submit(draft.id).await.unwrap().unwrap();
+    for (approver, action) in [
+        (approvers[0], ApprovalAction::Approve),
+        (approvers[1], ApprovalAction::Reject),
+    ] {
+        request
+            .flow_process
+            .0
+            .handle_action(action, approver)
+            .unwrap();
+        if action == ApprovalAction::Reject {
+            request.status = "rejected".to_string();
+            repo.add_comment(request.id, requester, "Why?".to_string())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        request = repo
+            .record_action(request, approver, action, Some("No budget".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let events = outbox.find_by_approval(request.id).await.unwrap();
+    let types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(
+        types,
+        [
+            "request_created",
+            "request_submitted",
+            "step_approved",
+            "comment_added",
+            "request_rejected"
+        ]
+    );
+    assert!(events.iter().all(|e| e.status == "pending"));
+    match &events[4].payload.0 {
+        DomainEvent::RequestRejected { approval, reason } => {
+            assert_eq!(approval.status, "rejected");
+            assert_eq!(reason.as_deref(), Some("No budget"));
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+    // 이력도 같은 트랜잭션에서 기록
+    let logs = repo.get_logs(request.id).await.unwrap();
+    assert_eq!(logs.len(), 5);
+
+    // 2. consumer 실패 -> 재시도 대기 (attempts, last_error 기록)
+    let recorder = Arc::new(Recorder {
+        approval_id: request.id,
+        fail: AtomicBool::new(true),
+        seen: Mutex::new(Vec::new()),
+    });
+    let dispatcher = OutboxDispatcher::new(
+        pool.clone(),
+        vec![recorder.clone()],
+        // 다른 테스트가 남긴 이벤트까지 한 번에 처리하도록 batch를 크게
+        OutboxConfig {
+            batch_size: 100_000,
+            backoff_base: Duration::ZERO,
+            ..OutboxConfig::default()
+        },
+    );
+    dispatcher.drain().await.unwrap();
+    let failed = outbox.find_by_approval(request.id).await.unwrap();
+    assert!(failed.iter().all(|e| e.status == "pending"
+        && e.attempts == 1
+        && e.last_error.as_deref() == Some("recorder: consumer down")));
+
+    // 3. 복구 후 발생 순서대로 전달되고 processed 처리
+    recorder.fail.store(false, Ordering::SeqCst);
+    drain_all(&dispatcher).await;
+    assert_eq!(*recorder.seen.lock().unwrap(), types);
+    let processed = outbox.find_by_approval(request.id).await.unwrap();
+    assert!(processed.iter().all(|e| e.status == "processed"));
+}
+
+#[tokio::test]
+async fn test_failed_state_change_writes_no_event() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+
+    let request = repo
+        .create(
+            "Already pending".to_string(),
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    // draft가 아니므로 상신되지 않고 이벤트도 남지 않음
+    assert!(repo.submit(request.id).await.unwrap().is_none());
+    let events = OutboxRepository::new(pool)
+        .find_by_approval(request.id)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, "request_created");
+}
+
+#[tokio::test]
+async fn test_concurrent_actions_on_the_same_step_record_once() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+
+    let approver = Uuid::new_v4();
+    let request = repo
+        .create(
+            "Double click".to_string(),
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Lead".to_string(),
+                    approver_id: approver,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                }],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    // 같은 행을 읽은 두 요청(웹 UI + 채팅 버튼)이 동시에 승인/반려
+    let acted = |action| {
+        let mut copy = request.clone();
+        let result = copy.flow_process.0.handle_action(action, approver).unwrap();
+        copy.status = match result.as_str() {
+            "completed" => "approved".to_string(),
+            "rejected" => "rejected".to_string(),
+            _ => copy.status.clone(),
+        };
+        copy
+    };
+    let (first, second) = tokio::join!(
+        repo.record_action(
+            acted(ApprovalAction::Approve),
+            approver,
+            ApprovalAction::Approve,
+            None
+        ),
+        repo.record_action(
+            acted(ApprovalAction::Reject),
+            approver,
+            ApprovalAction::Reject,
+            None
+        ),
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert_eq!(
+        first.is_some() as u8 + second.is_some() as u8,
+        1,
+        "exactly one action must win"
+    );
+
+    // 이긴 쪽의 이벤트만 남음
+    let events = OutboxRepository::new(pool)
+        .find_by_approval(request.id)
+        .await
+        .unwrap();
+    let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(types.len(), 2);
+    assert_eq!(types[0], "request_created");
+    assert!(matches!(types[1], "request_completed" | "request_rejected"));
+}
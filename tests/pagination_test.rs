@@ -0,0 +1,66 @@
+use backend::domain::approval::{ApprovalListQuery, ApprovalSort, FlowProcess};
+use backend::domain::pagination::SortOrder;
+use backend::domain::role::UserRoles;
+use backend::establish_connection;
+use backend::repositories::approval_repository::{ApprovalRepository, ApprovalScope};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_approval_keyset_pagination() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool);
+
+    // 1. 요청자 한 명이 문서 3건 상신
+    let requester = Uuid::new_v4();
+    for title in ["Charlie", "Alpha", "Bravo"] {
+        repo.create(
+            title.to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // 2. 제목 오름차순, 2건씩
+    let roles = UserRoles::default();
+    let mut query = ApprovalListQuery {
+        requester_id: Some(requester),
+        sort: ApprovalSort::Title,
+        order: SortOrder::Asc,
+        limit: Some(2),
+        ..Default::default()
+    };
+    let scope = || ApprovalScope::Visible {
+        viewer_id: requester,
+        roles: &roles,
+    };
+
+    let first = repo.find_page(scope(), &query, None).await.unwrap();
+    assert_eq!(first.total_count, 3);
+    let titles: Vec<_> = first.items.iter().map(|r| r.title.as_str()).collect();
+    assert_eq!(titles, ["Alpha", "Bravo"]);
+
+    // 3. next_cursor로 나머지 페이지
+    query.cursor = first.next_cursor;
+    let keyset = query.keyset().unwrap();
+    let second = repo.find_page(scope(), &query, keyset).await.unwrap();
+    let titles: Vec<_> = second.items.iter().map(|r| r.title.as_str()).collect();
+    assert_eq!(titles, ["Charlie"]);
+    assert!(second.next_cursor.is_none());
+
+    // 4. 정렬 기준이 다른 cursor는 거부
+    query.sort = ApprovalSort::CreatedAt;
+    query.cursor =
+        Some(backend::domain::pagination::Cursor::new("title", "Alpha", Uuid::new_v4()).encode());
+    assert!(query.keyset().is_err());
+}
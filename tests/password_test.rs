@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use axum::extract::{Extension, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use backend::domain::session::RefreshDto;
+use backend::domain::user::{
+    AuthUser, ChangePasswordDto, ForgotPasswordDto, LoginDto, ResetPasswordDto, User,
+};
+use backend::establish_connection;
+use backend::handlers::auth_handler::{
+    change_password, forgot_password, login, refresh, reset_password,
+};
+use backend::repositories::user_repository::UserRepository;
+use backend::services::password_reset::{
+    PasswordResetConfig, PasswordResetNotifier, PasswordResetService,
+};
+use backend::utils::auth::hash_password;
+use backend::utils::client_ip::ClientIp;
+use backend::utils::jwt_keys::{JwtKeys, install_jwt_keys};
+use dotenvy::dotenv;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+mod common;
+use common::{TEST_JWT_SECRET, me, start_server};
+
+// 보낸 재설정 토큰을 채널로 넘겨주는 notifier
+struct ChannelNotifier(mpsc::UnboundedSender<(Uuid, String)>);
+
+#[async_trait]
+impl PasswordResetNotifier for ChannelNotifier {
+    async fn send_reset(&self, user: &User, token: &str, _ttl: Duration) -> Result<(), String> {
+        self.0
+            .send((user.id, token.to_string()))
+            .map_err(|e| e.to_string())
+    }
+}
+
+async fn login_with(pool: &sqlx::PgPool, email: &str, password: &str) -> Option<String> {
+    login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        Json(LoginDto {
+            email: email.to_string(),
+            password: password.to_string(),
+        }),
+    )
+    .await
+    .ok()
+    .map(|Json(response)| response.token)
+}
+
+#[tokio::test]
+async fn test_change_password() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let server = start_server(
+        pool.clone(),
+        Router::new().route("/auth/password/change", post(change_password)),
+    )
+    .await;
+
+    let email = format!("change_{}@pxm.com", Uuid::new_v4().simple());
+    UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Change User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let current = login_with(&pool, &email, "password123").await.unwrap();
+    let other = login_with(&pool, &email, "password123").await.unwrap();
+
+    let client = reqwest::Client::new();
+    let change = |old: &str, new: &str| {
+        client
+            .post(format!("{}/auth/password/change", server))
+            .bearer_auth(&current)
+            .json(&serde_json::json!({ "old_password": old, "new_password": new }))
+            .send()
+    };
+
+    // 1. 현재 비밀번호가 틀리면 거부
+    let status = change("wrong-password", "newpassword1")
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 2. 변경 -> 새 비밀번호로만 로그인, 다른 세션은 폐기되고 현재 세션은 유지
+    let status = change("password123", "newpassword1")
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(login_with(&pool, &email, "password123").await.is_none());
+    assert!(login_with(&pool, &email, "newpassword1").await.is_some());
+    assert_eq!(me(&server, &current).await, StatusCode::OK);
+    assert_eq!(me(&server, &other).await, StatusCode::UNAUTHORIZED);
+
+    // 3. 핸들러 직접 호출 시에도 길이 검증
+    let too_short = change_password(
+        State(pool.clone()),
+        Extension(AuthUser {
+            id: Uuid::new_v4(),
+            department_id: None,
+            roles: Default::default(),
+            session_id: Uuid::new_v4(),
+        }),
+        Json(ChangePasswordDto {
+            old_password: "newpassword1".to_string(),
+            new_password: "123".to_string(),
+        }),
+    )
+    .await;
+    assert_eq!(too_short.unwrap_err().0, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_forgot_and_reset_password() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let server = start_server(
+        pool.clone(),
+        Router::new().route("/auth/password/change", post(change_password)),
+    )
+    .await;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let service = Arc::new(PasswordResetService::new(
+        Arc::new(ChannelNotifier(tx)),
+        PasswordResetConfig::default(),
+    ));
+    let forgot = |email: &str| {
+        forgot_password(
+            State(pool.clone()),
+            State(service.clone()),
+            Json(ForgotPasswordDto {
+                email: email.to_string(),
+            }),
+        )
+    };
+    let reset = |token: &str, password: &str| {
+        reset_password(
+            State(pool.clone()),
+            Json(ResetPasswordDto {
+                token: token.to_string(),
+                new_password: password.to_string(),
+            }),
+        )
+    };
+
+    let email = format!("reset_{}@pxm.com", Uuid::new_v4().simple());
+    let user = UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "Reset User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let Json(session) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        Json(LoginDto {
+            email: email.clone(),
+            password: "password123".to_string(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    // 1. 없는 이메일도 같은 응답 (전달은 하지 않음)
+    let unknown = forgot("nobody@pxm.com").await.unwrap();
+    assert_eq!(unknown, StatusCode::ACCEPTED);
+
+    // 2. 토큰 발급 -> 새로 발급하면 이전 토큰은 무효
+    assert_eq!(forgot(&email).await.unwrap(), StatusCode::ACCEPTED);
+    let (user_id, stale_token) = rx.recv().await.unwrap();
+    assert_eq!(user_id, user.id);
+    assert_eq!(forgot(&email).await.unwrap(), StatusCode::ACCEPTED);
+    let (_, token) = rx.recv().await.unwrap();
+    let stale = reset(&stale_token, "resetpass1").await;
+    assert_eq!(stale.unwrap_err().0, StatusCode::BAD_REQUEST);
+
+    // 3. 재설정 -> 새 비밀번호로 로그인, 기존 access/refresh token 모두 폐기
+    assert_eq!(
+        reset(&token, "resetpass1").await.unwrap(),
+        StatusCode::NO_CONTENT
+    );
+    assert!(login_with(&pool, &email, "password123").await.is_none());
+    assert!(login_with(&pool, &email, "resetpass1").await.is_some());
+    assert_eq!(me(&server, &session.token).await, StatusCode::UNAUTHORIZED);
+    let refreshed = refresh(
+        State(pool.clone()),
+        Json(RefreshDto {
+            refresh_token: session.refresh_token,
+        }),
+    )
+    .await;
+    assert_eq!(refreshed.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+    // 4. 같은 토큰은 다시 쓸 수 없음
+    let reused = reset(&token, "resetpass2").await;
+    assert_eq!(reused.unwrap_err().0, StatusCode::BAD_REQUEST);
+}
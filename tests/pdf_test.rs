@@ -0,0 +1,78 @@
+use backend::domain::form_schema::{display_value, fields_of, fields_with_extras};
+use backend::services::pdf::{
+    ApprovalDocument, CommentEntry, PdfError, StampCell, render_approval_pdf,
+};
+use serde_json::json;
+
+#[test]
+fn test_form_schema_fields_follow_schema_order() {
+    // 1. 필드 배열 형태 (중첩 포함)
+    let schema = json!({
+        "fields": [
+            { "key": "amount", "label": "금액" },
+            { "key": "trip", "label": "출장", "fields": [ { "key": "city", "label": "도시" } ] },
+            { "name": "memo" }
+        ]
+    });
+    let keys: Vec<_> = fields_of(&schema).iter().map(|f| f.key()).collect();
+    assert_eq!(keys, ["amount", "trip.city", "memo"]);
+
+    // 2. JSON Schema 형태 (ui:order 우선)
+    let schema = json!({
+        "ui:order": ["b"],
+        "properties": { "a": { "title": "A" }, "b": { "title": "B" } }
+    });
+    let labels: Vec<_> = fields_of(&schema).into_iter().map(|f| f.label).collect();
+    assert_eq!(labels, ["B", "A"]);
+
+    // 3. 스키마에 없는 값은 뒤에 덧붙임
+    let data = json!({ "a": 1, "extra": { "note": ["x", "y"] } });
+    let fields = fields_with_extras(&schema, &data);
+    let extra = fields.last().unwrap();
+    assert_eq!(extra.key(), "extra.note");
+    assert_eq!(display_value(extra.value_in(&data).unwrap()), "x, y");
+}
+
+#[test]
+fn test_render_approval_pdf() {
+    let mut document = ApprovalDocument {
+        title: "Business trip request".to_string(),
+        document_number: "20260125-3F2A9C1D".to_string(),
+        status: "approved".to_string(),
+        template_name: Some("Trip".to_string()),
+        requester_name: "Kim".to_string(),
+        department_name: Some("IT".to_string()),
+        created_at: "2026-01-25 09:00".to_string(),
+        fields: vec![("Amount".to_string(), "120000".to_string())],
+        stamps: (1..=8)
+            .map(|seq| StampCell {
+                step_name: format!("Step {seq}"),
+                approver_name: "Lee".to_string(),
+                status: "approved".to_string(),
+                processed_at: Some("2026-01-25 10:00".to_string()),
+            })
+            .collect(),
+        comments: (0..80)
+            .map(|idx| CommentEntry {
+                actor_name: "Lee".to_string(),
+                action_type: "COMMENT".to_string(),
+                content: Some(format!("comment {idx} with 한글")),
+                created_at: "2026-01-25 10:00".to_string(),
+            })
+            .collect(),
+    };
+
+    // 한글 폰트(PDF_FONT_PATH)가 없으면 '?'로 바꿔 그리지 않고 거부
+    if std::env::var("PDF_FONT_PATH").is_err() {
+        assert!(matches!(
+            render_approval_pdf(&document),
+            Err(PdfError::MissingFont('한'))
+        ));
+        document.comments.iter_mut().for_each(|entry| {
+            entry.content = entry.content.as_ref().map(|c| c.replace(" with 한글", ""))
+        });
+    }
+
+    let bytes = render_approval_pdf(&document).unwrap();
+    assert!(bytes.starts_with(b"%PDF"));
+}
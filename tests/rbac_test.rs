@@ -0,0 +1,208 @@
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use backend::domain::role::Permission;
+use backend::domain::user::LoginDto;
+use backend::establish_connection;
+use backend::handlers::auth_handler::login;
+use backend::handlers::email_template_handler::list_email_templates;
+use backend::handlers::org_handler::unlock_user;
+use backend::handlers::role_handler::{get_user_roles, list_roles};
+use backend::handlers::webhook_handler::list_webhooks;
+use backend::repositories::role_repository::RoleRepository;
+use backend::repositories::user_repository::UserRepository;
+use backend::services::auth_context::auth_cache;
+use backend::utils::auth::hash_password;
+use backend::utils::client_ip::ClientIp;
+use backend::utils::jwt_keys::{JwtKeys, install_jwt_keys};
+use backend::utils::middleware::{auth_middleware, require_permission};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+const TEST_JWT_SECRET: &[u8] = b"test-only-jwt-secret-0123456789abcdef";
+
+// main.rs와 같은 권한 레이어를 씌운 관리 라우트
+async fn start_server(pool: sqlx::PgPool) -> String {
+    let gate = |permission| from_fn_with_state(permission, require_permission);
+    let app = Router::new()
+        .route(
+            "/roles",
+            get(list_roles).route_layer(gate(Permission::RoleManage)),
+        )
+        .route(
+            "/org/users/{id}/roles",
+            get(get_user_roles).route_layer(gate(Permission::RoleManage)),
+        )
+        .route(
+            "/org/users/{id}/unlock",
+            post(unlock_user).route_layer(gate(Permission::UserManage)),
+        )
+        .route(
+            "/email-templates",
+            get(list_email_templates).route_layer(gate(Permission::EmailTemplateManage)),
+        )
+        .route(
+            "/webhooks",
+            get(list_webhooks).route_layer(gate(Permission::WebhookManage)),
+        )
+        .layer(from_fn_with_state(pool.clone(), auth_middleware))
+        .with_state(pool);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+async fn call(server: &str, method: &str, path: &str, token: &str) -> reqwest::StatusCode {
+    let client = reqwest::Client::new();
+    let url = format!("{}{}", server, path);
+    let request = match method {
+        "POST" => client.post(url),
+        _ => client.get(url),
+    };
+    request.bearer_auth(token).send().await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_admin_routes_require_their_permission() {
+    dotenv().ok();
+    install_jwt_keys(JwtKeys::hmac(TEST_JWT_SECRET, None).unwrap());
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let server = start_server(pool.clone()).await;
+
+    let email = format!("rbac_{}@pxm.com", Uuid::new_v4().simple());
+    let user = UserRepository::new(pool.clone())
+        .create(
+            email.clone(),
+            hash_password("password123").unwrap(),
+            "RBAC User".into(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let Json(session) = login(
+        State(pool.clone()),
+        ClientIp(None),
+        HeaderMap::new(),
+        Json(LoginDto {
+            email,
+            password: "password123".to_string(),
+        }),
+    )
+    .await
+    .unwrap();
+    let token = session.token;
+
+    let user_path = format!("/org/users/{}", user.id);
+    let routes = [
+        (Permission::RoleManage, "GET", "/roles".to_string()),
+        (
+            Permission::RoleManage,
+            "GET",
+            format!("{}/roles", user_path),
+        ),
+        (
+            Permission::UserManage,
+            "POST",
+            format!("{}/unlock", user_path),
+        ),
+        (
+            Permission::EmailTemplateManage,
+            "GET",
+            "/email-templates".to_string(),
+        ),
+        (Permission::WebhookManage, "GET", "/webhooks".to_string()),
+    ];
+
+    // 권한별 단일 권한 역할 생성
+    let suffix = Uuid::new_v4().simple().to_string()[..8].to_uppercase();
+    let role_of = |permission: Permission| {
+        format!(
+            "RBAC_{}_{}",
+            suffix,
+            permission.code().replace([':', '_'], "")
+        )
+    };
+    let mut role_codes: Vec<String> = routes.iter().map(|(p, _, _)| role_of(*p)).collect();
+    role_codes.dedup();
+    for (permission, _, _) in &routes {
+        let code = role_of(*permission);
+        sqlx::query!(
+            "INSERT INTO roles (code, name) VALUES ($1, $1) ON CONFLICT DO NOTHING",
+            code
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query!(
+            "INSERT INTO role_permissions (role_code, permission_code) VALUES ($1, $2)
+             ON CONFLICT DO NOTHING",
+            code,
+            permission.code()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    // 1. 역할 없음 -> 모든 관리 라우트 403
+    for (_, method, path) in &routes {
+        assert_eq!(
+            call(&server, method, path, &token).await,
+            reqwest::StatusCode::FORBIDDEN,
+            "{} {} without permission",
+            method,
+            path
+        );
+    }
+
+    // 2. 권한 하나씩 부여 -> 해당 권한의 라우트만 통과
+    let roles = RoleRepository::new(pool.clone());
+    for (granted, _, _) in &routes {
+        roles
+            .set_user_roles(user.id, &[role_of(*granted)])
+            .await
+            .unwrap();
+        auth_cache().invalidate(user.id);
+        for (required, method, path) in &routes {
+            let status = call(&server, method, path, &token).await;
+            if required == granted {
+                assert!(
+                    status.is_success(),
+                    "{} {} with {}: {}",
+                    method,
+                    path,
+                    granted.code(),
+                    status
+                );
+            } else {
+                assert_eq!(
+                    status,
+                    reqwest::StatusCode::FORBIDDEN,
+                    "{} {} with {}",
+                    method,
+                    path,
+                    granted.code()
+                );
+            }
+        }
+    }
+
+    // 3. 역할 회수 -> 다시 403
+    roles.set_user_roles(user.id, &[]).await.unwrap();
+    auth_cache().invalidate(user.id);
+    assert_eq!(
+        call(&server, "GET", "/roles", &token).await,
+        reqwest::StatusCode::FORBIDDEN
+    );
+
+    sqlx::query!("DELETE FROM roles WHERE code = ANY($1)", &role_codes)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,193 @@
+use backend::domain::approval::{ApprovalAction, ApprovalStep, FlowProcess};
+use backend::domain::realtime::{COMMENT_ADDED, INBOX_ADDED, REQUEST_UPDATED};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::repositories::realtime_repository::RealtimeRepository;
+use backend::services::outbox::EventConsumer;
+use backend::services::realtime::{HubMessage, RealtimeConsumer, RealtimeHub};
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_domain_events_fan_out_to_affected_users() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+    let outbox = OutboxRepository::new(pool.clone());
+    let realtime = RealtimeRepository::new(pool.clone());
+    let consumer = RealtimeConsumer::new(pool.clone());
+
+    // LISTEN 연결이 준비되면 Resync가 먼저 옵니다.
+    let hub = RealtimeHub::new();
+    let mut receiver = hub.subscribe();
+    tokio::spawn(hub.clone().listen(pool.clone()));
+    let first = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+        .await
+        .expect("listener did not connect")
+        .unwrap();
+    assert!(matches!(first, HubMessage::Resync));
+
+    // 1. 상신 -> 1단계 승인 -> 2단계 결재자가 의견
+    let requester = Uuid::new_v4();
+    let approvers = [Uuid::new_v4(), Uuid::new_v4()];
+    let draft = repo
+        .create_draft(
+            "Realtime request".to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: approvers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, approver)| ApprovalStep {
+                        seq: i as i32 + 1,
+                        name: format!("Step {}", i + 1),
+                        approver_id: *approver,
+                        status: "pending".to_string(),
+                        timestamp: None,
+                    })
+                    .collect(),
+                references: vec![],
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let mut request = repo.submit(draft.id).await.unwrap().unwrap();
+    request
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, approvers[0])
+        .unwrap();
+    let request = repo
+        .record_action(request, approvers[0], ApprovalAction::Approve, None)
+        .await
+        .unwrap()
+        .unwrap();
+    repo.add_comment(request.id, approvers[1], "Looking into it".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // 2. outbox 이벤트를 consumer에 두 번씩 전달해도 user_events는 한 번만 생깁니다.
+    let events = outbox.find_by_approval(request.id).await.unwrap();
+    for event in events.iter().chain(events.iter()) {
+        consumer.handle(event).await.unwrap();
+    }
+
+    let first_approver = realtime.find_after(approvers[0], 0, 100).await.unwrap();
+    let second_approver = realtime.find_after(approvers[1], 0, 100).await.unwrap();
+    let requester_events = realtime.find_after(requester, 0, 100).await.unwrap();
+    let types = |events: &[backend::domain::realtime::UserEvent]| {
+        events
+            .iter()
+            .map(|e| e.event_type.clone())
+            .collect::<Vec<_>>()
+    };
+    // 행위자 본인(의견 작성자)에게는 보내지 않음
+    assert_eq!(types(&first_approver), [INBOX_ADDED, COMMENT_ADDED]);
+    assert_eq!(types(&second_approver), [INBOX_ADDED]);
+    assert_eq!(types(&requester_events), [REQUEST_UPDATED, COMMENT_ADDED]);
+    assert_eq!(
+        requester_events[0].data.0["approval_id"],
+        request.id.to_string()
+    );
+    assert_eq!(
+        requester_events[1].data.0["comment"]["preview"],
+        "Looking into it"
+    );
+
+    // 3. NOTIFY로 hub에 전달됨 (다른 테스트의 이벤트는 건너뜀)
+    let ours = [requester, approvers[0], approvers[1]];
+    let mut received = Vec::new();
+    while received.len() < 5 {
+        let message = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+            .await
+            .expect("notification not received")
+            .unwrap();
+        if let HubMessage::Event(event) = message
+            && ours.contains(&event.user_id)
+        {
+            received.push(event.id);
+        }
+    }
+    let mut stored: Vec<i64> = first_approver
+        .iter()
+        .chain(&second_approver)
+        .chain(&requester_events)
+        .map(|e| e.id)
+        .collect();
+    stored.sort();
+    received.sort();
+    assert_eq!(received, stored);
+
+    // 4. Last-Event-ID 이후만 다시 읽음
+    let replay = realtime
+        .find_after(requester, requester_events[0].id, 100)
+        .await
+        .unwrap();
+    assert_eq!(replay.len(), 1);
+    assert_eq!(replay[0].id, requester_events[1].id);
+    assert_eq!(
+        realtime.latest_id(requester).await.unwrap(),
+        requester_events[1].id
+    );
+}
+
+#[tokio::test]
+async fn test_draft_comments_stay_with_the_requester() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool.clone());
+    let outbox = OutboxRepository::new(pool.clone());
+    let realtime = RealtimeRepository::new(pool.clone());
+    let consumer = RealtimeConsumer::new(pool.clone());
+
+    // 임시저장 문서에 요청자가 의견 작성
+    let (requester, approver, reference) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let draft = repo
+        .create_draft(
+            "Secret draft".to_string(),
+            requester,
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Lead".to_string(),
+                    approver_id: approver,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                }],
+                references: vec![reference],
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    repo.add_comment(draft.id, requester, "Not ready yet".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+
+    for event in outbox.find_by_approval(draft.id).await.unwrap() {
+        consumer.handle(&event).await.unwrap();
+    }
+
+    // 결재자/참조자는 draft를 볼 수 없으므로 제목/의견 미리보기도 받지 않음
+    for user_id in [approver, reference] {
+        assert!(
+            realtime
+                .find_after(user_id, 0, 100)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use backend::domain::approval::FlowProcess;
+use backend::domain::role::UserRoles;
+use backend::domain::search::SearchQuery;
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[test]
+fn test_search_query_parsing_and_highlight() {
+    assert!(SearchQuery::parse("  ").is_err());
+
+    let query = SearchQuery::parse("출장 it's").unwrap();
+    assert_eq!(query.tsquery(), "'출장':* & 'its':*");
+    assert_eq!(
+        SearchQuery::parse("100%").unwrap().like_patterns(),
+        ["%100\\%%"]
+    );
+
+    let query = SearchQuery::parse("요청 HOTEL").unwrap();
+    assert_eq!(
+        query.highlight("출장 결재요청서\nHotel 예약"),
+        "출장 결재<mark>요청</mark>서 <mark>Hotel</mark> 예약"
+    );
+
+    // 원문의 마크업은 <mark> 외에는 모두 이스케이프
+    let query = SearchQuery::parse("hotel").unwrap();
+    assert_eq!(
+        query.highlight("<script>alert(1)</script> Hotel & \"Inn\""),
+        "&lt;script&gt;alert(1)&lt;/script&gt; <mark>Hotel</mark> &amp; &quot;Inn&quot;"
+    );
+}
+
+#[tokio::test]
+async fn test_search_covers_form_data_and_comments() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool);
+
+    let requester = Uuid::new_v4();
+    let marker = Uuid::new_v4().simple().to_string()[..10].to_string();
+    let request = repo
+        .create(
+            "출장 결재요청서".to_string(),
+            requester,
+            serde_json::json!({ "detail": { "places": [format!("제주 {marker}")] } }),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    let roles = UserRoles::default();
+
+    // 1. form_data 중첩 문자열 값
+    let result = repo
+        .search(requester, &roles, &SearchQuery::parse(&marker).unwrap(), 20)
+        .await
+        .unwrap();
+    assert_eq!(result.total_count, 1);
+    assert!(result.items[0].highlight.contains("<mark>"));
+
+    // 2. 한국어 복합어 안의 부분 문자열 (pg_trgm)
+    let query = SearchQuery::parse(&format!("요청서 {marker}")).unwrap();
+    let result = repo.search(requester, &roles, &query, 20).await.unwrap();
+    assert_eq!(result.items[0].request.id, request.id);
+
+    // 3. 코멘트 추가 후 검색
+    let comment_marker = format!("c{}", &Uuid::new_v4().simple().to_string()[..10]);
+    repo.add_log(
+        request.id,
+        requester,
+        "COMMENT".to_string(),
+        Some(format!("영수증 첨부 {comment_marker}")),
+    )
+    .await
+    .unwrap();
+    let query = SearchQuery::parse(&comment_marker).unwrap();
+    let result = repo.search(requester, &roles, &query, 20).await.unwrap();
+    assert_eq!(result.total_count, 1);
+
+    // 4. 열람 권한이 없으면 제외
+    let result = repo
+        .search(Uuid::new_v4(), &roles, &query, 20)
+        .await
+        .unwrap();
+    assert_eq!(result.total_count, 0);
+}
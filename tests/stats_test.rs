@@ -0,0 +1,121 @@
+use backend::domain::approval::{ApprovalStep, FlowProcess};
+use backend::domain::stats::{StatsQuery, StatsScope};
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::stats_repository::StatsRepository;
+use backend::repositories::user_repository::UserRepository;
+use chrono::{Duration, Utc};
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_stats_durations_rejections_and_backlog() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let approval_repo = ApprovalRepository::new(pool.clone());
+    let user_repo = UserRepository::new(pool.clone());
+    let stats_repo = StatsRepository::new(pool.clone());
+
+    // 1. 부서(부서장 manager) 소속 요청자, 결재자
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let mut users = Vec::new();
+    for name in ["manager", "requester", "approver"] {
+        let user = user_repo
+            .create(
+                format!("stats-{name}-{suffix}@pxm.com"),
+                "hash".to_string(),
+                name.to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        users.push(user.id);
+    }
+    let [manager, requester, approver] = users[..] else {
+        unreachable!()
+    };
+    let department_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO departments (id, name, code, manager_id) VALUES ($1, $2, $2, $3)")
+        .bind(department_id)
+        .bind(format!("STATS-{suffix}"))
+        .bind(manager)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET department_id = $1 WHERE id = $2")
+        .bind(department_id)
+        .bind(requester)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // 2. 승인(2시간), 반려(4시간), 대기 각 1건
+    let now = Utc::now();
+    for (status, hours) in [
+        ("approved", Some(2)),
+        ("rejected", Some(4)),
+        ("pending", None),
+    ] {
+        let mut request = approval_repo
+            .create(
+                format!("Stats {status}"),
+                requester,
+                serde_json::json!({}),
+                FlowProcess {
+                    current_step: 1,
+                    steps: vec![ApprovalStep {
+                        seq: 1,
+                        name: "Lead".to_string(),
+                        approver_id: approver,
+                        status: status.to_string(),
+                        timestamp: hours.map(|h| now + Duration::hours(h)),
+                    }],
+                    references: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        request.status = status.to_string();
+        approval_repo.update(request).await.unwrap();
+    }
+
+    // 3. 부서장 범위 집계
+    let scope = StatsScope::ManagedBy(manager);
+    let query = StatsQuery::default();
+
+    let templates = stats_repo.by_template(scope, &query).await.unwrap();
+    assert_eq!(templates.len(), 1);
+    let template = &templates[0];
+    assert_eq!(
+        (template.submitted, template.completed, template.rejected),
+        (3, 2, 1)
+    );
+    assert_eq!(template.rejection_rate, Some(0.5));
+    assert!((template.avg_hours.unwrap() - 3.0).abs() < 0.1);
+
+    let approvers = stats_repo.by_approver(scope, &query).await.unwrap();
+    assert_eq!(approvers[0].approver_id, approver);
+    assert_eq!(approvers[0].processed, 2);
+
+    let backlog = stats_repo.backlog(scope, &query).await.unwrap();
+    assert_eq!(backlog.len(), 1);
+    assert_eq!((backlog[0].approver_id, backlog[0].pending), (approver, 1));
+
+    // 4. 기간 필터 / 관리하지 않는 부서장
+    let future = StatsQuery {
+        from: Some(now + Duration::days(1)),
+        ..Default::default()
+    };
+    assert!(stats_repo.by_step(scope, &future).await.unwrap().is_empty());
+    let other = StatsScope::ManagedBy(Uuid::new_v4());
+    assert!(
+        stats_repo
+            .by_template(other, &query)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
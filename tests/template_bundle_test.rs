@@ -0,0 +1,220 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use backend::domain::approval::{ApprovalStep, FlowProcess};
+use backend::domain::template::CreateTemplateDto;
+use backend::domain::template_bundle::{
+    ExportReport, ImportReport, PortableApprover, TemplateBundle,
+};
+use backend::establish_connection;
+use backend::handlers::template_bundle_handler::{
+    BundleFormat, ExportQuery, ImportQuery, export_template, import_template,
+};
+use backend::repositories::template_repository::TemplateRepository;
+use backend::repositories::user_repository::UserRepository;
+use dotenvy::dotenv;
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+async fn export(pool: &PgPool, id: Uuid) -> (StatusCode, Vec<u8>) {
+    let response: Response = export_template(
+        Path(id),
+        State(pool.clone()),
+        Query(ExportQuery {
+            format: BundleFormat::Json,
+        }),
+    )
+    .await
+    .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, body.to_vec())
+}
+
+async fn import(pool: &PgPool, bundle: &str, dry_run: bool) -> (StatusCode, ImportReport) {
+    let (status, axum::Json(report)) = import_template(
+        State(pool.clone()),
+        Query(ImportQuery { dry_run }),
+        HeaderMap::new(),
+        bundle.to_string(),
+    )
+    .await
+    .unwrap();
+    (status, report)
+}
+
+// 원본 환경의 사용자/부서 (대상 환경에서는 같은 email/code, 다른 UUID로 다시 만듭니다)
+async fn seed_org(pool: &PgPool, suffix: &str) -> (Uuid, Uuid, Uuid) {
+    let users = UserRepository::new(pool.clone());
+    let mut ids = Vec::new();
+    for name in ["approver", "reference"] {
+        let user = users
+            .create(
+                format!("bundle_{}_{}@pxm.com", name, suffix),
+                "hash".into(),
+                name.into(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        ids.push(user.id);
+    }
+    let department_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO departments (id, name, code) VALUES ($1, $2, $2)")
+        .bind(department_id)
+        .bind(format!("BUNDLE-{suffix}"))
+        .execute(pool)
+        .await
+        .unwrap();
+    (ids[0], ids[1], department_id)
+}
+
+async fn drop_org(pool: &PgPool, users: &[Uuid], department_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(users)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM departments WHERE id = $1")
+        .bind(department_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+fn template_dto(
+    name: &str,
+    approver: Uuid,
+    references: Vec<Uuid>,
+    departments: Vec<Uuid>,
+) -> CreateTemplateDto {
+    CreateTemplateDto {
+        name: name.to_string(),
+        description: Some("bundle round trip".to_string()),
+        form_schema: serde_json::json!({ "fields": [{ "name": "amount", "type": "number" }] }),
+        workflow_snapshot: FlowProcess {
+            current_step: 1,
+            steps: vec![ApprovalStep {
+                seq: 1,
+                name: "Lead".to_string(),
+                approver_id: approver,
+                status: "pending".to_string(),
+                timestamp: None,
+            }],
+            references,
+        },
+        category_code: None,
+        title_pattern: None,
+        visible_department_ids: departments,
+    }
+}
+
+#[tokio::test]
+async fn test_export_fails_on_unknown_approver() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = TemplateRepository::new(pool.clone());
+
+    // 원본 환경에도 없는 결재자 UUID -> 번들에 UUID를 쓰지 않고 실패
+    let dangling = Uuid::new_v4();
+    let name = format!("Dangling {}", Uuid::new_v4().simple());
+    let template = repo
+        .create(template_dto(&name, dangling, vec![], vec![]))
+        .await
+        .unwrap();
+
+    let (status, body) = export(&pool, template.id).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let report: ExportReport = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report.unresolved.len(), 1);
+    assert_eq!(report.unresolved[0].path, "workflow.steps[0].approver");
+    assert_eq!(report.unresolved[0].identifier, dangling.to_string());
+
+    repo.delete(template.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_bundle_round_trip_into_clean_org() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = TemplateRepository::new(pool.clone());
+    let suffix = Uuid::new_v4().simple().to_string()[..8].to_string();
+
+    // 1. 원본 환경에서 템플릿 export
+    let (approver, reference, department_id) = seed_org(&pool, &suffix).await;
+    let name = format!("Bundle {}", suffix);
+    let source = repo
+        .create(template_dto(
+            &name,
+            approver,
+            vec![reference],
+            vec![department_id],
+        ))
+        .await
+        .unwrap();
+
+    let (status, body) = export(&pool, source.id).await;
+    assert_eq!(status, StatusCode::OK);
+    let bundle_json = String::from_utf8(body).unwrap();
+    let bundle: TemplateBundle = serde_json::from_str(&bundle_json).unwrap();
+    assert!(matches!(
+        &bundle.template.workflow.steps[0].approver,
+        PortableApprover::Email { email } if email == &format!("bundle_approver_{}@pxm.com", suffix)
+    ));
+    assert_eq!(
+        bundle.template.visible_departments,
+        vec![format!("BUNDLE-{suffix}")]
+    );
+    for id in [approver, reference, department_id] {
+        assert!(!bundle_json.contains(&id.to_string()));
+    }
+
+    // 2. 같은 이름의 템플릿이 있는 환경 -> 저장하지 않고 충돌 리포트
+    for dry_run in [true, false] {
+        let (status, report) = import(&pool, &bundle_json, dry_run).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(report.unresolved.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].path, "name");
+        assert_eq!(report.conflicts[0].existing_id, source.id);
+        assert!(report.template.is_none());
+    }
+
+    // 3. 깨끗한 대상 환경: 같은 email/code, 다른 UUID
+    repo.delete(source.id).await.unwrap();
+    drop_org(&pool, &[approver, reference], department_id).await;
+    let (new_approver, new_reference, new_department_id) = seed_org(&pool, &suffix).await;
+
+    let (status, report) = import(&pool, &bundle_json, true).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(report.template.is_none());
+    assert_eq!(report.resolved.len(), 3);
+
+    let (status, report) = import(&pool, &bundle_json, false).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(report.conflicts.is_empty());
+    let imported = report.template.unwrap();
+    assert_eq!(imported.name, name);
+    assert_eq!(imported.form_schema.0, source.form_schema.0);
+    let flow = &imported.workflow_snapshot.0;
+    assert_eq!(flow.steps[0].approver_id, new_approver);
+    assert_eq!(flow.references, vec![new_reference]);
+    assert_eq!(imported.visible_department_ids, vec![new_department_id]);
+
+    // 4. 대상 환경에 참조자가 없으면 해석 실패가 충돌보다 우선
+    drop_org(&pool, &[new_reference], Uuid::nil()).await;
+    let (status, report) = import(&pool, &bundle_json, false).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(report.unresolved.len(), 1);
+    assert_eq!(report.unresolved[0].path, "workflow.references[0]");
+    assert_eq!(report.conflicts.len(), 1);
+
+    repo.delete(imported.id).await.unwrap();
+    drop_org(&pool, &[new_approver], new_department_id).await;
+}
@@ -0,0 +1,242 @@
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use backend::domain::approval::FlowProcess;
+use backend::domain::role::{Permission, UserRoles};
+use backend::domain::template::{CreateTemplateDto, PatchTemplateDto};
+use backend::domain::user::AuthUser;
+use backend::establish_connection;
+use backend::handlers::template_handler::get_template;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::template_repository::TemplateRepository;
+use backend::repositories::user_repository::UserRepository;
+use dotenvy::dotenv;
+use std::env;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_archive_and_delete_guard() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let template_repo = TemplateRepository::new(pool.clone());
+    let approval_repo = ApprovalRepository::new(pool);
+
+    let flow_process = FlowProcess {
+        current_step: 1,
+        steps: vec![],
+        references: vec![],
+    };
+
+    // 1. 템플릿 생성
+    let template = template_repo
+        .create(CreateTemplateDto {
+            name: "Lifecycle Test Template".to_string(),
+            description: None,
+            form_schema: serde_json::json!({}),
+            workflow_snapshot: flow_process.clone(),
+            category_code: None,
+            title_pattern: None,
+            visible_department_ids: vec![],
+        })
+        .await
+        .expect("Failed to create template");
+    assert!(!template.is_archived);
+
+    // 2. 결재 요청에서 참조
+    approval_repo
+        .create_from_template(
+            template.id,
+            "From Template".to_string(),
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            flow_process,
+        )
+        .await
+        .expect("Failed to create approval request");
+    assert_eq!(template_repo.count_usages(template.id).await.unwrap(), 1);
+
+    // 3. 참조 중인 템플릿은 FK(RESTRICT)로 삭제 불가
+    assert!(template_repo.delete(template.id).await.is_err());
+
+    // 4. Archive 후 기본 목록에서 제외
+    let archived = template_repo
+        .archive(template.id)
+        .await
+        .unwrap()
+        .expect("Template should exist");
+    assert!(archived.is_archived);
+    assert!(archived.archived_at.is_some());
+
+    let visible = template_repo.find_all(false, None, None).await.unwrap();
+    assert!(visible.iter().all(|t| t.id != template.id));
+    let all = template_repo.find_all(true, None, None).await.unwrap();
+    assert!(all.iter().any(|t| t.id == template.id));
+}
+
+#[tokio::test]
+async fn test_department_scoped_visibility() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let template_repo = TemplateRepository::new(pool.clone());
+    let user_repo = UserRepository::new(pool.clone());
+
+    // 1. 부서 계층 준비: parent -> child, 그리고 무관한 other
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let (parent_id, child_id, other_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    for (id, code, parent) in [
+        (parent_id, format!("P-{suffix}"), None),
+        (child_id, format!("C-{suffix}"), Some(parent_id)),
+        (other_id, format!("O-{suffix}"), None),
+    ] {
+        sqlx::query("INSERT INTO departments (id, name, code, parent_id) VALUES ($1, $2, $2, $3)")
+            .bind(id)
+            .bind(code)
+            .bind(parent)
+            .execute(&pool)
+            .await
+            .expect("Failed to create department");
+    }
+
+    let child_member = user_repo
+        .create(
+            format!("child-{suffix}@pxm.com"),
+            "hash".to_string(),
+            "Child Member".to_string(),
+            None,
+            Some(child_id),
+        )
+        .await
+        .unwrap();
+    let other_member = user_repo
+        .create(
+            format!("other-{suffix}@pxm.com"),
+            "hash".to_string(),
+            "Other Member".to_string(),
+            None,
+            Some(other_id),
+        )
+        .await
+        .unwrap();
+
+    // 2. 상위 부서에만 공개된 템플릿
+    let template = template_repo
+        .create(CreateTemplateDto {
+            name: "Scoped Template".to_string(),
+            description: None,
+            form_schema: serde_json::json!({}),
+            workflow_snapshot: FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+            category_code: Some("HR".to_string()),
+            title_pattern: None,
+            visible_department_ids: vec![parent_id],
+        })
+        .await
+        .expect("Failed to create template");
+    assert_eq!(template.visible_department_ids, vec![parent_id]);
+
+    // 3. 하위 부서원은 볼 수 있고, 무관한 부서원은 볼 수 없음
+    assert!(
+        template_repo
+            .is_visible_to(template.id, child_member.id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !template_repo
+            .is_visible_to(template.id, other_member.id)
+            .await
+            .unwrap()
+    );
+
+    let listed = template_repo
+        .find_all(false, Some("HR".to_string()), Some(other_member.id))
+        .await
+        .unwrap();
+    assert!(listed.iter().all(|t| t.id != template.id));
+
+    // 4. 단건 조회도 같은 공개 범위 (template:manage는 예외)
+    let viewer = |id: Uuid, permissions: &[Permission]| {
+        Extension(AuthUser {
+            id,
+            department_id: None,
+            roles: UserRoles {
+                roles: vec![],
+                permissions: permissions.iter().map(|p| p.code().to_string()).collect(),
+            },
+            session_id: Uuid::new_v4(),
+        })
+    };
+    let fetch = |auth| get_template(Path(template.id), State(pool.clone()), auth);
+    assert!(fetch(viewer(child_member.id, &[])).await.is_ok());
+    assert_eq!(
+        fetch(viewer(other_member.id, &[])).await.unwrap_err(),
+        StatusCode::NOT_FOUND
+    );
+    assert!(
+        fetch(viewer(other_member.id, &[Permission::TemplateManage]))
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_patch_clears_category() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let template_repo = TemplateRepository::new(pool);
+
+    let template = template_repo
+        .create(CreateTemplateDto {
+            name: "Categorized Template".to_string(),
+            description: Some("HR only".to_string()),
+            form_schema: serde_json::json!({}),
+            workflow_snapshot: FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+            category_code: Some("HR".to_string()),
+            title_pattern: Some("{{template.name}} ({{date:%Y-%m-%d}})".to_string()),
+            visible_department_ids: vec![],
+        })
+        .await
+        .unwrap();
+    let patch =
+        |body: serde_json::Value| -> PatchTemplateDto { serde_json::from_value(body).unwrap() };
+
+    // 필드가 없으면 유지
+    let kept = template_repo
+        .patch(template.id, patch(serde_json::json!({ "name": "Renamed" })))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(kept.category_code.as_deref(), Some("HR"));
+    assert_eq!(kept.description.as_deref(), Some("HR only"));
+    assert!(kept.title_pattern.is_some());
+
+    // null이면 해제
+    let cleared = template_repo
+        .patch(
+            template.id,
+            patch(serde_json::json!({
+                "category_code": null,
+                "description": null,
+                "title_pattern": null,
+            })),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cleared.category_code, None);
+    assert_eq!(cleared.description, None);
+    assert_eq!(cleared.title_pattern, None);
+    assert_eq!(cleared.name, "Renamed");
+
+    template_repo.delete(template.id).await.unwrap();
+}
@@ -0,0 +1,48 @@
+use backend::domain::title_pattern::{TitleContext, TitlePattern, TitlePatternError};
+use chrono::TimeZone;
+use chrono_tz::Asia::Seoul;
+
+#[test]
+fn test_render_title_pattern() {
+    let pattern = TitlePattern::parse(
+        "[Expense] {{form.purpose}} - {{requester.full_name}} ({{date:%Y-%m-%d}})",
+    )
+    .expect("Pattern should be valid");
+
+    // UTC 2026-01-20 20:00 == KST 2026-01-21 05:00 (조직 타임존 기준 날짜가 사용되어야 함)
+    let now = chrono::Utc
+        .with_ymd_and_hms(2026, 1, 20, 20, 0, 0)
+        .unwrap()
+        .with_timezone(&Seoul);
+    let form_data = serde_json::json!({ "purpose": "Team Dinner", "amount": 120000 });
+
+    let title = pattern.render(&TitleContext {
+        form_data: &form_data,
+        requester_full_name: "Kim Manager",
+        requester_email: "kim@pxm.com",
+        requester_position: Some("Manager"),
+        department_name: Some("IT Development Team"),
+        department_code: Some("IT01"),
+        template_name: "Expense",
+        now,
+    });
+
+    assert_eq!(title, "[Expense] Team Dinner - Kim Manager (2026-01-21)");
+}
+
+#[test]
+fn test_reject_invalid_placeholders() {
+    assert_eq!(
+        TitlePattern::parse("{{requester.salary}}"),
+        Err(TitlePatternError::Unknown("requester.salary".to_string()))
+    );
+    assert_eq!(
+        TitlePattern::parse("{{form.purpose"),
+        Err(TitlePatternError::Unclosed(0))
+    );
+    assert!(matches!(
+        TitlePattern::parse("{{date:%Q}}"),
+        Err(TitlePatternError::InvalidDateFormat(_))
+    ));
+    assert!(TitlePattern::parse("{{ form.a.b }} {{date}} {{department.code}}").is_ok());
+}
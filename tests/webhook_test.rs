@@ -0,0 +1,253 @@
+use axum::{Router, body::Bytes, http::HeaderMap, http::StatusCode, routing::post};
+use backend::domain::approval::{ApprovalAction, ApprovalStep, FlowProcess};
+use backend::domain::template::CreateTemplateDto;
+use backend::domain::webhook::DeliveryStatus;
+use backend::establish_connection;
+use backend::repositories::approval_repository::ApprovalRepository;
+use backend::repositories::outbox_repository::OutboxRepository;
+use backend::repositories::template_repository::TemplateRepository;
+use backend::repositories::webhook_repository::WebhookRepository;
+use backend::services::outbox::EventConsumer;
+use backend::services::webhook::{
+    SIGNATURE_HEADER, TIMESTAMP_HEADER, WebhookConfig, WebhookConsumer, WebhookDispatcher, sign,
+};
+use dotenvy::dotenv;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+// 로컬 수신 서버: /ok는 200, /fail은 500
+async fn start_receiver() -> (String, Received) {
+    let received: Received = Arc::default();
+    let ok_received = received.clone();
+    let app = Router::new()
+        .route(
+            "/ok",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                ok_received.lock().unwrap().push((headers, body));
+                StatusCode::OK
+            }),
+        )
+        .route(
+            "/fail",
+            post(|| async { (StatusCode::INTERNAL_SERVER_ERROR, "boom") }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (base, received)
+}
+
+#[tokio::test]
+async fn test_webhook_filters_signing_retries_and_redelivery() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let approval_repo = ApprovalRepository::new(pool.clone());
+    let webhook_repo = WebhookRepository::new(pool.clone());
+    let (base, received) = start_receiver().await;
+
+    // 1. 템플릿 필터를 건 구독 2개: 승인 이벤트만(/ok), 모든 이벤트(/fail)
+    let flow_process = FlowProcess {
+        current_step: 1,
+        steps: vec![],
+        references: vec![],
+    };
+    let template = TemplateRepository::new(pool.clone())
+        .create(CreateTemplateDto {
+            name: "Webhook Template".to_string(),
+            description: None,
+            form_schema: serde_json::json!({}),
+            workflow_snapshot: flow_process.clone(),
+            category_code: None,
+            title_pattern: None,
+            visible_department_ids: vec![],
+        })
+        .await
+        .unwrap();
+    let admin = Uuid::new_v4();
+    let approved_only = webhook_repo
+        .create(
+            "ERP".to_string(),
+            format!("{base}/ok"),
+            "erp-secret-0123456789".to_string(),
+            vec!["approval.approved".to_string()],
+            Some(template.id),
+            admin,
+        )
+        .await
+        .unwrap();
+    let failing = webhook_repo
+        .create(
+            "HR".to_string(),
+            format!("{base}/fail"),
+            "hr-secret-0123456789".to_string(),
+            vec![],
+            Some(template.id),
+            admin,
+        )
+        .await
+        .unwrap();
+
+    // 2. 상신 + 승인 이벤트를 consumer에 전달 (다른 템플릿 문서의 이벤트는 무시)
+    //    같은 outbox 이벤트를 두 번 받아도 delivery는 한 번만 생성
+    let requester = Uuid::new_v4();
+    let approver = Uuid::new_v4();
+    let request = approval_repo
+        .create_from_template(
+            template.id,
+            "Webhook request".to_string(),
+            requester,
+            serde_json::json!({ "amount": 1000 }),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![ApprovalStep {
+                    seq: 1,
+                    name: "Lead".to_string(),
+                    approver_id: approver,
+                    status: "pending".to_string(),
+                    timestamp: None,
+                }],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    let mut acted = request.clone();
+    acted
+        .flow_process
+        .0
+        .handle_action(ApprovalAction::Approve, approver)
+        .unwrap();
+    acted.status = "approved".to_string();
+    approval_repo
+        .record_action(acted, approver, ApprovalAction::Approve, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let other = approval_repo
+        .create(
+            "Untemplated".to_string(),
+            requester,
+            serde_json::json!({}),
+            flow_process,
+        )
+        .await
+        .unwrap();
+
+    let consumer = WebhookConsumer::new(pool.clone());
+    let outbox_repo = OutboxRepository::new(pool.clone());
+    for approval_id in [request.id, request.id, other.id] {
+        for event in outbox_repo.find_by_approval(approval_id).await.unwrap() {
+            consumer.handle(&event).await.unwrap();
+        }
+    }
+
+    let ok_page = webhook_repo
+        .find_deliveries_page(approved_only.id, None, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(ok_page.total_count, 1);
+    let fail_page = webhook_repo
+        .find_deliveries_page(failing.id, None, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(fail_page.total_count, 2);
+
+    // 3. 전송: 성공 건은 서명 검증, 실패 건은 재시도 대기
+    let dispatcher = WebhookDispatcher::new(
+        pool.clone(),
+        WebhookConfig {
+            max_attempts: 2,
+            backoff_base: Duration::ZERO,
+            ..WebhookConfig::default()
+        },
+    );
+    dispatcher.deliver_due().await.unwrap();
+
+    {
+        let received = received.lock().unwrap();
+        let (headers, body) = received
+            .iter()
+            .find(|(_, body)| {
+                serde_json::from_slice::<serde_json::Value>(body).unwrap()["data"]["approval"]["id"]
+                    == serde_json::json!(request.id)
+            })
+            .expect("approved event delivered");
+        let timestamp: i64 = headers[TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("erp-secret-0123456789", timestamp, body)
+        );
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["type"], "approval.approved");
+    }
+
+    let delivered = webhook_repo
+        .find_deliveries_page(approved_only.id, Some(DeliveryStatus::Succeeded), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(delivered.total_count, 1);
+    assert_eq!(delivered.items[0].response_status, Some(200));
+
+    let retrying = webhook_repo
+        .find_deliveries_page(failing.id, Some(DeliveryStatus::Pending), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(retrying.total_count, 2);
+    assert!(
+        retrying
+            .items
+            .iter()
+            .all(|d| d.attempts == 1 && d.response_status == Some(500) && d.last_error.is_some())
+    );
+
+    // 4. max_attempts 도달 -> dead
+    dispatcher.deliver_due().await.unwrap();
+    let dead = webhook_repo
+        .find_deliveries_page(failing.id, Some(DeliveryStatus::Dead), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(dead.total_count, 2);
+
+    // 5. 수동 재전송: 시도 횟수 초기화 후 대기열로
+    let redelivered = webhook_repo
+        .redeliver(dead.items[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(redelivered.status, "pending");
+    assert_eq!(redelivered.attempts, 0);
+
+    assert!(webhook_repo.delete(approved_only.id).await.unwrap());
+    assert!(webhook_repo.delete(failing.id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_withdraw_only_pending_requests() {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).await;
+    let repo = ApprovalRepository::new(pool);
+
+    let request = repo
+        .create(
+            "Withdraw me".to_string(),
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            FlowProcess {
+                current_step: 1,
+                steps: vec![],
+                references: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    let withdrawn = repo.withdraw(request.id).await.unwrap().unwrap();
+    assert_eq!(withdrawn.status, "withdrawn");
+    assert!(repo.withdraw(request.id).await.unwrap().is_none());
+}